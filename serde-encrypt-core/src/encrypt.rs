@@ -1,5 +1,11 @@
 //! Encryption/Decryption implementations.
 
+pub mod aead;
 pub mod encrypted_message;
+pub mod plain_message_multi_recipient;
 pub mod plain_message_public_key;
+pub mod plain_message_sealed_box;
 pub mod plain_message_shared_key;
+pub mod plain_message_signed_public_key;
+pub mod public_key_backend;
+pub mod stream;
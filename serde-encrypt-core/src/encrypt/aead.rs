@@ -0,0 +1,106 @@
+//! AEAD (Authenticated Encryption with Associated Data) algorithms for shared-key encryption.
+
+use aes_gcm::Aes256Gcm as Aes256GcmCipher;
+use aes_gcm_siv::Aes256GcmSiv as Aes256GcmSivCipher;
+use alloc::{format, vec::Vec};
+use chacha20poly1305::{
+    aead::{Aead, NewAead, Payload},
+    XChaCha20Poly1305 as XChaCha20Poly1305Cipher,
+};
+
+use super::encrypted_message::Algorithm;
+use crate::error::Error;
+
+/// AEAD algorithm used by shared-key encryption.
+///
+/// EncryptedMessage always holds 24-byte nonce.
+/// Algorithms with shorter nonce use its first [NONCE_SIZE](Self::NONCE_SIZE) bytes.
+pub trait AeadAlgorithm {
+    /// Algorithm ID recorded in message header.
+    const ALGORITHM: Algorithm;
+
+    /// Nonce size in bytes (up to 24).
+    const NONCE_SIZE: usize;
+
+    /// Encrypt `payload` with 32-byte `key`.
+    ///
+    /// # Failures
+    ///
+    /// - [EncryptionError](crate::error::ErrorKind::EncryptionError) when failed to encrypt.
+    fn encrypt(key: &[u8], nonce: &[u8; 24], payload: Payload) -> Result<Vec<u8>, Error>;
+
+    /// Decrypt `payload` with 32-byte `key`.
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](crate::error::ErrorKind::DecryptionError) when failed to decrypt (including authentication failure).
+    fn decrypt(key: &[u8], nonce: &[u8; 24], payload: Payload) -> Result<Vec<u8>, Error>;
+}
+
+macro_rules! impl_aead_algorithm {
+    ($name:ident, $cipher:ty, $algorithm:expr, $nonce_size:expr, $display:expr) => {
+        impl AeadAlgorithm for $name {
+            const ALGORITHM: Algorithm = $algorithm;
+            const NONCE_SIZE: usize = $nonce_size;
+
+            fn encrypt(key: &[u8], nonce: &[u8; 24], payload: Payload) -> Result<Vec<u8>, Error> {
+                let cipher = <$cipher>::new(key.into());
+                cipher
+                    .encrypt(nonce[..Self::NONCE_SIZE].into(), payload)
+                    .map_err(|e| {
+                        Error::encryption_error(&format!(
+                            "failed to encrypt serialized data by {}: {:?}",
+                            $display, e
+                        ))
+                    })
+            }
+
+            fn decrypt(key: &[u8], nonce: &[u8; 24], payload: Payload) -> Result<Vec<u8>, Error> {
+                let cipher = <$cipher>::new(key.into());
+                cipher
+                    .decrypt(nonce[..Self::NONCE_SIZE].into(), payload)
+                    .map_err(|e| {
+                        Error::decryption_error(&format!(
+                            "error on decryption of {} cipher-text: {:?}",
+                            $display, e
+                        ))
+                    })
+            }
+        }
+    };
+}
+
+/// XChaCha20-Poly1305 (192-bit nonce). Default choice.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct XChaCha20Poly1305;
+impl_aead_algorithm!(
+    XChaCha20Poly1305,
+    XChaCha20Poly1305Cipher,
+    Algorithm::XChaCha20Poly1305,
+    24,
+    "XChaCha20"
+);
+
+/// AES-256-GCM (96-bit nonce).
+///
+/// Random 96-bit nonces should not be used for more than 2^32 messages under the same key.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Aes256Gcm;
+impl_aead_algorithm!(
+    Aes256Gcm,
+    Aes256GcmCipher,
+    Algorithm::Aes256Gcm,
+    12,
+    "AES-256-GCM"
+);
+
+/// AES-256-GCM-SIV (96-bit nonce), which is nonce-misuse-resistant.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Aes256GcmSiv;
+impl_aead_algorithm!(
+    Aes256GcmSiv,
+    Aes256GcmSivCipher,
+    Algorithm::Aes256GcmSiv,
+    12,
+    "AES-256-GCM-SIV"
+);
@@ -1,8 +1,17 @@
 //! Encrypted message structure.
 
+mod message_header;
+#[cfg(feature = "serde")]
+mod serde_impl;
+
+pub use message_header::{
+    Algorithm, EncryptionMode, MessageHeader, CURRENT_FORMAT_VERSION, FORMAT_VERSION_HEADERLESS,
+    FORMAT_VERSION_WITH_KEY_ID,
+};
+
 use core::convert::TryInto;
 
-use alloc::vec::Vec;
+use alloc::{format, vec::Vec};
 
 use crate::error::Error;
 
@@ -16,27 +25,84 @@ const NONCE_SIZE: usize = 24;
 /// Then, the receiver should deserialize it by `let encrypted_message = EncryptedMessage::deserialize(bin);`.
 ///
 /// This struct includes Nonce, which is internally used when a receiver decrypt the encrypted message.
+///
+/// # Versioned format
+///
+/// `serialize()` emits headerless `nonce || cipher-text` binary, which is called "version 0".
+///
+/// `serialize_versioned()` prepends a [MessageHeader](self::MessageHeader)
+/// (magic number, format version, algorithm ID, encryption mode and optional key ID) to it.
+/// `deserialize_versioned()` reads both the versioned binary and the version 0 binary,
+/// so that data already stored in version 0 stays readable after algorithms change.
+///
+/// The header is bound to the cipher-text as associated data.
+/// A message serialized by `serialize()` is decrypted with the header of the decrypting algorithm and mode,
+/// so messages with key ID must be stored by `serialize_versioned()`.
+///
+/// # serde support
+///
+/// With `serde` feature, this struct implements `serde::Serialize` / `serde::Deserialize` in versioned format.
+/// Human-readable formats (`is_human_readable()`, e.g. JSON, TOML) get base64 string and binary formats get bytes.
+///
+/// Note that inherent `serialize()` / `deserialize()` shadow them in method call syntax.
+/// Use `Serialize::serialize(&encrypted_message, serializer)` or `<EncryptedMessage as Deserialize>::deserialize(deserializer)` to call them directly.
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub struct EncryptedMessage {
     encrypted: Vec<u8>,
 
     /// XChaCha20 nonce (192-bit / 24-byte)
     nonce: [u8; NONCE_SIZE],
+
+    /// `None` for headerless (version 0) message.
+    header: Option<MessageHeader>,
 }
 
 impl EncryptedMessage {
     #[doc(hidden)]
     pub fn new(encrypted: Vec<u8>, nonce: [u8; 24]) -> Self {
-        Self { encrypted, nonce }
+        Self {
+            encrypted,
+            nonce,
+            header: None,
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn new_with_header(encrypted: Vec<u8>, nonce: [u8; 24], header: MessageHeader) -> Self {
+        Self {
+            encrypted,
+            nonce,
+            header: Some(header),
+        }
     }
 
     /// Serialize this encrypted message into binary in order to send it to a remote receiver.
+    ///
+    /// Header is not included. Use [serialize_versioned()](Self::serialize_versioned) to include it.
     pub fn serialize(mut self) -> Vec<u8> {
         let mut serialized: Vec<u8> = self.nonce.to_vec();
         serialized.append(&mut self.encrypted);
         serialized
     }
 
+    /// Serialize this encrypted message into binary with [MessageHeader](self::MessageHeader).
+    ///
+    /// A message without header (e.g. deserialized by [deserialize()](Self::deserialize)) is serialized in version 0 format,
+    /// which is the same as [serialize()](Self::serialize).
+    pub fn serialize_versioned(self) -> Vec<u8> {
+        self.to_versioned_vec()
+    }
+
+    /// Same binary as [serialize_versioned()](Self::serialize_versioned) without consuming this encrypted message.
+    pub fn to_versioned_vec(&self) -> Vec<u8> {
+        let header = self.header.map(|h| h.serialize()).unwrap_or_default();
+        let mut serialized = Vec::with_capacity(header.len() + NONCE_SIZE + self.encrypted.len());
+        serialized.extend_from_slice(&header);
+        serialized.extend_from_slice(&self.nonce);
+        serialized.extend_from_slice(&self.encrypted);
+        serialized
+    }
+
     /// Deserializer function for a receiver.
     ///
     /// # Failures
@@ -51,6 +117,7 @@ impl EncryptedMessage {
                 nonce: serialized_encrypted_message
                     .try_into()
                     .expect("length already checked"),
+                header: None,
             })
         } else {
             Err(Error::decryption_error(
@@ -59,6 +126,128 @@ impl EncryptedMessage {
         }
     }
 
+    /// Deserializer function for binary created by [serialize_versioned()](Self::serialize_versioned) or [serialize()](Self::serialize).
+    ///
+    /// Binary without magic number is read as version 0 (headerless) format.
+    /// So is binary whose header is malformed, since random nonce of version 0 binary may start with magic number.
+    ///
+    /// # Failures
+    ///
+    /// - [DeserializationError](crate::error::ErrorKind::DeserializationError) when:
+    ///   - header is truncated.
+    ///   - format version, algorithm ID or encryption mode ID is unknown.
+    /// - [DecryptionError](crate::error::ErrorKind::DecryptionError) when:
+    ///   - binary data does not have nonce.
+    pub fn deserialize_versioned(mut serialized_encrypted_message: Vec<u8>) -> Result<Self, Error> {
+        match MessageHeader::deserialize(&serialized_encrypted_message) {
+            Ok(Some(header)) => {
+                let body = serialized_encrypted_message.split_off(header.size());
+                let mut message = Self::deserialize(body)?;
+                message.header = Some(header);
+                Ok(message)
+            }
+            Ok(None) => Self::deserialize(serialized_encrypted_message),
+            // random nonce of version 0 binary may start with magic number
+            Err(e) => Self::deserialize(serialized_encrypted_message).map_err(|_| e),
+        }
+    }
+
+    /// Ref to header. `None` for headerless (version 0) message.
+    pub fn header(&self) -> Option<&MessageHeader> {
+        self.header.as_ref()
+    }
+
+    /// Format version.
+    /// [FORMAT_VERSION_HEADERLESS](self::FORMAT_VERSION_HEADERLESS) for headerless message.
+    pub fn version(&self) -> u8 {
+        self.header
+            .map(|h| h.version())
+            .unwrap_or(FORMAT_VERSION_HEADERLESS)
+    }
+
+    /// Checks if header (if any) tells this message was produced by `algorithm` in `mode`.
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](crate::error::ErrorKind::DecryptionError) when header mismatches.
+    #[doc(hidden)]
+    pub fn ensure_produced_by(
+        &self,
+        algorithm: Algorithm,
+        mode: EncryptionMode,
+    ) -> Result<(), Error> {
+        match self.header {
+            Some(header) if header.algorithm() != algorithm || header.mode() != mode => {
+                Err(Error::decryption_error(&format!(
+                    "message was encrypted by {:?} in {:?} mode but tried to decrypt by {:?} in {:?} mode",
+                    header.algorithm(),
+                    header.mode(),
+                    algorithm,
+                    mode
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Decrypts this message by `decrypt`, which is given associated data bound to header (`serialized header || aad`).
+    ///
+    /// - Message with header must be produced by `algorithm` in `mode`.
+    /// - Headerless (version 0) message is decrypted with the header of `algorithm` and `mode`
+    ///   (message serialized by [serialize()](Self::serialize)), and then with `aad` alone if `algorithm` and `mode`
+    ///   were available before message header was introduced (message made by serde-encrypt 0.7 or earlier).
+    /// - Message with header is retried as headerless on failure, because random nonce of version 0 binary
+    ///   starts with magic number (and is parsed as header) once in 2^32 messages.
+    ///
+    /// Error on the first attempt is returned if all attempts fail.
+    pub(crate) fn open<T, F>(
+        &self,
+        algorithm: Algorithm,
+        mode: EncryptionMode,
+        aad: &[u8],
+        decrypt: F,
+    ) -> Result<T, Error>
+    where
+        F: Fn(&EncryptedMessage, &[u8]) -> Result<T, Error>,
+    {
+        match self.header {
+            Some(header) => self
+                .ensure_produced_by(algorithm, mode)
+                .and_then(|_| decrypt(self, &header.bound_aad(aad)))
+                .or_else(|e| {
+                    self.as_headerless()
+                        .open_headerless(algorithm, mode, aad, &decrypt)
+                        .map_err(|_| e)
+                }),
+            None => self.open_headerless(algorithm, mode, aad, &decrypt),
+        }
+    }
+
+    fn open_headerless<T, F>(
+        &self,
+        algorithm: Algorithm,
+        mode: EncryptionMode,
+        aad: &[u8],
+        decrypt: &F,
+    ) -> Result<T, Error>
+    where
+        F: Fn(&EncryptedMessage, &[u8]) -> Result<T, Error>,
+    {
+        let header = MessageHeader::new(algorithm, mode);
+        decrypt(self, &header.bound_aad(aad)).or_else(|e| {
+            if available_before_header(algorithm, mode) {
+                decrypt(self, aad).map_err(|_| e)
+            } else {
+                Err(e)
+            }
+        })
+    }
+
+    /// This message read as version 0 binary: `header || nonce` is taken as nonce.
+    fn as_headerless(&self) -> Self {
+        Self::deserialize(self.to_versioned_vec()).expect("header || nonce is longer than nonce")
+    }
+
     /// Ref to XChaCha20 nonce (192-bit / 24-byte) used to create this encrypted message.
     pub fn nonce(&self) -> &[u8] {
         &self.nonce
@@ -76,6 +265,15 @@ impl EncryptedMessage {
     }
 }
 
+/// Whether serde-encrypt 0.7 or earlier, which did not bind header, produced messages by `algorithm` in `mode`.
+fn available_before_header(algorithm: Algorithm, mode: EncryptionMode) -> bool {
+    matches!(
+        (algorithm, mode),
+        (Algorithm::XChaCha20Poly1305, EncryptionMode::SharedKey)
+            | (Algorithm::XChaCha20Poly1305, EncryptionMode::PublicKey)
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use alloc::vec;
@@ -92,6 +290,99 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_versioned_serialization() -> Result<(), Error> {
+        let header = MessageHeader::new(Algorithm::XChaCha20Poly1305, EncryptionMode::SharedKey);
+        let encrypted_message =
+            EncryptedMessage::new_with_header(b"*ENCRYPTED*".to_vec(), [42u8; 24], header);
+        let bin = encrypted_message.clone().serialize_versioned();
+        assert!(bin.starts_with(b"SENC"));
+
+        let deserialized = EncryptedMessage::deserialize_versioned(bin)?;
+        assert_eq!(deserialized, encrypted_message);
+        assert_eq!(deserialized.version(), CURRENT_FORMAT_VERSION);
+        assert_eq!(deserialized.header(), Some(&header));
+        Ok(())
+    }
+
+    #[test]
+    fn test_versioned_serialization_with_key_id() -> Result<(), Error> {
+        let header = MessageHeader::new(Algorithm::XChaCha20Poly1305, EncryptionMode::SharedKey)
+            .with_key_id(0x01020304);
+        let encrypted_message =
+            EncryptedMessage::new_with_header(b"*ENCRYPTED*".to_vec(), [42u8; 24], header);
+        assert_eq!(
+            encrypted_message.to_versioned_vec(),
+            encrypted_message.clone().serialize_versioned()
+        );
+        let bin = encrypted_message.clone().serialize_versioned();
+        assert_eq!(&bin[..11], b"SENC\x02\x01\x01\x01\x02\x03\x04");
+
+        let deserialized = EncryptedMessage::deserialize_versioned(bin.clone())?;
+        assert_eq!(deserialized, encrypted_message);
+        assert_eq!(deserialized.version(), FORMAT_VERSION_WITH_KEY_ID);
+        assert_eq!(deserialized.header().unwrap().key_id(), Some(0x01020304));
+
+        let e = EncryptedMessage::deserialize_versioned(bin[..9].to_vec()).unwrap_err();
+        assert_eq!(e.kind(), &ErrorKind::DeserializationError);
+        Ok(())
+    }
+
+    #[test]
+    fn test_headerless_as_version_0() -> Result<(), Error> {
+        let header = MessageHeader::new(Algorithm::XChaCha20Poly1305, EncryptionMode::SharedKey);
+        let encrypted_message =
+            EncryptedMessage::new_with_header(b"*ENCRYPTED*".to_vec(), [42u8; 24], header);
+        let bin = encrypted_message.serialize();
+
+        let deserialized = EncryptedMessage::deserialize_versioned(bin)?;
+        assert_eq!(deserialized.version(), FORMAT_VERSION_HEADERLESS);
+        assert_eq!(deserialized.header(), None);
+        assert_eq!(deserialized.encrypted(), b"*ENCRYPTED*");
+        Ok(())
+    }
+
+    #[test]
+    fn test_deserialization_error_on_unknown_header() {
+        let mut bin = b"SENC".to_vec();
+        bin.extend_from_slice(&[FORMAT_VERSION_WITH_KEY_ID + 1, 1, 1]);
+        let e = EncryptedMessage::deserialize_versioned(bin).unwrap_err();
+        assert_eq!(e.kind(), &ErrorKind::DeserializationError);
+
+        let mut bin = b"SENC".to_vec();
+        bin.extend_from_slice(&[CURRENT_FORMAT_VERSION, 255, 1]);
+        let e = EncryptedMessage::deserialize_versioned(bin).unwrap_err();
+        assert_eq!(e.kind(), &ErrorKind::DeserializationError);
+
+        let e = EncryptedMessage::deserialize_versioned(b"SENC".to_vec()).unwrap_err();
+        assert_eq!(e.kind(), &ErrorKind::DeserializationError);
+    }
+
+    #[test]
+    fn test_unknown_header_as_version_0() -> Result<(), Error> {
+        // version 0 binary whose random nonce starts with magic number
+        let mut bin = b"SENC".to_vec();
+        bin.extend_from_slice(&[FORMAT_VERSION_WITH_KEY_ID + 1, 1, 1]);
+        bin.extend_from_slice(&[42u8; NONCE_SIZE]);
+
+        let deserialized = EncryptedMessage::deserialize_versioned(bin.clone())?;
+        assert_eq!(deserialized.version(), FORMAT_VERSION_HEADERLESS);
+        assert_eq!(deserialized.nonce(), &bin[..NONCE_SIZE]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_as_headerless() -> Result<(), Error> {
+        let header = MessageHeader::new(Algorithm::XChaCha20Poly1305, EncryptionMode::SharedKey);
+        let encrypted_message =
+            EncryptedMessage::new_with_header(b"*ENCRYPTED*".to_vec(), [42u8; 24], header);
+        let bin = encrypted_message.clone().serialize_versioned();
+
+        let headerless = encrypted_message.as_headerless();
+        assert_eq!(headerless, EncryptedMessage::deserialize(bin)?);
+        Ok(())
+    }
+
     #[test]
     fn test_decryption_error_on_no_nonce() {
         let bin = vec![42u8; NONCE_SIZE - 1];
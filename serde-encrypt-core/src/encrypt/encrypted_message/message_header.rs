@@ -0,0 +1,261 @@
+//! Self-describing header of EncryptedMessage.
+
+use alloc::{format, vec::Vec};
+use core::convert::TryInto;
+
+use crate::error::Error;
+
+/// Magic number at the head of versioned EncryptedMessage binary.
+pub(crate) const MAGIC: [u8; 4] = *b"SENC";
+
+/// Format version of headerless EncryptedMessage binary (`nonce || cipher-text`).
+pub const FORMAT_VERSION_HEADERLESS: u8 = 0;
+
+/// Format version written by [EncryptedMessage::serialize_versioned()](super::EncryptedMessage::serialize_versioned).
+pub const CURRENT_FORMAT_VERSION: u8 = 1;
+
+/// Format version written by [EncryptedMessage::serialize_versioned()](super::EncryptedMessage::serialize_versioned)
+/// for a message with key ID (see [MessageHeader::with_key_id()](MessageHeader::with_key_id)).
+pub const FORMAT_VERSION_WITH_KEY_ID: u8 = 2;
+
+/// Header size in bytes: `magic (4) || version (1) || algorithm (1) || mode (1)`.
+pub(crate) const HEADER_SIZE: usize = MAGIC.len() + 3;
+
+/// Key ID size in bytes following header of [FORMAT_VERSION_WITH_KEY_ID](self::FORMAT_VERSION_WITH_KEY_ID).
+const KEY_ID_SIZE: usize = 4;
+
+/// Algorithm which produced an EncryptedMessage.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[non_exhaustive]
+pub enum Algorithm {
+    /// XChaCha20-Poly1305 (also used inside ChaChaBox for public-key encryption).
+    XChaCha20Poly1305,
+    /// XChaCha20-Poly1305 with synthetic nonce derived by HMAC-SHA256 from plain-text and associated data (SIV construction).
+    XChaCha20Poly1305Siv,
+    /// AES-256-GCM.
+    Aes256Gcm,
+    /// AES-256-GCM-SIV.
+    Aes256GcmSiv,
+    /// HPKE (RFC 9180) base mode with DHKEM(X25519, HKDF-SHA256), HKDF-SHA256 and ChaCha20Poly1305.
+    HpkeBase,
+    /// HPKE (RFC 9180) auth mode with DHKEM(X25519, HKDF-SHA256), HKDF-SHA256 and ChaCha20Poly1305.
+    HpkeAuth,
+}
+
+impl Algorithm {
+    /// ID written in header.
+    pub fn id(&self) -> u8 {
+        match self {
+            Algorithm::XChaCha20Poly1305 => 1,
+            Algorithm::XChaCha20Poly1305Siv => 2,
+            Algorithm::Aes256Gcm => 3,
+            Algorithm::Aes256GcmSiv => 4,
+            Algorithm::HpkeBase => 5,
+            Algorithm::HpkeAuth => 6,
+        }
+    }
+
+    /// # Failures
+    ///
+    /// - [DeserializationError](crate::error::ErrorKind::DeserializationError) when `id` is unknown.
+    pub fn from_id(id: u8) -> Result<Self, Error> {
+        match id {
+            1 => Ok(Algorithm::XChaCha20Poly1305),
+            2 => Ok(Algorithm::XChaCha20Poly1305Siv),
+            3 => Ok(Algorithm::Aes256Gcm),
+            4 => Ok(Algorithm::Aes256GcmSiv),
+            5 => Ok(Algorithm::HpkeBase),
+            6 => Ok(Algorithm::HpkeAuth),
+            _ => Err(Error::deserialization_error(&format!(
+                "unknown algorithm ID in message header: {}",
+                id
+            ))),
+        }
+    }
+}
+
+/// Encryption mode which produced an EncryptedMessage.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[non_exhaustive]
+pub enum EncryptionMode {
+    /// Shared-key encryption.
+    SharedKey,
+    /// Shared-key deterministic encryption.
+    SharedKeyDeterministic,
+    /// Public-key encryption.
+    PublicKey,
+    /// Shared-key streaming encryption (STREAM construction).
+    SharedKeyStream,
+    /// Anonymous public-key encryption with ephemeral sender key (sealed box).
+    SealedBox,
+    /// Public-key encryption for multiple receivers (content key wrapped for each receiver).
+    MultiRecipient,
+    /// Public-key encryption of plain message signed by sender's Ed25519 key (sign-then-encrypt).
+    SignedPublicKey,
+    /// Shared-key encryption with key derived from password (salt and KDF parameters in cipher-text).
+    SharedKeyPassword,
+}
+
+impl EncryptionMode {
+    /// ID written in header.
+    pub fn id(&self) -> u8 {
+        match self {
+            EncryptionMode::SharedKey => 1,
+            EncryptionMode::SharedKeyDeterministic => 2,
+            EncryptionMode::PublicKey => 3,
+            EncryptionMode::SharedKeyStream => 4,
+            EncryptionMode::SealedBox => 5,
+            EncryptionMode::MultiRecipient => 6,
+            EncryptionMode::SignedPublicKey => 7,
+            EncryptionMode::SharedKeyPassword => 8,
+        }
+    }
+
+    /// # Failures
+    ///
+    /// - [DeserializationError](crate::error::ErrorKind::DeserializationError) when `id` is unknown.
+    pub fn from_id(id: u8) -> Result<Self, Error> {
+        match id {
+            1 => Ok(EncryptionMode::SharedKey),
+            2 => Ok(EncryptionMode::SharedKeyDeterministic),
+            3 => Ok(EncryptionMode::PublicKey),
+            4 => Ok(EncryptionMode::SharedKeyStream),
+            5 => Ok(EncryptionMode::SealedBox),
+            6 => Ok(EncryptionMode::MultiRecipient),
+            7 => Ok(EncryptionMode::SignedPublicKey),
+            8 => Ok(EncryptionMode::SharedKeyPassword),
+            _ => Err(Error::deserialization_error(&format!(
+                "unknown encryption mode ID in message header: {}",
+                id
+            ))),
+        }
+    }
+}
+
+/// Header describing how an EncryptedMessage was produced.
+///
+/// Serialized header is bound to the cipher-text as associated data (`serialized header || aad`),
+/// so that modified algorithm, mode or key ID makes decryption fail.
+///
+/// Header with key ID is written in [FORMAT_VERSION_WITH_KEY_ID](self::FORMAT_VERSION_WITH_KEY_ID):
+/// `magic (4) || version (1) || algorithm (1) || mode (1) || key ID (u32 BE)`.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct MessageHeader {
+    version: u8,
+    algorithm: Algorithm,
+    mode: EncryptionMode,
+    key_id: Option<u32>,
+}
+
+impl MessageHeader {
+    /// Constructor with [CURRENT_FORMAT_VERSION](self::CURRENT_FORMAT_VERSION).
+    pub fn new(algorithm: Algorithm, mode: EncryptionMode) -> Self {
+        Self {
+            version: CURRENT_FORMAT_VERSION,
+            algorithm,
+            mode,
+            key_id: None,
+        }
+    }
+
+    /// Records ID of the key which encrypted the message (e.g. in a key ring).
+    pub fn with_key_id(self, key_id: u32) -> Self {
+        Self {
+            version: FORMAT_VERSION_WITH_KEY_ID,
+            key_id: Some(key_id),
+            ..self
+        }
+    }
+
+    /// Format version.
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// Algorithm which produced the message.
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    /// Encryption mode which produced the message.
+    pub fn mode(&self) -> EncryptionMode {
+        self.mode
+    }
+
+    /// ID of the key which encrypted the message, if recorded.
+    pub fn key_id(&self) -> Option<u32> {
+        self.key_id
+    }
+
+    /// Serialized size in bytes.
+    pub(crate) fn size(&self) -> usize {
+        match self.key_id {
+            Some(_) => HEADER_SIZE + KEY_ID_SIZE,
+            None => HEADER_SIZE,
+        }
+    }
+
+    pub(crate) fn serialize(&self) -> Vec<u8> {
+        let mut serialized = MAGIC.to_vec();
+        serialized.push(self.version);
+        serialized.push(self.algorithm.id());
+        serialized.push(self.mode.id());
+        if let Some(key_id) = self.key_id {
+            serialized.extend_from_slice(&key_id.to_be_bytes());
+        }
+        serialized
+    }
+
+    /// Associated data given to AEAD: `serialized header || aad`.
+    pub(crate) fn bound_aad(&self, aad: &[u8]) -> Vec<u8> {
+        let mut bound = self.serialize();
+        bound.extend_from_slice(aad);
+        bound
+    }
+
+    /// Returns `None` if `serialized` does not start with the magic number (headerless binary).
+    ///
+    /// # Failures
+    ///
+    /// - [DeserializationError](crate::error::ErrorKind::DeserializationError) when:
+    ///   - header (including key ID) is truncated.
+    ///   - format version, algorithm ID or mode ID is unknown.
+    pub(crate) fn deserialize(serialized: &[u8]) -> Result<Option<Self>, Error> {
+        if !serialized.starts_with(&MAGIC) {
+            return Ok(None);
+        }
+        if serialized.len() < HEADER_SIZE {
+            return Err(Error::deserialization_error("message header is truncated"));
+        }
+
+        let version = serialized[MAGIC.len()];
+        let key_id = match version {
+            CURRENT_FORMAT_VERSION => None,
+            FORMAT_VERSION_WITH_KEY_ID => {
+                let key_id: [u8; KEY_ID_SIZE] = serialized
+                    .get(HEADER_SIZE..HEADER_SIZE + KEY_ID_SIZE)
+                    .ok_or_else(|| {
+                        Error::deserialization_error("key ID in message header is truncated")
+                    })?
+                    .try_into()
+                    .expect("KEY_ID_SIZE bytes");
+                Some(u32::from_be_bytes(key_id))
+            }
+            _ => {
+                return Err(Error::deserialization_error(&format!(
+                    "unsupported format version in message header: {}",
+                    version
+                )))
+            }
+        };
+        let algorithm = Algorithm::from_id(serialized[MAGIC.len() + 1])?;
+        let mode = EncryptionMode::from_id(serialized[MAGIC.len() + 2])?;
+
+        Ok(Some(Self {
+            version,
+            algorithm,
+            mode,
+            key_id,
+        }))
+    }
+}
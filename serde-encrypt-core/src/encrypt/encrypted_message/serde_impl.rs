@@ -0,0 +1,116 @@
+//! `serde::Serialize` / `serde::Deserialize` for EncryptedMessage (only `serde` feature).
+//!
+//! Human-readable formats get base64 string and binary formats get bytes,
+//! both of which are [versioned](super::EncryptedMessage::serialize_versioned) binary.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use super::EncryptedMessage;
+
+impl Serialize for EncryptedMessage {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let bin = self.to_versioned_vec();
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&base64::encode(bin))
+        } else {
+            serializer.serialize_bytes(&bin)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for EncryptedMessage {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bin = if deserializer.is_human_readable() {
+            deserializer.deserialize_str(EncryptedMessageVisitor)?
+        } else {
+            deserializer.deserialize_byte_buf(EncryptedMessageVisitor)?
+        };
+        EncryptedMessage::deserialize_versioned(bin).map_err(de::Error::custom)
+    }
+}
+
+/// Accepts base64 string, bytes and sequence of u8 (for binary formats without native bytes).
+struct EncryptedMessageVisitor;
+
+impl<'de> Visitor<'de> for EncryptedMessageVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("base64 string or bytes of encrypted message")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        base64::decode(v)
+            .map_err(|e| E::custom(alloc::format!("invalid base64 of encrypted message: {}", e)))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(v.to_vec())
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(v)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(b) = seq.next_element()? {
+            bytes.push(b);
+        }
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+
+    use crate::{
+        encrypt::encrypted_message::{Algorithm, EncryptionMode, MessageHeader},
+        error::Error,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_human_readable_base64() -> Result<(), Error> {
+        let header = MessageHeader::new(Algorithm::XChaCha20Poly1305, EncryptionMode::SharedKey);
+        let encrypted_message =
+            EncryptedMessage::new_with_header(b"*ENCRYPTED*".to_vec(), [42u8; 24], header);
+
+        let json = serde_json::to_string(&encrypted_message).unwrap();
+        let encoded: String = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            base64::decode(encoded).unwrap(),
+            encrypted_message.clone().serialize_versioned()
+        );
+
+        let deserialized: EncryptedMessage = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, encrypted_message);
+        Ok(())
+    }
+}
@@ -0,0 +1,311 @@
+//! Multi-recipient public-key encryption.
+
+use core::{convert::TryInto, ops::DerefMut};
+
+use crate::{
+    error::Error,
+    key::{
+        combined_key::ReceiverCombinedKey,
+        key_pair::{private_key::SenderPrivateKey, public_key::ReceiverPublicKey},
+    },
+    random::RngSingleton,
+};
+use alloc::vec::Vec;
+use chacha20poly1305::{aead::Payload, XNonce};
+use crypto_box::{aead::Aead, ChaChaBox, PublicKey, KEY_SIZE};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use super::{
+    aead::{AeadAlgorithm, XChaCha20Poly1305},
+    encrypted_message::{Algorithm, EncryptedMessage, EncryptionMode, MessageHeader},
+};
+
+/// Size of recipient key ID: first 8 bytes of SHA-256 of receiver's public key.
+pub const KEY_ID_SIZE: usize = 8;
+
+/// Size of a content key wrapped by ChaChaBox (32-byte key and 16-byte Poly1305 tag).
+pub const WRAPPED_KEY_SIZE: usize = KEY_SIZE + 16;
+
+/// Size of an entry in recipient table.
+pub const RECIPIENT_ENTRY_SIZE: usize = KEY_ID_SIZE + WRAPPED_KEY_SIZE;
+
+const RECIPIENT_COUNT_SIZE: usize = 4;
+
+/// Plain message structure serialized via serde.
+///
+/// Plain message is encrypted once by XChaCha20-Poly1305 under a random content key,
+/// and the content key is wrapped by ChaChaBox (sender's private key and each receiver's public key).
+/// EncryptedMessage holds:
+///
+/// ```text
+/// encrypted = recipient count (u32 BE) || recipient entries || content cipher-text
+/// recipient entry = key ID (8 bytes) || wrapped content key (48 bytes)
+/// ```
+///
+/// Recipient entries are sorted by key ID so that a receiver finds its entry by binary search.
+/// The whole recipient table is bound to content cipher-text as associated data (followed by message header and `aad`).
+pub trait PlainMessageMultiRecipientCore {
+    /// RNG singleton
+    type R: RngSingleton;
+
+    /// Constructor
+    fn new(plain_message: Vec<u8>) -> Self
+    where
+        Self: Sized;
+
+    /// Raw representation
+    fn into_vec(self) -> Vec<u8>;
+
+    /// Ref to raw representation
+    fn as_slice(&self) -> &[u8];
+
+    /// Encrypt into EncryptedMessage readable by each of `receiver_public_keys`.
+    fn encrypt(
+        &self,
+        sender_private_key: &SenderPrivateKey,
+        receiver_public_keys: &[&ReceiverPublicKey],
+    ) -> Result<EncryptedMessage, Error> {
+        self.encrypt_with_aad(sender_private_key, receiver_public_keys, &[])
+    }
+
+    /// Encrypt into EncryptedMessage readable by each of `receiver_public_keys`, binding associated data (`aad`) to the cipher-text.
+    ///
+    /// `aad` itself is neither encrypted nor included in EncryptedMessage.
+    /// The same `aad` must be given to [decrypt_with_aad()](Self::decrypt_with_aad).
+    ///
+    /// Duplicate receiver public keys get a single recipient entry.
+    ///
+    /// # Failures
+    ///
+    /// - [EncryptionError](crate::error::ErrorKind::EncryptionError) when:
+    ///   - `receiver_public_keys` is empty.
+    ///   - failed to encrypt plain message or to wrap content key.
+    fn encrypt_with_aad(
+        &self,
+        sender_private_key: &SenderPrivateKey,
+        receiver_public_keys: &[&ReceiverPublicKey],
+        aad: &[u8],
+    ) -> Result<EncryptedMessage, Error> {
+        if receiver_public_keys.is_empty() {
+            return Err(Error::encryption_error(
+                "multi-recipient encryption needs at least one receiver",
+            ));
+        }
+
+        let (content_key, nonce) = {
+            let mut rng = Self::R::instance();
+            let mut content_key = Zeroizing::new([0u8; KEY_SIZE]);
+            rng.deref_mut().fill_bytes(content_key.as_mut());
+            let nonce: XNonce = crypto_box::generate_nonce(rng.deref_mut());
+            (content_key, nonce)
+        };
+
+        let header =
+            MessageHeader::new(Algorithm::XChaCha20Poly1305, EncryptionMode::MultiRecipient);
+
+        let mut receivers: Vec<([u8; KEY_ID_SIZE], &PublicKey)> = receiver_public_keys
+            .iter()
+            .map(|pk| (key_id(pk.as_ref()), pk.as_ref()))
+            .collect();
+        receivers.sort_by(|(id_a, pk_a), (id_b, pk_b)| {
+            id_a.cmp(id_b).then(pk_a.as_bytes().cmp(pk_b.as_bytes()))
+        });
+        receivers.dedup_by(|(_, pk_a), (_, pk_b)| pk_a.as_bytes() == pk_b.as_bytes());
+
+        let recipient_count: u32 = receivers.len().try_into().map_err(|_| {
+            Error::encryption_error("too many receivers for multi-recipient encryption")
+        })?;
+
+        let mut table =
+            Vec::with_capacity(RECIPIENT_COUNT_SIZE + receivers.len() * RECIPIENT_ENTRY_SIZE);
+        table.extend_from_slice(&recipient_count.to_be_bytes());
+        for (id, receiver_public_key) in receivers {
+            let sender_box = ChaChaBox::new(receiver_public_key, sender_private_key.as_ref());
+            let wrapped_key = sender_box
+                .encrypt(
+                    &nonce,
+                    Payload {
+                        msg: content_key.as_ref(),
+                        aad: &id,
+                    },
+                )
+                .map_err(|_| {
+                    Error::encryption_error("failed to wrap content key into ChaChaBox")
+                })?;
+
+            table.extend_from_slice(&id);
+            table.extend_from_slice(&wrapped_key);
+        }
+
+        let encrypted_content = XChaCha20Poly1305::encrypt(
+            content_key.as_ref(),
+            &nonce.into(),
+            Payload {
+                msg: self.as_slice(),
+                aad: &bound_aad(&table, &header.bound_aad(aad)),
+            },
+        )?;
+
+        let mut encrypted = table;
+        encrypted.extend_from_slice(&encrypted_content);
+
+        Ok(EncryptedMessage::new_with_header(
+            encrypted,
+            nonce.into(),
+            header,
+        ))
+    }
+
+    /// Decrypt from EncryptedMessage
+    fn decrypt(
+        encrypted_message: &EncryptedMessage,
+        combined_key: &ReceiverCombinedKey,
+    ) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        Self::decrypt_with_aad(encrypted_message, combined_key, &[])
+    }
+
+    /// Decrypt from EncryptedMessage created by [encrypt_with_aad()](Self::encrypt_with_aad).
+    ///
+    /// Fails if `aad` differs from the one given on encryption.
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](crate::error::ErrorKind::DecryptionError) when:
+    ///   - recipient table is malformed.
+    ///   - receiver is not listed in recipient table.
+    ///   - failed to unwrap content key or to decrypt content (including `aad` mismatch).
+    fn decrypt_with_aad(
+        encrypted_message: &EncryptedMessage,
+        combined_key: &ReceiverCombinedKey,
+        aad: &[u8],
+    ) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        let serial_plain = encrypted_message.open(
+            Algorithm::XChaCha20Poly1305,
+            EncryptionMode::MultiRecipient,
+            aad,
+            |encrypted_message, header_bound_aad| {
+                decrypt_content(encrypted_message, combined_key, header_bound_aad)
+            },
+        )?;
+
+        Ok(Self::new(serial_plain))
+    }
+}
+
+/// Unwraps content key for receiver of `combined_key` and decrypts content.
+fn decrypt_content(
+    encrypted_message: &EncryptedMessage,
+    combined_key: &ReceiverCombinedKey,
+    header_bound_aad: &[u8],
+) -> Result<Vec<u8>, Error> {
+    let encrypted = encrypted_message.encrypted();
+    if encrypted.len() < RECIPIENT_COUNT_SIZE {
+        return Err(Error::decryption_error(
+            "multi-recipient message does not have recipient count",
+        ));
+    }
+    let recipient_count = u32::from_be_bytes(
+        encrypted[..RECIPIENT_COUNT_SIZE]
+            .try_into()
+            .expect("length already checked"),
+    ) as usize;
+    let table_len = recipient_count
+        .checked_mul(RECIPIENT_ENTRY_SIZE)
+        .and_then(|entries_len| entries_len.checked_add(RECIPIENT_COUNT_SIZE))
+        .filter(|table_len| *table_len <= encrypted.len())
+        .ok_or_else(|| {
+            Error::decryption_error("multi-recipient message has truncated recipient table")
+        })?;
+    let (table, encrypted_content) = encrypted.split_at(table_len);
+    let entries = &table[RECIPIENT_COUNT_SIZE..];
+
+    let nonce: &[u8; 24] = encrypted_message
+        .nonce()
+        .try_into()
+        .map_err(|_| Error::decryption_error("nonce must be 24-byte"))?;
+
+    let receiver_private_key = combined_key.receiver_private_key().as_ref();
+    let id = key_id(&receiver_private_key.public_key());
+    let receiver_box = ChaChaBox::new(
+        combined_key.sender_public_key().as_ref(),
+        receiver_private_key,
+    );
+
+    // Key IDs may collide, so every entry with the same ID is tried.
+    let content_key = find_entries(entries, &id)
+        .find_map(|wrapped_key| {
+            receiver_box
+                .decrypt(
+                    nonce[..].into(),
+                    Payload {
+                        msg: wrapped_key,
+                        aad: &id,
+                    },
+                )
+                .ok()
+                .map(Zeroizing::new)
+        })
+        .ok_or_else(|| {
+            Error::decryption_error(
+                "receiver is not listed in multi-recipient message or sender differs",
+            )
+        })?;
+
+    XChaCha20Poly1305::decrypt(
+        &content_key,
+        nonce,
+        Payload {
+            msg: encrypted_content,
+            aad: &bound_aad(table, header_bound_aad),
+        },
+    )
+}
+
+/// First [KEY_ID_SIZE](self::KEY_ID_SIZE) bytes of SHA-256 of `public_key`.
+fn key_id(public_key: &PublicKey) -> [u8; KEY_ID_SIZE] {
+    let digest = Sha256::digest(public_key.as_bytes());
+    digest[..KEY_ID_SIZE]
+        .try_into()
+        .expect("SHA-256 digest is longer than key ID")
+}
+
+/// Wrapped keys in `entries` (sorted by key ID) whose key ID is `id`.
+fn find_entries<'e>(
+    entries: &'e [u8],
+    id: &'e [u8; KEY_ID_SIZE],
+) -> impl Iterator<Item = &'e [u8]> {
+    let count = entries.len() / RECIPIENT_ENTRY_SIZE;
+    let entry = move |i: usize| &entries[i * RECIPIENT_ENTRY_SIZE..(i + 1) * RECIPIENT_ENTRY_SIZE];
+
+    // binary search of the first entry with key ID >= `id`
+    let (mut lo, mut hi) = (0, count);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if entry(mid)[..KEY_ID_SIZE] < id[..] {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+
+    (lo..count)
+        .map(entry)
+        .take_while(move |e| e[..KEY_ID_SIZE] == id[..])
+        .map(|e| &e[KEY_ID_SIZE..])
+}
+
+/// `recipient table || aad`
+fn bound_aad(table: &[u8], aad: &[u8]) -> Vec<u8> {
+    let mut bound = Vec::with_capacity(table.len() + aad.len());
+    bound.extend_from_slice(table);
+    bound.extend_from_slice(aad);
+    bound
+}
@@ -1,4 +1,4 @@
-//! Shared key encryption.
+//! Public key encryption.
 
 use core::ops::DerefMut;
 
@@ -8,16 +8,21 @@ use crate::{
     random::RngSingleton,
 };
 use alloc::vec::Vec;
-use chacha20poly1305::{aead::Payload, XNonce};
-use crypto_box::{aead::Aead, ChaChaBox};
+use chacha20poly1305::XNonce;
 
-use super::encrypted_message::EncryptedMessage;
+use super::{
+    encrypted_message::{EncryptedMessage, EncryptionMode},
+    public_key_backend::PublicKeyBackend,
+};
 
 /// Plain message structure serialized via serde.
 pub trait PlainMessagePublicKeyCore {
     /// RNG singleton
     type R: RngSingleton;
 
+    /// Construction of public-key encryption
+    type B: PublicKeyBackend;
+
     /// Constructor
     fn new(plain_message: Vec<u8>) -> Self
     where
@@ -31,28 +36,19 @@ pub trait PlainMessagePublicKeyCore {
 
     /// Encrypt into EncryptedMessage
     fn encrypt(&self, combined_key: &SenderCombinedKey) -> Result<EncryptedMessage, Error> {
-        let nonce = Self::generate_nonce();
-        let sender_box = ChaChaBox::new(
-            combined_key.receiver_public_key().as_ref(),
-            combined_key.sender_private_key().as_ref(),
-        );
-
-        // TODO https://github.com/laysakura/serde-encrypt/issues/19
-        let aad = b"".as_ref();
-
-        let encrypted = sender_box
-            .encrypt(
-                &nonce,
-                Payload {
-                    msg: self.as_slice(),
-                    aad,
-                },
-            )
-            .map_err(|_| {
-                Error::encryption_error("failed to encrypt serialized data into ChaChaBox")
-            })?;
+        self.encrypt_with_aad(combined_key, &[])
+    }
 
-        Ok(EncryptedMessage::new(encrypted, nonce.into()))
+    /// Encrypt into EncryptedMessage, binding associated data (`aad`) to the cipher-text.
+    ///
+    /// `aad` itself is neither encrypted nor included in EncryptedMessage.
+    /// The same `aad` must be given to [decrypt_with_aad()](Self::decrypt_with_aad).
+    fn encrypt_with_aad(
+        &self,
+        combined_key: &SenderCombinedKey,
+        aad: &[u8],
+    ) -> Result<EncryptedMessage, Error> {
+        Self::B::encrypt::<Self::R>(self.as_slice(), combined_key, aad)
     }
 
     /// Decrypt from EncryptedMessage
@@ -63,18 +59,26 @@ pub trait PlainMessagePublicKeyCore {
     where
         Self: Sized,
     {
-        let receiver_box = ChaChaBox::new(
-            combined_key.sender_public_key().as_ref(),
-            combined_key.receiver_private_key().as_ref(),
-        );
-
-        let nonce = encrypted_message.nonce();
-        let encrypted = encrypted_message.encrypted();
-
-        let serial_plain = receiver_box
-            .decrypt(nonce.into(), encrypted)
-            .map_err(|_| Error::decryption_error("error on decryption of ChaChaBox"))?;
+        Self::decrypt_with_aad(encrypted_message, combined_key, &[])
+    }
 
+    /// Decrypt from EncryptedMessage created by [encrypt_with_aad()](Self::encrypt_with_aad).
+    ///
+    /// Fails if `aad` differs from the one given on encryption.
+    fn decrypt_with_aad(
+        encrypted_message: &EncryptedMessage,
+        combined_key: &ReceiverCombinedKey,
+        aad: &[u8],
+    ) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        let serial_plain = encrypted_message.open(
+            Self::B::ALGORITHM,
+            EncryptionMode::PublicKey,
+            aad,
+            |encrypted_message, aad| Self::B::decrypt(encrypted_message, combined_key, aad),
+        )?;
         Ok(Self::new(serial_plain))
     }
 
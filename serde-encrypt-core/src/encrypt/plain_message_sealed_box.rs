@@ -0,0 +1,169 @@
+//! Anonymous public-key encryption (sealed box).
+
+use core::{convert::TryInto, ops::DerefMut};
+
+use crate::{
+    error::Error,
+    key::key_pair::{public_key::ReceiverPublicKey, ReceiverKeyPairCore},
+    random::RngSingleton,
+};
+use alloc::vec::Vec;
+use chacha20poly1305::{aead::Payload, XNonce};
+use crypto_box::{aead::Aead, ChaChaBox, PublicKey, SecretKey, KEY_SIZE};
+
+use super::encrypted_message::{Algorithm, EncryptedMessage, EncryptionMode, MessageHeader};
+
+/// Plain message structure serialized via serde.
+///
+/// Sender generates an ephemeral X25519 key-pair per message,
+/// so that it does not need (and receiver cannot learn) any long-term sender key.
+/// The ephemeral public key is prepended to cipher-text in EncryptedMessage:
+///
+/// ```text
+/// encrypted = ephemeral public key (32 bytes) || ChaChaBox cipher-text
+/// ```
+///
+/// Both the ephemeral public key and receiver's public key are bound to cipher-text as associated data
+/// (followed by message header and `aad`).
+pub trait PlainMessageSealedBoxCore {
+    /// RNG singleton
+    type R: RngSingleton;
+
+    /// Constructor
+    fn new(plain_message: Vec<u8>) -> Self
+    where
+        Self: Sized;
+
+    /// Raw representation
+    fn into_vec(self) -> Vec<u8>;
+
+    /// Ref to raw representation
+    fn as_slice(&self) -> &[u8];
+
+    /// Encrypt into EncryptedMessage
+    fn encrypt(&self, receiver_public_key: &ReceiverPublicKey) -> Result<EncryptedMessage, Error> {
+        self.encrypt_with_aad(receiver_public_key, &[])
+    }
+
+    /// Encrypt into EncryptedMessage, binding associated data (`aad`) to the cipher-text.
+    ///
+    /// `aad` itself is neither encrypted nor included in EncryptedMessage.
+    /// The same `aad` must be given to [decrypt_with_aad()](Self::decrypt_with_aad).
+    fn encrypt_with_aad(
+        &self,
+        receiver_public_key: &ReceiverPublicKey,
+        aad: &[u8],
+    ) -> Result<EncryptedMessage, Error> {
+        let (ephemeral_secret_key, nonce) = {
+            let mut rng = Self::R::instance();
+            let sk = SecretKey::generate(rng.deref_mut());
+            let nonce: XNonce = crypto_box::generate_nonce(rng.deref_mut());
+            (sk, nonce)
+        };
+        let ephemeral_public_key = ephemeral_secret_key.public_key();
+        let header = MessageHeader::new(Algorithm::XChaCha20Poly1305, EncryptionMode::SealedBox);
+
+        let sender_box = ChaChaBox::new(receiver_public_key.as_ref(), &ephemeral_secret_key);
+        let encrypted = sender_box
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: self.as_slice(),
+                    aad: &bound_aad(
+                        &ephemeral_public_key,
+                        receiver_public_key,
+                        &header.bound_aad(aad),
+                    ),
+                },
+            )
+            .map_err(|_| {
+                Error::encryption_error("failed to encrypt serialized data into sealed box")
+            })?;
+
+        let mut sealed = ephemeral_public_key.as_bytes().to_vec();
+        sealed.extend_from_slice(&encrypted);
+
+        Ok(EncryptedMessage::new_with_header(
+            sealed,
+            nonce.into(),
+            header,
+        ))
+    }
+
+    /// Decrypt from EncryptedMessage
+    fn decrypt<K>(
+        encrypted_message: &EncryptedMessage,
+        receiver_key_pair: &K,
+    ) -> Result<Self, Error>
+    where
+        Self: Sized,
+        K: ReceiverKeyPairCore,
+    {
+        Self::decrypt_with_aad(encrypted_message, receiver_key_pair, &[])
+    }
+
+    /// Decrypt from EncryptedMessage created by [encrypt_with_aad()](Self::encrypt_with_aad).
+    ///
+    /// Fails if `aad` differs from the one given on encryption.
+    fn decrypt_with_aad<K>(
+        encrypted_message: &EncryptedMessage,
+        receiver_key_pair: &K,
+        aad: &[u8],
+    ) -> Result<Self, Error>
+    where
+        Self: Sized,
+        K: ReceiverKeyPairCore,
+    {
+        let serial_plain = encrypted_message.open(
+            Algorithm::XChaCha20Poly1305,
+            EncryptionMode::SealedBox,
+            aad,
+            |encrypted_message, header_bound_aad| {
+                let sealed = encrypted_message.encrypted();
+                if sealed.len() < KEY_SIZE {
+                    return Err(Error::decryption_error(
+                        "sealed box does not have ephemeral public key",
+                    ));
+                }
+                let (ephemeral_public_key, encrypted) = sealed.split_at(KEY_SIZE);
+                let ephemeral_public_key: [u8; KEY_SIZE] = ephemeral_public_key
+                    .try_into()
+                    .expect("length already checked");
+                let ephemeral_public_key = PublicKey::from(ephemeral_public_key);
+
+                let receiver_box = ChaChaBox::new(
+                    &ephemeral_public_key,
+                    receiver_key_pair.private_key().as_ref(),
+                );
+                receiver_box
+                    .decrypt(
+                        encrypted_message.nonce().into(),
+                        Payload {
+                            msg: encrypted,
+                            aad: &bound_aad(
+                                &ephemeral_public_key,
+                                receiver_key_pair.public_key(),
+                                header_bound_aad,
+                            ),
+                        },
+                    )
+                    .map_err(|_| Error::decryption_error("error on decryption of sealed box"))
+            },
+        )?;
+
+        Ok(Self::new(serial_plain))
+    }
+}
+
+/// `ephemeral public key || receiver public key || aad`
+fn bound_aad(
+    ephemeral_public_key: &PublicKey,
+    receiver_public_key: &ReceiverPublicKey,
+    aad: &[u8],
+) -> Vec<u8> {
+    let mut bound = Vec::with_capacity(KEY_SIZE * 2 + aad.len());
+    bound.extend_from_slice(ephemeral_public_key.as_bytes());
+    bound.extend_from_slice(receiver_public_key.as_ref().as_bytes());
+    bound.extend_from_slice(aad);
+    bound
+}
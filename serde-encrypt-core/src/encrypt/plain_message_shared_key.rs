@@ -6,47 +6,56 @@ mod shared_key_deterministic_core;
 pub use shared_key_core::PlainMessageSharedKeyCore;
 pub use shared_key_deterministic_core::PlainMessageSharedKeyDeterministicCore;
 
-use super::encrypted_message::EncryptedMessage;
-use crate::{error::Error, key::as_shared_key::AsSharedKey};
-use alloc::{format, vec::Vec};
-use chacha20poly1305::{XChaCha20Poly1305, XNonce};
-use crypto_box::aead::{Aead, NewAead};
-
-/// Encrypt into EncryptedMessage
-fn encrypt<S>(
+use super::{
+    aead::AeadAlgorithm,
+    encrypted_message::{EncryptedMessage, MessageHeader},
+};
+use crate::error::Error;
+use alloc::vec::Vec;
+use chacha20poly1305::aead::Payload;
+use core::convert::TryInto;
+
+/// Encrypt into EncryptedMessage, binding `header` and `aad` to the cipher-text.
+fn encrypt<A>(
     plain_message: &[u8],
-    shared_key: &S,
-    nonce: XNonce,
+    aad: &[u8],
+    key: &[u8],
+    nonce: [u8; 24],
+    header: MessageHeader,
 ) -> Result<EncryptedMessage, Error>
 where
-    S: AsSharedKey,
+    A: AeadAlgorithm,
 {
-    let chacha = XChaCha20Poly1305::new(shared_key.to_chacha_key());
-
-    let encrypted = chacha.encrypt(&nonce, plain_message).map_err(|e| {
-        Error::encryption_error(&format!(
-            "failed to encrypt serialized data by XChaCha20: {:?}",
-            e
-        ))
-    })?;
-
-    Ok(EncryptedMessage::new(encrypted, nonce.into()))
+    let aad = header.bound_aad(aad);
+    let payload = Payload {
+        msg: plain_message,
+        aad: &aad,
+    };
+    let encrypted = A::encrypt(key, &nonce, payload)?;
+
+    Ok(EncryptedMessage::new_with_header(encrypted, nonce, header))
 }
 
-/// Decrypt from EncryptedMessage
-fn decrypt<S>(encrypted_message: &EncryptedMessage, shared_key: &S) -> Result<Vec<u8>, Error>
+/// Decrypt from EncryptedMessage.
+///
+/// `aad` must be bound to header by [EncryptedMessage::open()](super::encrypted_message::EncryptedMessage::open).
+fn decrypt<A>(
+    encrypted_message: &EncryptedMessage,
+    aad: &[u8],
+    key: &[u8],
+) -> Result<Vec<u8>, Error>
 where
-    S: AsSharedKey,
+    A: AeadAlgorithm,
 {
-    let nonce = encrypted_message.nonce();
-    let chacha = XChaCha20Poly1305::new(shared_key.to_chacha_key());
+    let nonce: &[u8; 24] = encrypted_message
+        .nonce()
+        .try_into()
+        .expect("EncryptedMessage always has 24-byte nonce");
 
-    let encrypted = encrypted_message.encrypted();
+    let payload = Payload {
+        msg: encrypted_message.encrypted(),
+        aad,
+    };
 
-    chacha.decrypt(nonce.into(), encrypted).map_err(|e| {
-        Error::decryption_error(&format!(
-            "error on decryption of XChaCha20 cipher-text: {:?}",
-            e
-        ))
-    })
+    A::decrypt(key, nonce, payload)
 }
@@ -1,11 +1,15 @@
 //! Shared key encryption.
 
-use crate::encrypt::encrypted_message::EncryptedMessage;
+use crate::encrypt::{
+    aead::AeadAlgorithm,
+    encrypted_message::{EncryptedMessage, EncryptionMode, MessageHeader},
+};
+use crate::key::password_key::{PasswordKdf, PasswordKeyHeader, PASSWORD_KEY_HEADER_SIZE};
 use crate::random::RngSingleton;
 use crate::{error::Error, key::as_shared_key::AsSharedKey};
 use alloc::vec::Vec;
-use chacha20poly1305::XNonce;
-use core::ops::DerefMut;
+use chacha20poly1305::{aead::Payload, XNonce};
+use core::{convert::TryInto, ops::DerefMut};
 
 use super::{decrypt, encrypt};
 
@@ -14,6 +18,9 @@ pub trait PlainMessageSharedKeyCore {
     /// RNG singleton
     type R: RngSingleton;
 
+    /// AEAD algorithm
+    type A: AeadAlgorithm;
+
     /// Constructor
     fn new(plain_message: Vec<u8>) -> Self
     where
@@ -27,11 +34,53 @@ pub trait PlainMessageSharedKeyCore {
 
     /// Encrypt into EncryptedMessage
     fn encrypt<S>(&self, shared_key: &S) -> Result<EncryptedMessage, Error>
+    where
+        S: AsSharedKey,
+    {
+        self.encrypt_with_aad(shared_key, &[])
+    }
+
+    /// Encrypt into EncryptedMessage, binding associated data (`aad`) to the cipher-text.
+    ///
+    /// `aad` itself is neither encrypted nor included in EncryptedMessage.
+    /// The same `aad` must be given to [decrypt_with_aad()](Self::decrypt_with_aad).
+    fn encrypt_with_aad<S>(&self, shared_key: &S, aad: &[u8]) -> Result<EncryptedMessage, Error>
     where
         S: AsSharedKey,
     {
         let nonce = Self::generate_nonce();
-        encrypt(self.as_slice(), shared_key, nonce)
+        let header = MessageHeader::new(Self::A::ALGORITHM, EncryptionMode::SharedKey);
+        encrypt::<Self::A>(
+            self.as_slice(),
+            aad,
+            shared_key.as_slice(),
+            nonce.into(),
+            header,
+        )
+    }
+
+    /// Encrypt into EncryptedMessage whose header records `key_id` of `shared_key` (e.g. ID in a key ring).
+    ///
+    /// Decrypted by [decrypt_with_aad()](Self::decrypt_with_aad) with the key of `key_id`.
+    fn encrypt_with_key_id<S>(
+        &self,
+        shared_key: &S,
+        key_id: u32,
+        aad: &[u8],
+    ) -> Result<EncryptedMessage, Error>
+    where
+        S: AsSharedKey,
+    {
+        let nonce = Self::generate_nonce();
+        let header =
+            MessageHeader::new(Self::A::ALGORITHM, EncryptionMode::SharedKey).with_key_id(key_id);
+        encrypt::<Self::A>(
+            self.as_slice(),
+            aad,
+            shared_key.as_slice(),
+            nonce.into(),
+            header,
+        )
     }
 
     /// Decrypt from EncryptedMessage
@@ -40,11 +89,131 @@ pub trait PlainMessageSharedKeyCore {
         Self: Sized,
         S: AsSharedKey,
     {
-        let plain = decrypt(encrypted_message, shared_key)?;
+        Self::decrypt_with_aad(encrypted_message, shared_key, &[])
+    }
+
+    /// Decrypt from EncryptedMessage created by [encrypt_with_aad()](Self::encrypt_with_aad).
+    ///
+    /// Fails if `aad` differs from the one given on encryption.
+    fn decrypt_with_aad<S>(
+        encrypted_message: &EncryptedMessage,
+        shared_key: &S,
+        aad: &[u8],
+    ) -> Result<Self, Error>
+    where
+        Self: Sized,
+        S: AsSharedKey,
+    {
+        let plain = encrypted_message.open(
+            Self::A::ALGORITHM,
+            EncryptionMode::SharedKey,
+            aad,
+            |encrypted_message, aad| {
+                decrypt::<Self::A>(encrypted_message, aad, shared_key.as_slice())
+            },
+        )?;
+        Ok(Self::new(plain))
+    }
+
+    /// Encrypt into EncryptedMessage by a key derived from `password`.
+    ///
+    /// Random salt and `kdf` parameters are stored in EncryptedMessage as [PasswordKeyHeader](crate::key::password_key::PasswordKeyHeader)
+    /// and authenticated as associated data (following message header):
+    ///
+    /// ```text
+    /// encrypted = password key header (29 bytes) || cipher-text
+    /// ```
+    ///
+    /// # Failures
+    ///
+    /// - [KeyDerivationError](crate::error::ErrorKind::KeyDerivationError) when `kdf` parameters are invalid.
+    /// - [EncryptionError](crate::error::ErrorKind::EncryptionError) when failed to encrypt.
+    fn encrypt_with_password(
+        &self,
+        password: &[u8],
+        kdf: &PasswordKdf,
+    ) -> Result<EncryptedMessage, Error> {
+        let key_header = PasswordKeyHeader::generate::<Self::R>(*kdf);
+        let key = key_header.derive_key(password)?;
+        let key_header = key_header.to_bytes();
+
+        let header = MessageHeader::new(Self::A::ALGORITHM, EncryptionMode::SharedKeyPassword);
+        let nonce: [u8; 24] = Self::generate_nonce().into();
+        let encrypted = Self::A::encrypt(
+            key.as_ref(),
+            &nonce,
+            Payload {
+                msg: self.as_slice(),
+                aad: &header.bound_aad(&key_header),
+            },
+        )?;
+
+        let mut with_key_header = Vec::with_capacity(key_header.len() + encrypted.len());
+        with_key_header.extend_from_slice(&key_header);
+        with_key_header.extend_from_slice(&encrypted);
+
+        Ok(EncryptedMessage::new_with_header(
+            with_key_header,
+            nonce,
+            header,
+        ))
+    }
+
+    /// Decrypt from EncryptedMessage created by [encrypt_with_password()](Self::encrypt_with_password).
+    ///
+    /// KDF parameters are read from `encrypted_message`, and rejected if their costs exceed upper bounds
+    /// (see [PasswordKdf](crate::key::password_key::PasswordKdf)) before key derivation allocates memory.
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](crate::error::ErrorKind::DecryptionError) when:
+    ///   - `password` is wrong or message is malformed or tampered.
+    ///   - KDF costs in message exceed upper bounds.
+    fn decrypt_with_password(
+        encrypted_message: &EncryptedMessage,
+        password: &[u8],
+    ) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        let plain = encrypted_message.open(
+            Self::A::ALGORITHM,
+            EncryptionMode::SharedKeyPassword,
+            &[],
+            |encrypted_message, bound_aad| {
+                let with_key_header = encrypted_message.encrypted();
+                if with_key_header.len() < PASSWORD_KEY_HEADER_SIZE {
+                    return Err(Error::decryption_error(
+                        "encrypted message is shorter than password key header",
+                    ));
+                }
+                let (key_header, encrypted) = with_key_header.split_at(PASSWORD_KEY_HEADER_SIZE);
+                let key = PasswordKeyHeader::from_bytes(key_header)
+                    .and_then(|h| h.derive_key(password))
+                    .map_err(|_| Error::decryption_error("malformed password key header"))?;
+
+                let nonce: &[u8; 24] = encrypted_message
+                    .nonce()
+                    .try_into()
+                    .expect("EncryptedMessage always has 24-byte nonce");
+                let mut aad = bound_aad.to_vec();
+                aad.extend_from_slice(key_header);
+                Self::A::decrypt(
+                    key.as_ref(),
+                    nonce,
+                    Payload {
+                        msg: encrypted,
+                        aad: &aad,
+                    },
+                )
+            },
+        )?;
         Ok(Self::new(plain))
     }
 
     /// Generate random nonce which is large enough (24-byte) to rarely conflict.
+    ///
+    /// AEAD algorithms with shorter nonce use its prefix.
     fn generate_nonce() -> XNonce {
         let mut rng = Self::R::instance();
         crypto_box::generate_nonce(rng.deref_mut())
@@ -1,16 +1,46 @@
 //! Shared key deterministic encryption.
 
+use core::convert::TryInto;
+
 use crate::{
-    encrypt::encrypted_message::EncryptedMessage, error::Error, key::as_shared_key::AsSharedKey,
+    encrypt::{
+        aead::XChaCha20Poly1305,
+        encrypted_message::{Algorithm, EncryptedMessage, EncryptionMode, MessageHeader},
+    },
+    error::Error,
+    key::as_shared_key::AsSharedKey,
 };
 use alloc::vec::Vec;
 use chacha20poly1305::XNonce;
+use hmac::{Hmac, Mac, NewMac};
+use sha2::Sha256;
+use subtle::ConstantTimeEq;
+use zeroize::{Zeroize, Zeroizing};
 
 use super::{decrypt, encrypt};
 
-const FIXED_NONCE: [u8; 24] = [255; 24];
+type HmacSha256 = Hmac<Sha256>;
+
+/// Nonce used by deterministic encryption before synthetic nonce was introduced.
+/// Only used to decrypt legacy cipher-text.
+const LEGACY_FIXED_NONCE: [u8; 24] = [255; 24];
+
+const MAC_KEY_LABEL: &[u8] = b"serde-encrypt deterministic nonce key";
+const ENC_KEY_LABEL: &[u8] = b"serde-encrypt deterministic encryption key";
 
 /// Plain message structure serialized via serde.
+///
+/// # Algorithm
+///
+/// Nonce-misuse-resistant deterministic encryption by synthetic IV (SIV) construction:
+///
+/// 1. Two subkeys are derived from shared key: `mac_key = HMAC-SHA256(shared_key, label_mac)`, `enc_key = HMAC-SHA256(shared_key, label_enc)`.
+/// 2. `nonce = HMAC-SHA256(mac_key, len(aad) || aad || plain_message)[..24]`.
+/// 3. `cipher_text = XChaCha20Poly1305(enc_key, nonce, plain_message, serialized_header || aad)`.
+///
+/// Same plain-text (and associated data) always produces the same cipher-text,
+/// while different plain-texts share a nonce only with probability of a collision in 192-bit truncated HMAC
+/// (about `q^2 / 2^193` for `q` distinct plain-texts under one key).
 pub trait PlainMessageSharedKeyDeterministicCore {
     /// Constructor
     fn new(plain_message: Vec<u8>) -> Self
@@ -28,8 +58,29 @@ pub trait PlainMessageSharedKeyDeterministicCore {
     where
         S: AsSharedKey,
     {
-        let nonce = Self::generate_nonce();
-        encrypt(self.as_slice(), shared_key, nonce)
+        self.encrypt_with_aad(shared_key, &[])
+    }
+
+    /// Encrypt into EncryptedMessage, binding associated data (`aad`) to the cipher-text.
+    ///
+    /// `aad` itself is neither encrypted nor included in EncryptedMessage.
+    /// The same `aad` must be given to [decrypt_with_aad()](Self::decrypt_with_aad).
+    fn encrypt_with_aad<S>(&self, shared_key: &S, aad: &[u8]) -> Result<EncryptedMessage, Error>
+    where
+        S: AsSharedKey,
+    {
+        let nonce = Self::generate_nonce(shared_key, self.as_slice(), aad);
+        let header = MessageHeader::new(
+            Algorithm::XChaCha20Poly1305Siv,
+            EncryptionMode::SharedKeyDeterministic,
+        );
+        encrypt::<XChaCha20Poly1305>(
+            self.as_slice(),
+            aad,
+            derive_key(shared_key, ENC_KEY_LABEL).as_ref(),
+            nonce.into(),
+            header,
+        )
     }
 
     /// Decrypt from EncryptedMessage
@@ -38,13 +89,83 @@ pub trait PlainMessageSharedKeyDeterministicCore {
         Self: Sized,
         S: AsSharedKey,
     {
-        let plain = decrypt(encrypted_message, shared_key)?;
+        Self::decrypt_with_aad(encrypted_message, shared_key, &[])
+    }
+
+    /// Decrypt from EncryptedMessage created by [encrypt_with_aad()](Self::encrypt_with_aad).
+    ///
+    /// Cipher-text encrypted with legacy fixed nonce is also decrypted.
+    ///
+    /// Fails if `aad` differs from the one given on encryption.
+    fn decrypt_with_aad<S>(
+        encrypted_message: &EncryptedMessage,
+        shared_key: &S,
+        aad: &[u8],
+    ) -> Result<Self, Error>
+    where
+        Self: Sized,
+        S: AsSharedKey,
+    {
+        if encrypted_message.nonce() == LEGACY_FIXED_NONCE {
+            encrypted_message.ensure_produced_by(
+                Algorithm::XChaCha20Poly1305,
+                EncryptionMode::SharedKeyDeterministic,
+            )?;
+            let plain =
+                decrypt::<XChaCha20Poly1305>(encrypted_message, aad, shared_key.as_slice())?;
+            return Ok(Self::new(plain));
+        }
+
+        let enc_key = derive_key(shared_key, ENC_KEY_LABEL);
+        let plain = encrypted_message.open(
+            Algorithm::XChaCha20Poly1305Siv,
+            EncryptionMode::SharedKeyDeterministic,
+            aad,
+            |encrypted_message, bound_aad| {
+                let mut plain =
+                    decrypt::<XChaCha20Poly1305>(encrypted_message, bound_aad, enc_key.as_ref())?;
+
+                let nonce = Self::generate_nonce(shared_key, &plain, aad);
+                if !bool::from(nonce[..].ct_eq(encrypted_message.nonce())) {
+                    plain.zeroize();
+                    return Err(Error::decryption_error(
+                        "synthetic nonce does not match decrypted plain-text",
+                    ));
+                }
+                Ok(plain)
+            },
+        )?;
+
         Ok(Self::new(plain))
     }
 
-    /// Generate fixed nonce to enable eq-match in cipher-text.
-    /// Note that is more vulnerable than generating random nonce (which [PlainMessageSharedKeyCore](crate::encrypt::plain_message_shared_key::PlainMessageSharedKeyCore) does).
-    fn generate_nonce() -> XNonce {
-        *XNonce::from_slice(&FIXED_NONCE)
+    /// Generate synthetic nonce from plain-text and associated data to enable eq-match in cipher-text.
+    ///
+    /// Unlike fixed nonce, different plain-texts (or associated data) lead to different nonces except with negligible probability.
+    fn generate_nonce<S>(shared_key: &S, plain_message: &[u8], aad: &[u8]) -> XNonce
+    where
+        S: AsSharedKey,
+    {
+        let mac_key = derive_key(shared_key, MAC_KEY_LABEL);
+        let mut mac =
+            HmacSha256::new_from_slice(mac_key.as_ref()).expect("HMAC accepts any key size");
+        mac.update(&(aad.len() as u64).to_le_bytes());
+        mac.update(aad);
+        mac.update(plain_message);
+        let tag = mac.finalize().into_bytes();
+
+        let nonce: [u8; 24] = tag[..24].try_into().expect("HMAC-SHA256 emits 32 bytes");
+        nonce.into()
     }
 }
+
+/// Derives a subkey from shared key for each purpose.
+fn derive_key<S>(shared_key: &S, label: &[u8]) -> Zeroizing<[u8; 32]>
+where
+    S: AsSharedKey,
+{
+    let mut mac =
+        HmacSha256::new_from_slice(shared_key.as_slice()).expect("HMAC accepts any key size");
+    mac.update(label);
+    Zeroizing::new(mac.finalize().into_bytes().into())
+}
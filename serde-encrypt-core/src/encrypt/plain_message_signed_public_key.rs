@@ -0,0 +1,222 @@
+//! Sign-then-encrypt public-key encryption.
+
+use core::ops::DerefMut;
+
+use crate::{
+    error::Error,
+    key::{
+        combined_key::{ReceiverCombinedKey, SenderCombinedKey},
+        signing_key_pair::{SigningKeyPairCore, VerifyingKey, SIGNATURE_LENGTH},
+    },
+    random::RngSingleton,
+};
+use alloc::vec::Vec;
+use chacha20poly1305::{aead::Payload, XNonce};
+use crypto_box::{aead::Aead, ChaChaBox, PublicKey};
+use zeroize::Zeroizing;
+
+use super::encrypted_message::{Algorithm, EncryptedMessage, EncryptionMode, MessageHeader};
+
+/// Domain separation tag at the head of signed data.
+const SIGNATURE_CONTEXT: &[u8] = b"serde-encrypt signed public-key v1";
+
+/// Plain message structure serialized via serde.
+///
+/// Sender signs plain message and its context by Ed25519 signing key, then encrypts signature and plain message by ChaChaBox:
+///
+/// ```text
+/// bound aad = serialized header || aad
+/// signed data = "serde-encrypt signed public-key v1" || sender public key || receiver public key || bound aad length (u64 BE) || bound aad || plain message
+/// encrypted = ChaChaBox(signature (64 bytes) || plain message), with bound aad as associated data
+/// ```
+///
+/// Binding both X25519 public keys to the signature prevents a receiver from re-encrypting a signed message to another receiver.
+pub trait PlainMessageSignedPublicKeyCore {
+    /// RNG singleton
+    type R: RngSingleton;
+
+    /// Constructor
+    fn new(plain_message: Vec<u8>) -> Self
+    where
+        Self: Sized;
+
+    /// Raw representation
+    fn into_vec(self) -> Vec<u8>;
+
+    /// Ref to raw representation
+    fn as_slice(&self) -> &[u8];
+
+    /// Sign and encrypt into EncryptedMessage
+    fn encrypt<K>(
+        &self,
+        combined_key: &SenderCombinedKey,
+        signing_key_pair: &K,
+    ) -> Result<EncryptedMessage, Error>
+    where
+        K: SigningKeyPairCore,
+    {
+        self.encrypt_with_aad(combined_key, signing_key_pair, &[])
+    }
+
+    /// Sign and encrypt into EncryptedMessage, binding associated data (`aad`) to the signature and cipher-text.
+    ///
+    /// `aad` itself is neither encrypted nor included in EncryptedMessage.
+    /// The same `aad` must be given to [decrypt_with_aad()](Self::decrypt_with_aad).
+    fn encrypt_with_aad<K>(
+        &self,
+        combined_key: &SenderCombinedKey,
+        signing_key_pair: &K,
+        aad: &[u8],
+    ) -> Result<EncryptedMessage, Error>
+    where
+        K: SigningKeyPairCore,
+    {
+        let sender_private_key = combined_key.sender_private_key().as_ref();
+        let receiver_public_key = combined_key.receiver_public_key().as_ref();
+
+        let header = MessageHeader::new(
+            Algorithm::XChaCha20Poly1305,
+            EncryptionMode::SignedPublicKey,
+        );
+        let aad = &header.bound_aad(aad);
+
+        let signed_data = Zeroizing::new(signed_data(
+            &sender_private_key.public_key(),
+            receiver_public_key,
+            aad,
+            self.as_slice(),
+        ));
+        let signature = signing_key_pair.sign(&signed_data);
+
+        let mut signed_plain =
+            Zeroizing::new(Vec::with_capacity(SIGNATURE_LENGTH + self.as_slice().len()));
+        signed_plain.extend_from_slice(&signature);
+        signed_plain.extend_from_slice(self.as_slice());
+
+        let nonce = Self::generate_nonce();
+        let sender_box = ChaChaBox::new(receiver_public_key, sender_private_key);
+        let encrypted = sender_box
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: &signed_plain,
+                    aad,
+                },
+            )
+            .map_err(|_| {
+                Error::encryption_error("failed to encrypt signed serialized data into ChaChaBox")
+            })?;
+
+        Ok(EncryptedMessage::new_with_header(
+            encrypted,
+            nonce.into(),
+            header,
+        ))
+    }
+
+    /// Decrypt from EncryptedMessage and verify its signature by `verifying_key`.
+    fn decrypt(
+        encrypted_message: &EncryptedMessage,
+        combined_key: &ReceiverCombinedKey,
+        verifying_key: &VerifyingKey,
+    ) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        Self::decrypt_with_aad(encrypted_message, combined_key, verifying_key, &[])
+    }
+
+    /// Decrypt from EncryptedMessage created by [encrypt_with_aad()](Self::encrypt_with_aad) and verify its signature by `verifying_key`.
+    ///
+    /// Fails if `aad` differs from the one given on encryption.
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](crate::error::ErrorKind::DecryptionError) when failed to decrypt (including `aad` mismatch).
+    /// - [SignatureError](crate::error::ErrorKind::SignatureError) when signature does not match `verifying_key`.
+    fn decrypt_with_aad(
+        encrypted_message: &EncryptedMessage,
+        combined_key: &ReceiverCombinedKey,
+        verifying_key: &VerifyingKey,
+        aad: &[u8],
+    ) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        let mut serial_plain = Zeroizing::new(encrypted_message.open(
+            Algorithm::XChaCha20Poly1305,
+            EncryptionMode::SignedPublicKey,
+            aad,
+            |encrypted_message, aad| {
+                decrypt_and_verify(encrypted_message, combined_key, verifying_key, aad)
+            },
+        )?);
+
+        Ok(Self::new(core::mem::take(&mut serial_plain)))
+    }
+
+    /// Generate random nonce which is large enough (24-byte) to rarely conflict.
+    fn generate_nonce() -> XNonce {
+        let mut rng = Self::R::instance();
+        crypto_box::generate_nonce(rng.deref_mut())
+    }
+}
+
+/// Decrypts signed plain message and verifies its signature.
+fn decrypt_and_verify(
+    encrypted_message: &EncryptedMessage,
+    combined_key: &ReceiverCombinedKey,
+    verifying_key: &VerifyingKey,
+    aad: &[u8],
+) -> Result<Vec<u8>, Error> {
+    let sender_public_key = combined_key.sender_public_key().as_ref();
+    let receiver_private_key = combined_key.receiver_private_key().as_ref();
+
+    let receiver_box = ChaChaBox::new(sender_public_key, receiver_private_key);
+    let mut signed_plain = Zeroizing::new(
+        receiver_box
+            .decrypt(
+                encrypted_message.nonce().into(),
+                Payload {
+                    msg: encrypted_message.encrypted(),
+                    aad,
+                },
+            )
+            .map_err(|_| Error::decryption_error("error on decryption of ChaChaBox"))?,
+    );
+    if signed_plain.len() < SIGNATURE_LENGTH {
+        return Err(Error::signature_error(
+            "decrypted message does not have signature",
+        ));
+    }
+
+    let mut serial_plain = Zeroizing::new(signed_plain.split_off(SIGNATURE_LENGTH));
+
+    let signed_data = Zeroizing::new(signed_data(
+        sender_public_key,
+        &receiver_private_key.public_key(),
+        aad,
+        &serial_plain,
+    ));
+    verifying_key.verify(&signed_data, &signed_plain)?;
+
+    Ok(core::mem::take(&mut serial_plain))
+}
+
+/// Data to be signed. See [PlainMessageSignedPublicKeyCore](self::PlainMessageSignedPublicKeyCore).
+fn signed_data(
+    sender_public_key: &PublicKey,
+    receiver_public_key: &PublicKey,
+    aad: &[u8],
+    plain: &[u8],
+) -> Vec<u8> {
+    let mut data =
+        Vec::with_capacity(SIGNATURE_CONTEXT.len() + 32 * 2 + 8 + aad.len() + plain.len());
+    data.extend_from_slice(SIGNATURE_CONTEXT);
+    data.extend_from_slice(sender_public_key.as_bytes());
+    data.extend_from_slice(receiver_public_key.as_bytes());
+    data.extend_from_slice(&(aad.len() as u64).to_be_bytes());
+    data.extend_from_slice(aad);
+    data.extend_from_slice(plain);
+    data
+}
@@ -0,0 +1,120 @@
+//! Backends (constructions) for public-key encryption.
+
+mod hpke;
+
+pub use hpke::{HpkeAuth, HpkeBase};
+
+use core::ops::DerefMut;
+
+use alloc::vec::Vec;
+use chacha20poly1305::{aead::Payload, XNonce};
+use crypto_box::{aead::Aead, ChaChaBox as ChaChaBoxCipher};
+
+use super::encrypted_message::{Algorithm, EncryptedMessage, EncryptionMode, MessageHeader};
+use crate::{
+    error::Error,
+    key::combined_key::{ReceiverCombinedKey, SenderCombinedKey},
+    random::RngSingleton,
+};
+
+/// Construction used by public-key encryption.
+pub trait PublicKeyBackend {
+    /// Algorithm ID recorded in message header.
+    const ALGORITHM: Algorithm;
+
+    /// Encrypt `plain_message` from sender to receiver of `combined_key`.
+    ///
+    /// Message header is bound to cipher-text as associated data (`serialized header || aad`).
+    ///
+    /// # Failures
+    ///
+    /// - [EncryptionError](crate::error::ErrorKind::EncryptionError) when failed to encrypt.
+    fn encrypt<R>(
+        plain_message: &[u8],
+        combined_key: &SenderCombinedKey,
+        aad: &[u8],
+    ) -> Result<EncryptedMessage, Error>
+    where
+        R: RngSingleton;
+
+    /// Decrypt `encrypted_message` by receiver of `combined_key`.
+    ///
+    /// `aad` is already bound to message header (`serialized header || aad`).
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](crate::error::ErrorKind::DecryptionError) when failed to decrypt (including authentication failure).
+    fn decrypt(
+        encrypted_message: &EncryptedMessage,
+        combined_key: &ReceiverCombinedKey,
+        aad: &[u8],
+    ) -> Result<Vec<u8>, Error>;
+}
+
+/// NaCl-style `crypto_box` construction: X25519 and XChaCha20-Poly1305 ([ChaChaBox](https://docs.rs/crypto_box/0.6.0/crypto_box/struct.ChaChaBox.html)).
+///
+/// Compatible with cipher-text made by earlier versions of serde-encrypt.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ChaChaBox;
+
+impl PublicKeyBackend for ChaChaBox {
+    const ALGORITHM: Algorithm = Algorithm::XChaCha20Poly1305;
+
+    fn encrypt<R>(
+        plain_message: &[u8],
+        combined_key: &SenderCombinedKey,
+        aad: &[u8],
+    ) -> Result<EncryptedMessage, Error>
+    where
+        R: RngSingleton,
+    {
+        let header = MessageHeader::new(Self::ALGORITHM, EncryptionMode::PublicKey);
+        let nonce: XNonce = {
+            let mut rng = R::instance();
+            crypto_box::generate_nonce(rng.deref_mut())
+        };
+        let sender_box = ChaChaBoxCipher::new(
+            combined_key.receiver_public_key().as_ref(),
+            combined_key.sender_private_key().as_ref(),
+        );
+
+        let encrypted = sender_box
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: plain_message,
+                    aad: &header.bound_aad(aad),
+                },
+            )
+            .map_err(|_| {
+                Error::encryption_error("failed to encrypt serialized data into ChaChaBox")
+            })?;
+
+        Ok(EncryptedMessage::new_with_header(
+            encrypted,
+            nonce.into(),
+            header,
+        ))
+    }
+
+    fn decrypt(
+        encrypted_message: &EncryptedMessage,
+        combined_key: &ReceiverCombinedKey,
+        aad: &[u8],
+    ) -> Result<Vec<u8>, Error> {
+        let receiver_box = ChaChaBoxCipher::new(
+            combined_key.sender_public_key().as_ref(),
+            combined_key.receiver_private_key().as_ref(),
+        );
+
+        receiver_box
+            .decrypt(
+                encrypted_message.nonce().into(),
+                Payload {
+                    msg: encrypted_message.encrypted(),
+                    aad,
+                },
+            )
+            .map_err(|_| Error::decryption_error("error on decryption of ChaChaBox"))
+    }
+}
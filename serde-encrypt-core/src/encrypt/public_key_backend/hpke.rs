@@ -0,0 +1,481 @@
+//! HPKE ([RFC 9180](https://www.rfc-editor.org/rfc/rfc9180.html)) single-shot encryption
+//! with DHKEM(X25519, HKDF-SHA256), HKDF-SHA256 and ChaCha20Poly1305.
+
+use core::ops::DerefMut;
+
+use alloc::vec::Vec;
+use chacha20poly1305::{
+    aead::{Aead, NewAead, Payload},
+    ChaCha20Poly1305,
+};
+use hkdf::{Hkdf, HkdfExtract};
+use rand::RngCore;
+use sha2::Sha256;
+use x25519_dalek::{x25519, X25519_BASEPOINT_BYTES};
+use zeroize::Zeroizing;
+
+use super::PublicKeyBackend;
+use crate::{
+    encrypt::encrypted_message::{Algorithm, EncryptedMessage, EncryptionMode, MessageHeader},
+    error::Error,
+    key::combined_key::{ReceiverCombinedKey, SenderCombinedKey},
+    random::RngSingleton,
+};
+
+const HPKE_VERSION: &[u8] = b"HPKE-v1";
+
+/// `"KEM" || I2OSP(kem_id, 2)` with DHKEM(X25519, HKDF-SHA256) (0x0020).
+const KEM_SUITE_ID: &[u8] = b"KEM\x00\x20";
+
+/// `"HPKE" || I2OSP(kem_id, 2) || I2OSP(kdf_id, 2) || I2OSP(aead_id, 2)` with HKDF-SHA256 (0x0001) and ChaCha20Poly1305 (0x0003).
+const HPKE_SUITE_ID: &[u8] = b"HPKE\x00\x20\x00\x01\x00\x03";
+
+const MODE_BASE: u8 = 0x00;
+const MODE_AUTH: u8 = 0x02;
+
+/// Nsecret, Nenc, Npk, Nsk, Nk and Nh of this suite.
+const N_32: usize = 32;
+/// Nn of ChaCha20Poly1305.
+const N_N: usize = 12;
+
+/// HPKE `info` given by serde-encrypt (empty).
+const INFO: &[u8] = b"";
+
+/// HPKE base mode (`mode_base`): DHKEM(X25519, HKDF-SHA256), HKDF-SHA256 and ChaCha20Poly1305.
+///
+/// Only receiver's key is used; sender's key in `SenderCombinedKey` / `ReceiverCombinedKey` is ignored.
+/// So this backend does **not** authenticate the sender; anyone who knows receiver's public key can encrypt a message.
+///
+/// EncryptedMessage holds `enc (32 bytes) || cipher-text` and an unused all-zero nonce.
+/// HPKE `info` is empty and `serialized header || aad` is passed as HPKE `aad`.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct HpkeBase;
+
+impl PublicKeyBackend for HpkeBase {
+    const ALGORITHM: Algorithm = Algorithm::HpkeBase;
+
+    fn encrypt<R>(
+        plain_message: &[u8],
+        combined_key: &SenderCombinedKey,
+        aad: &[u8],
+    ) -> Result<EncryptedMessage, Error>
+    where
+        R: RngSingleton,
+    {
+        let pk_r = combined_key.receiver_public_key().as_ref().as_bytes();
+        seal::<R>(Self::ALGORITHM, MODE_BASE, pk_r, None, plain_message, aad)
+    }
+
+    fn decrypt(
+        encrypted_message: &EncryptedMessage,
+        combined_key: &ReceiverCombinedKey,
+        aad: &[u8],
+    ) -> Result<Vec<u8>, Error> {
+        let sk_r = Zeroizing::new(combined_key.receiver_private_key().as_ref().to_bytes());
+        open(MODE_BASE, encrypted_message, &sk_r, None, aad)
+    }
+}
+
+/// HPKE auth mode (`mode_auth`): DHKEM(X25519, HKDF-SHA256), HKDF-SHA256 and ChaCha20Poly1305.
+///
+/// Sender's X25519 key-pair authenticates the sender as well as ChaChaBox does.
+///
+/// EncryptedMessage holds `enc (32 bytes) || cipher-text` and an unused all-zero nonce.
+/// HPKE `info` is empty and `serialized header || aad` is passed as HPKE `aad`.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct HpkeAuth;
+
+impl PublicKeyBackend for HpkeAuth {
+    const ALGORITHM: Algorithm = Algorithm::HpkeAuth;
+
+    fn encrypt<R>(
+        plain_message: &[u8],
+        combined_key: &SenderCombinedKey,
+        aad: &[u8],
+    ) -> Result<EncryptedMessage, Error>
+    where
+        R: RngSingleton,
+    {
+        let pk_r = combined_key.receiver_public_key().as_ref().as_bytes();
+        let sk_s = Zeroizing::new(combined_key.sender_private_key().as_ref().to_bytes());
+        seal::<R>(
+            Self::ALGORITHM,
+            MODE_AUTH,
+            pk_r,
+            Some(&sk_s),
+            plain_message,
+            aad,
+        )
+    }
+
+    fn decrypt(
+        encrypted_message: &EncryptedMessage,
+        combined_key: &ReceiverCombinedKey,
+        aad: &[u8],
+    ) -> Result<Vec<u8>, Error> {
+        let sk_r = Zeroizing::new(combined_key.receiver_private_key().as_ref().to_bytes());
+        let pk_s = combined_key.sender_public_key().as_ref().as_bytes();
+        open(MODE_AUTH, encrypted_message, &sk_r, Some(pk_s), aad)
+    }
+}
+
+fn seal<R>(
+    algorithm: Algorithm,
+    mode: u8,
+    pk_r: &[u8; N_32],
+    sk_s: Option<&[u8; N_32]>,
+    plain_message: &[u8],
+    aad: &[u8],
+) -> Result<EncryptedMessage, Error>
+where
+    R: RngSingleton,
+{
+    let mut ikm_e = Zeroizing::new([0u8; N_32]);
+    {
+        let mut rng = R::instance();
+        rng.deref_mut().fill_bytes(ikm_e.as_mut());
+    }
+
+    let header = MessageHeader::new(algorithm, EncryptionMode::PublicKey);
+    let (enc, context) = setup_s(mode, pk_r, sk_s, ikm_e.as_ref(), INFO)?;
+    let ct = context.seal(&header.bound_aad(aad), plain_message)?;
+
+    let mut encrypted = enc.to_vec();
+    encrypted.extend_from_slice(&ct);
+
+    Ok(EncryptedMessage::new_with_header(
+        encrypted, [0u8; 24], header,
+    ))
+}
+
+fn open(
+    mode: u8,
+    encrypted_message: &EncryptedMessage,
+    sk_r: &[u8; N_32],
+    pk_s: Option<&[u8; N_32]>,
+    aad: &[u8],
+) -> Result<Vec<u8>, Error> {
+    let encrypted = encrypted_message.encrypted();
+    if encrypted.len() < N_32 {
+        return Err(Error::decryption_error(
+            "HPKE message does not have encapsulated key",
+        ));
+    }
+    let (enc, ct) = encrypted.split_at(N_32);
+    let mut enc_array = [0u8; N_32];
+    enc_array.copy_from_slice(enc);
+
+    let context = setup_r(mode, &enc_array, sk_r, pk_s, INFO)?;
+    context.open(aad, ct)
+}
+
+/// Encryption context of single-shot API (sequence number is always 0).
+struct Context {
+    key: Zeroizing<[u8; N_32]>,
+    base_nonce: [u8; N_N],
+}
+
+impl Context {
+    fn seal(&self, aad: &[u8], pt: &[u8]) -> Result<Vec<u8>, Error> {
+        ChaCha20Poly1305::new(self.key.as_ref().into())
+            .encrypt((&self.base_nonce).into(), Payload { msg: pt, aad })
+            .map_err(|_| Error::encryption_error("failed to encrypt serialized data by HPKE"))
+    }
+
+    fn open(&self, aad: &[u8], ct: &[u8]) -> Result<Vec<u8>, Error> {
+        ChaCha20Poly1305::new(self.key.as_ref().into())
+            .decrypt((&self.base_nonce).into(), Payload { msg: ct, aad })
+            .map_err(|_| Error::decryption_error("error on decryption of HPKE"))
+    }
+}
+
+/// `SetupBaseS` / `SetupAuthS`. Returns `(enc, context)`.
+fn setup_s(
+    mode: u8,
+    pk_r: &[u8; N_32],
+    sk_s: Option<&[u8; N_32]>,
+    ikm_e: &[u8],
+    info: &[u8],
+) -> Result<([u8; N_32], Context), Error> {
+    let (sk_e, pk_e) = derive_key_pair(ikm_e);
+
+    let mut dh_value = Zeroizing::new(dh(&sk_e, pk_r)?.to_vec());
+    let mut kem_context = pk_e.to_vec();
+    kem_context.extend_from_slice(pk_r);
+    if let Some(sk_s) = sk_s {
+        dh_value.extend_from_slice(dh(sk_s, pk_r)?.as_ref());
+        kem_context.extend_from_slice(&public_key(sk_s));
+    }
+
+    let shared_secret = extract_and_expand(&dh_value, &kem_context);
+    Ok((pk_e, key_schedule(mode, shared_secret.as_ref(), info)))
+}
+
+/// `SetupBaseR` / `SetupAuthR`.
+fn setup_r(
+    mode: u8,
+    enc: &[u8; N_32],
+    sk_r: &[u8; N_32],
+    pk_s: Option<&[u8; N_32]>,
+    info: &[u8],
+) -> Result<Context, Error> {
+    let dh_error = |_| Error::decryption_error("invalid public key in HPKE message");
+
+    let mut dh_value = Zeroizing::new(dh(sk_r, enc).map_err(dh_error)?.to_vec());
+    let mut kem_context = enc.to_vec();
+    kem_context.extend_from_slice(&public_key(sk_r));
+    if let Some(pk_s) = pk_s {
+        dh_value.extend_from_slice(dh(sk_r, pk_s).map_err(dh_error)?.as_ref());
+        kem_context.extend_from_slice(pk_s);
+    }
+
+    let shared_secret = extract_and_expand(&dh_value, &kem_context);
+    Ok(key_schedule(mode, shared_secret.as_ref(), info))
+}
+
+fn key_schedule_context(mode: u8, info: &[u8]) -> Vec<u8> {
+    let psk_id_hash = labeled_extract(HPKE_SUITE_ID, b"", b"psk_id_hash", b"");
+    let info_hash = labeled_extract(HPKE_SUITE_ID, b"", b"info_hash", info);
+
+    let mut context = Vec::with_capacity(1 + N_32 * 2);
+    context.push(mode);
+    context.extend_from_slice(psk_id_hash.as_ref());
+    context.extend_from_slice(info_hash.as_ref());
+    context
+}
+
+fn key_schedule(mode: u8, shared_secret: &[u8], info: &[u8]) -> Context {
+    let context = key_schedule_context(mode, info);
+    let secret = labeled_extract(HPKE_SUITE_ID, shared_secret, b"secret", b"");
+
+    let mut key = Zeroizing::new([0u8; N_32]);
+    labeled_expand(
+        HPKE_SUITE_ID,
+        secret.as_ref(),
+        b"key",
+        &context,
+        key.as_mut(),
+    );
+    let mut base_nonce = [0u8; N_N];
+    labeled_expand(
+        HPKE_SUITE_ID,
+        secret.as_ref(),
+        b"base_nonce",
+        &context,
+        &mut base_nonce,
+    );
+
+    Context { key, base_nonce }
+}
+
+/// `DeriveKeyPair` of DHKEM(X25519, HKDF-SHA256). Returns `(sk, pk)`.
+fn derive_key_pair(ikm: &[u8]) -> (Zeroizing<[u8; N_32]>, [u8; N_32]) {
+    let dkp_prk = labeled_extract(KEM_SUITE_ID, b"", b"dkp_prk", ikm);
+    let mut sk = Zeroizing::new([0u8; N_32]);
+    labeled_expand(KEM_SUITE_ID, dkp_prk.as_ref(), b"sk", b"", sk.as_mut());
+    let pk = public_key(&sk);
+    (sk, pk)
+}
+
+fn public_key(sk: &[u8; N_32]) -> [u8; N_32] {
+    x25519(*sk, X25519_BASEPOINT_BYTES)
+}
+
+/// X25519 which rejects all-zero output (small-order public key).
+fn dh(sk: &[u8; N_32], pk: &[u8; N_32]) -> Result<Zeroizing<[u8; N_32]>, Error> {
+    let shared = Zeroizing::new(x25519(*sk, *pk));
+    if shared.iter().all(|b| *b == 0) {
+        return Err(Error::encryption_error(
+            "X25519 with small-order public key",
+        ));
+    }
+    Ok(shared)
+}
+
+fn extract_and_expand(dh_value: &[u8], kem_context: &[u8]) -> Zeroizing<[u8; N_32]> {
+    let eae_prk = labeled_extract(KEM_SUITE_ID, b"", b"eae_prk", dh_value);
+    let mut shared_secret = Zeroizing::new([0u8; N_32]);
+    labeled_expand(
+        KEM_SUITE_ID,
+        eae_prk.as_ref(),
+        b"shared_secret",
+        kem_context,
+        shared_secret.as_mut(),
+    );
+    shared_secret
+}
+
+fn labeled_extract(
+    suite_id: &[u8],
+    salt: &[u8],
+    label: &[u8],
+    ikm: &[u8],
+) -> Zeroizing<[u8; N_32]> {
+    let mut extract = HkdfExtract::<Sha256>::new(Some(salt));
+    extract.input_ikm(HPKE_VERSION);
+    extract.input_ikm(suite_id);
+    extract.input_ikm(label);
+    extract.input_ikm(ikm);
+    let (prk, _) = extract.finalize();
+    Zeroizing::new(prk.into())
+}
+
+fn labeled_expand(suite_id: &[u8], prk: &[u8], label: &[u8], info: &[u8], okm: &mut [u8]) {
+    let length = (okm.len() as u16).to_be_bytes();
+    Hkdf::<Sha256>::from_prk(prk)
+        .expect("PRK has hash length")
+        .expand_multi_info(&[&length, HPKE_VERSION, suite_id, label, info], okm)
+        .expect("output is shorter than 255 * hash length");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unhex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    /// RFC 9180 A.2.1: DHKEM(X25519, HKDF-SHA256), HKDF-SHA256, ChaCha20Poly1305 in base mode.
+    #[test]
+    fn test_rfc9180_base_mode_vector() {
+        let info = unhex("4f6465206f6e2061204772656369616e2055726e");
+        let ikm_e = unhex("909a9b35d3dc4713a5e72a4da274b55d3d3821a37e5d099e74a647db583a904b");
+        let ikm_r = unhex("1ac01f181fdf9f352797655161c58b75c656a6cc2716dcb66372da835542e1df");
+
+        let (sk_e, pk_e) = derive_key_pair(&ikm_e);
+        assert_eq!(
+            sk_e.to_vec(),
+            unhex("f4ec9b33b792c372c1d2c2063507b684ef925b8c75a42dbcbf57d63ccd381600")
+        );
+        assert_eq!(
+            pk_e.to_vec(),
+            unhex("1afa08d3dec047a643885163f1180476fa7ddb54c6a8029ea33f95796bf2ac4a")
+        );
+        let (sk_r, pk_r) = derive_key_pair(&ikm_r);
+        assert_eq!(
+            sk_r.to_vec(),
+            unhex("8057991eef8f1f1af18f4a9491d16a1ce333f695d4db8e38da75975c4478e0fb")
+        );
+        assert_eq!(
+            pk_r.to_vec(),
+            unhex("4310ee97d88cc1f088a5576c77ab0cf5c3ac797f3d95139c6c84b5429c59662a")
+        );
+
+        assert_eq!(
+            key_schedule_context(MODE_BASE, &info),
+            unhex("00431df6cd95e11ff49d7013563baf7f11588c75a6611ee2a4404a49306ae4cfc5b69c5718a60cc5876c358d3f7fc31ddb598503f67be58ea1e798c0bb19eb9796")
+        );
+
+        let (enc, sender_context) = setup_s(MODE_BASE, &pk_r, None, &ikm_e, &info).unwrap();
+        assert_eq!(enc, pk_e);
+        assert_eq!(
+            sender_context.key.to_vec(),
+            unhex("ad2744de8e17f4ebba575b3f5f5a8fa1f69c2a07f6e7500bc60ca6e3e3ec1c91")
+        );
+        assert_eq!(
+            sender_context.base_nonce.to_vec(),
+            unhex("5c4d98150661b848853b547f")
+        );
+
+        // sequence number 0
+        let pt = unhex("4265617574792069732074727574682c20747275746820626561757479");
+        let aad = unhex("436f756e742d30");
+        let ct = unhex("1c5250d8034ec2b784ba2cfd69dbdb8af406cfe3ff938e131f0def8c8b60b4db21993c62ce81883d2dd1b51a28");
+        assert_eq!(sender_context.seal(&aad, &pt).unwrap(), ct);
+
+        let receiver_context = setup_r(MODE_BASE, &enc, &sk_r, None, &info).unwrap();
+        assert_eq!(receiver_context.open(&aad, &ct).unwrap(), pt);
+    }
+
+    /// RFC 9180 A.2.3: DHKEM(X25519, HKDF-SHA256), HKDF-SHA256, ChaCha20Poly1305 in auth mode.
+    #[test]
+    fn test_rfc9180_auth_mode_vector() {
+        let info = unhex("4f6465206f6e2061204772656369616e2055726e");
+        let ikm_e = unhex("938d3daa5a8904540bc24f48ae90eed3f4f7f11839560597b55e7c9598c996c0");
+        let ikm_r = unhex("64835d5ee64aa7aad57c6f2e4f758f7696617f8829e70bc9ac7a5ef95d1c756c");
+        let ikm_s = unhex("9d8f94537d5a3ddef71234c0baedfad4ca6861634d0b94c3007fed557ad17df6");
+
+        let (sk_e, pk_e) = derive_key_pair(&ikm_e);
+        assert_eq!(
+            sk_e.to_vec(),
+            unhex("c94619e1af28971c8fa7957192b7e62a71ca2dcdde0a7cc4a8a9e741d600ab13")
+        );
+        assert_eq!(
+            pk_e.to_vec(),
+            unhex("f7674cc8cd7baa5872d1f33dbaffe3314239f6197ddf5ded1746760bfc847e0e")
+        );
+        let (sk_r, pk_r) = derive_key_pair(&ikm_r);
+        assert_eq!(
+            sk_r.to_vec(),
+            unhex("3ca22a6d1cda1bb9480949ec5329d3bf0b080ca4c45879c95eddb55c70b80b82")
+        );
+        assert_eq!(
+            pk_r.to_vec(),
+            unhex("1a478716d63cb2e16786ee93004486dc151e988b34b475043d3e0175bdb01c44")
+        );
+        let (sk_s, pk_s) = derive_key_pair(&ikm_s);
+        assert_eq!(
+            sk_s.to_vec(),
+            unhex("2def0cb58ffcf83d1062dd085c8aceca7f4c0c3fd05912d847b61f3e54121f05")
+        );
+        assert_eq!(
+            pk_s.to_vec(),
+            unhex("f0f4f9e96c54aeed3f323de8534fffd7e0577e4ce269896716bcb95643c8712b")
+        );
+
+        assert_eq!(
+            key_schedule_context(MODE_AUTH, &info),
+            unhex("02431df6cd95e11ff49d7013563baf7f11588c75a6611ee2a4404a49306ae4cfc5b69c5718a60cc5876c358d3f7fc31ddb598503f67be58ea1e798c0bb19eb9796")
+        );
+
+        let (enc, sender_context) = setup_s(MODE_AUTH, &pk_r, Some(&sk_s), &ikm_e, &info).unwrap();
+        assert_eq!(enc, pk_e);
+        assert_eq!(
+            sender_context.key.to_vec(),
+            unhex("b071fd1136680600eb447a845a967d35e9db20749cdf9ce098bcc4deef4b1356")
+        );
+        assert_eq!(
+            sender_context.base_nonce.to_vec(),
+            unhex("d20577dff16d7cea2c4bf780")
+        );
+
+        // sequence number 0
+        let pt = unhex("4265617574792069732074727574682c20747275746820626561757479");
+        let aad = unhex("436f756e742d30");
+        let ct = unhex("ab1a13c9d4f01a87ec3440dbd756e2677bd2ecf9df0ce7ed73869b98e00c09be111cb9fdf077347aeb88e61bdf");
+        assert_eq!(sender_context.seal(&aad, &pt).unwrap(), ct);
+
+        let receiver_context = setup_r(MODE_AUTH, &enc, &sk_r, Some(&pk_s), &info).unwrap();
+        assert_eq!(receiver_context.open(&aad, &ct).unwrap(), pt);
+    }
+
+    #[test]
+    fn test_auth_mode_binds_sender_key() {
+        let (sk_r, pk_r) = derive_key_pair(b"receiver");
+        let (sk_s, pk_s) = derive_key_pair(b"sender");
+        let (_, pk_other) = derive_key_pair(b"other");
+
+        let (enc, sender_context) =
+            setup_s(MODE_AUTH, &pk_r, Some(&sk_s), b"ephemeral", INFO).unwrap();
+        let ct = sender_context.seal(b"aad", b"plain").unwrap();
+
+        let receiver_context = setup_r(MODE_AUTH, &enc, &sk_r, Some(&pk_s), INFO).unwrap();
+        assert_eq!(receiver_context.open(b"aad", &ct).unwrap(), b"plain");
+
+        let receiver_context = setup_r(MODE_AUTH, &enc, &sk_r, Some(&pk_other), INFO).unwrap();
+        assert!(receiver_context.open(b"aad", &ct).is_err());
+
+        let receiver_context = setup_r(MODE_BASE, &enc, &sk_r, None, INFO).unwrap();
+        assert!(receiver_context.open(b"aad", &ct).is_err());
+    }
+
+    #[test]
+    fn test_small_order_public_key() {
+        let (sk_r, _) = derive_key_pair(b"receiver");
+        assert!(setup_r(MODE_BASE, &[0u8; N_32], &sk_r, None, INFO).is_err());
+    }
+}
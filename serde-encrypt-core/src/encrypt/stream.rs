@@ -0,0 +1,266 @@
+//! Shared-key streaming encryption for large payloads (STREAM construction).
+//!
+//! Plain-text is split into chunks of [CHUNK_SIZE](self::CHUNK_SIZE) bytes and each chunk is encrypted by XChaCha20-Poly1305
+//! with nonce `nonce prefix (19 bytes) || chunk counter (u32 big-endian) || last-chunk flag (1 byte)`.
+//!
+//! - Counter in nonce prevents chunks from being reordered, dropped or duplicated.
+//! - Last-chunk flag in nonce prevents stream from being truncated at a chunk boundary.
+//! - Stream header is bound to every chunk as associated data.
+//!
+//! # Stream format
+//!
+//! ```text
+//! message header (7 bytes) || nonce prefix (19 bytes) || chunk_0 || chunk_1 || ... || chunk_last
+//! ```
+//!
+//! Every chunk but the last has exactly [ENCRYPTED_CHUNK_SIZE](self::ENCRYPTED_CHUNK_SIZE) bytes.
+//! The last chunk has up to [ENCRYPTED_CHUNK_SIZE](self::ENCRYPTED_CHUNK_SIZE) bytes (16-byte tag only for empty plain-text).
+//!
+//! [StreamEncryptor](self::StreamEncryptor) and [StreamDecryptor](self::StreamDecryptor) are I/O agnostic.
+//! With `std` feature, [EncryptingWriter](self::EncryptingWriter) and [DecryptingReader](self::DecryptingReader)
+//! wrap them as `std::io::Write` and `std::io::Read`.
+
+#[cfg(feature = "std")]
+mod io;
+
+#[cfg(feature = "std")]
+pub use io::{DecryptingReader, EncryptingWriter};
+
+use alloc::{format, vec::Vec};
+use core::{convert::TryInto, ops::DerefMut};
+
+use chacha20poly1305::aead::Payload;
+use rand::RngCore;
+use zeroize::Zeroizing;
+
+use super::{
+    aead::{AeadAlgorithm, XChaCha20Poly1305},
+    encrypted_message::{Algorithm, EncryptionMode, MessageHeader},
+};
+use crate::{error::Error, key::as_shared_key::AsSharedKey, random::RngSingleton};
+
+/// Plain-text size of each chunk.
+pub const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Authentication tag size of each chunk.
+pub const TAG_SIZE: usize = 16;
+
+/// Cipher-text size of each chunk but the last.
+pub const ENCRYPTED_CHUNK_SIZE: usize = CHUNK_SIZE + TAG_SIZE;
+
+/// Nonce prefix size.
+pub const NONCE_PREFIX_SIZE: usize = 19;
+
+/// Stream header size: message header (7 bytes) and nonce prefix (19 bytes).
+pub const STREAM_HEADER_SIZE: usize = 7 + NONCE_PREFIX_SIZE;
+
+/// Chunk nonce generator and cipher shared by encryptor and decryptor.
+#[derive(Debug)]
+struct StreamCipher {
+    key: Zeroizing<[u8; 32]>,
+    /// Message header and nonce prefix. Bound to each chunk as associated data.
+    stream_header: [u8; STREAM_HEADER_SIZE],
+    counter: u32,
+    /// True after the last chunk.
+    finished: bool,
+}
+
+impl StreamCipher {
+    fn new<S>(shared_key: &S, stream_header: [u8; STREAM_HEADER_SIZE]) -> Self
+    where
+        S: AsSharedKey,
+    {
+        let key: [u8; 32] = shared_key
+            .as_slice()
+            .try_into()
+            .expect("shared key is 32 bytes");
+        Self {
+            key: Zeroizing::new(key),
+            stream_header,
+            counter: 0,
+            finished: false,
+        }
+    }
+
+    fn nonce(&self, last: bool) -> [u8; 24] {
+        let mut nonce = [0u8; 24];
+        nonce[..NONCE_PREFIX_SIZE].copy_from_slice(&self.stream_header[7..]);
+        nonce[NONCE_PREFIX_SIZE..23].copy_from_slice(&self.counter.to_be_bytes());
+        nonce[23] = last as u8;
+        nonce
+    }
+
+    /// Checks if next chunk can be processed.
+    fn ensure_next(&self) -> Result<(), &'static str> {
+        if self.finished {
+            Err("stream is already finished")
+        } else if self.counter == u32::MAX {
+            Err("too many chunks in a stream")
+        } else {
+            Ok(())
+        }
+    }
+
+    fn advance(&mut self, last: bool) {
+        self.counter += 1;
+        self.finished = last;
+    }
+}
+
+/// Encrypts a stream chunk by chunk.
+#[derive(Debug)]
+pub struct StreamEncryptor {
+    cipher: StreamCipher,
+}
+
+impl StreamEncryptor {
+    /// Constructor with random nonce prefix.
+    pub fn new<S>(shared_key: &S) -> Self
+    where
+        S: AsSharedKey,
+    {
+        let mut stream_header = [0u8; STREAM_HEADER_SIZE];
+        stream_header[..7].copy_from_slice(
+            &MessageHeader::new(
+                Algorithm::XChaCha20Poly1305,
+                EncryptionMode::SharedKeyStream,
+            )
+            .serialize(),
+        );
+        S::R::instance()
+            .deref_mut()
+            .fill_bytes(&mut stream_header[7..]);
+
+        Self {
+            cipher: StreamCipher::new(shared_key, stream_header),
+        }
+    }
+
+    /// Stream header to be written before chunks.
+    pub fn stream_header(&self) -> &[u8] {
+        &self.cipher.stream_header
+    }
+
+    /// Encrypts next chunk.
+    ///
+    /// `plain_chunk` must have exactly [CHUNK_SIZE](self::CHUNK_SIZE) bytes unless `last` is true,
+    /// and `last` must be true for the final chunk.
+    ///
+    /// # Failures
+    ///
+    /// - [EncryptionError](crate::error::ErrorKind::EncryptionError) when:
+    ///   - chunk size is invalid.
+    ///   - stream is already finished.
+    ///   - too many chunks (2^32 - 1) are encrypted.
+    pub fn encrypt_chunk(&mut self, plain_chunk: &[u8], last: bool) -> Result<Vec<u8>, Error> {
+        self.cipher.ensure_next().map_err(Error::encryption_error)?;
+        if plain_chunk.len() > CHUNK_SIZE || (!last && plain_chunk.len() != CHUNK_SIZE) {
+            return Err(Error::encryption_error(&format!(
+                "invalid plain-text chunk size: {}",
+                plain_chunk.len()
+            )));
+        }
+
+        let payload = Payload {
+            msg: plain_chunk,
+            aad: &self.cipher.stream_header,
+        };
+        let encrypted = XChaCha20Poly1305::encrypt(
+            self.cipher.key.as_ref(),
+            &self.cipher.nonce(last),
+            payload,
+        )?;
+        self.cipher.advance(last);
+        Ok(encrypted)
+    }
+
+    /// True after the last chunk is encrypted.
+    pub fn is_finished(&self) -> bool {
+        self.cipher.finished
+    }
+}
+
+/// Decrypts a stream chunk by chunk.
+#[derive(Debug)]
+pub struct StreamDecryptor {
+    cipher: StreamCipher,
+    /// True after a chunk failed to be decrypted. Following chunks are not decrypted.
+    failed: bool,
+}
+
+impl StreamDecryptor {
+    /// Constructor from stream header written by [StreamEncryptor](self::StreamEncryptor).
+    ///
+    /// # Failures
+    ///
+    /// - [DeserializationError](crate::error::ErrorKind::DeserializationError) when stream header is invalid.
+    pub fn new<S>(shared_key: &S, stream_header: &[u8]) -> Result<Self, Error>
+    where
+        S: AsSharedKey,
+    {
+        let stream_header: [u8; STREAM_HEADER_SIZE] = stream_header
+            .try_into()
+            .map_err(|_| Error::deserialization_error("stream header is truncated"))?;
+
+        let header = MessageHeader::deserialize(&stream_header)?.ok_or_else(|| {
+            Error::deserialization_error("stream header does not have magic number")
+        })?;
+        if header.algorithm() != Algorithm::XChaCha20Poly1305
+            || header.mode() != EncryptionMode::SharedKeyStream
+        {
+            return Err(Error::deserialization_error(&format!(
+                "not a stream encrypted by XChaCha20Poly1305 but {:?} in {:?} mode",
+                header.algorithm(),
+                header.mode()
+            )));
+        }
+
+        Ok(Self {
+            cipher: StreamCipher::new(shared_key, stream_header),
+            failed: false,
+        })
+    }
+
+    /// Decrypts next chunk.
+    ///
+    /// `last` must be true only for the final chunk of the stream.
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](crate::error::ErrorKind::DecryptionError) when:
+    ///   - chunk is tampered, reordered or truncated.
+    ///   - `last` does not match the one on encryption.
+    ///   - stream is already finished or a previous chunk failed to be decrypted.
+    pub fn decrypt_chunk(&mut self, encrypted_chunk: &[u8], last: bool) -> Result<Vec<u8>, Error> {
+        if self.failed {
+            return Err(Error::decryption_error(
+                "previous chunk failed to be decrypted",
+            ));
+        }
+        self.cipher.ensure_next().map_err(Error::decryption_error)?;
+
+        let payload = Payload {
+            msg: encrypted_chunk,
+            aad: &self.cipher.stream_header,
+        };
+        match XChaCha20Poly1305::decrypt(
+            self.cipher.key.as_ref(),
+            &self.cipher.nonce(last),
+            payload,
+        ) {
+            Ok(plain) => {
+                self.cipher.advance(last);
+                Ok(plain)
+            }
+            Err(e) => {
+                self.failed = true;
+                Err(e)
+            }
+        }
+    }
+
+    /// True after the last chunk is decrypted.
+    pub fn is_finished(&self) -> bool {
+        self.cipher.finished
+    }
+}
@@ -0,0 +1,180 @@
+//! `std::io::Write` / `std::io::Read` adapters of stream encryption (only `std` feature).
+
+use std::io::{self, Read, Write};
+
+use alloc::vec::Vec;
+use zeroize::{Zeroize, Zeroizing};
+
+use super::{
+    StreamDecryptor, StreamEncryptor, CHUNK_SIZE, ENCRYPTED_CHUNK_SIZE, STREAM_HEADER_SIZE,
+};
+use crate::{error::Error, key::as_shared_key::AsSharedKey};
+
+fn to_io_error(e: Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+/// `std::io::Write` adapter which encrypts everything written into it and writes cipher-text into inner writer.
+///
+/// [finish()](Self::finish) must be called after all data is written.
+/// Otherwise, the stream lacks its last chunk and [DecryptingReader](super::DecryptingReader) reports it as truncated.
+#[derive(Debug)]
+pub struct EncryptingWriter<W: Write> {
+    inner: W,
+    encryptor: StreamEncryptor,
+    header_written: bool,
+    /// Plain-text not yet encrypted (up to `CHUNK_SIZE` bytes).
+    buf: Zeroizing<Vec<u8>>,
+}
+
+impl<W: Write> EncryptingWriter<W> {
+    /// Constructor. Stream header is written into `inner` on the first write (or finish).
+    pub fn new<S>(inner: W, shared_key: &S) -> Self
+    where
+        S: AsSharedKey,
+    {
+        Self {
+            inner,
+            encryptor: StreamEncryptor::new(shared_key),
+            header_written: false,
+            buf: Zeroizing::new(Vec::with_capacity(CHUNK_SIZE)),
+        }
+    }
+
+    /// Encrypts remaining data as the last chunk, flushes and returns inner writer.
+    ///
+    /// # Failures
+    ///
+    /// - When inner writer fails.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.write_header()?;
+        let encrypted = self
+            .encryptor
+            .encrypt_chunk(&self.buf, true)
+            .map_err(to_io_error)?;
+        self.buf.zeroize();
+        self.inner.write_all(&encrypted)?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+
+    fn write_header(&mut self) -> io::Result<()> {
+        if !self.header_written {
+            self.inner.write_all(self.encryptor.stream_header())?;
+            self.header_written = true;
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for EncryptingWriter<W> {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        if data.is_empty() {
+            return Ok(0);
+        }
+        self.write_header()?;
+
+        // Full buffer is encrypted only when more data arrives, so that the last chunk is never empty unless whole stream is empty.
+        if self.buf.len() == CHUNK_SIZE {
+            let encrypted = self
+                .encryptor
+                .encrypt_chunk(&self.buf, false)
+                .map_err(to_io_error)?;
+            self.buf.zeroize();
+            self.inner.write_all(&encrypted)?;
+        }
+
+        let n = data.len().min(CHUNK_SIZE - self.buf.len());
+        self.buf.extend_from_slice(&data[..n]);
+        Ok(n)
+    }
+
+    /// Flushes inner writer. Buffered plain-text is not encrypted until a chunk fills up or [finish()](Self::finish) is called.
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// `std::io::Read` adapter which reads cipher-text from inner reader and returns decrypted plain-text.
+///
+/// Reading fails with `std::io::ErrorKind::InvalidData` when the stream is tampered, reordered or truncated.
+/// Plain-text of a chunk is returned only after the chunk is authenticated.
+#[derive(Debug)]
+pub struct DecryptingReader<R: Read> {
+    inner: R,
+    decryptor: StreamDecryptor,
+    /// Decrypted plain-text of the current chunk.
+    plain: Zeroizing<Vec<u8>>,
+    pos: usize,
+    /// First byte of the next chunk, read ahead to tell whether the current chunk is the last.
+    lookahead: Option<u8>,
+}
+
+impl<R: Read> DecryptingReader<R> {
+    /// Constructor. Reads stream header from `inner`.
+    ///
+    /// # Failures
+    ///
+    /// - When inner reader fails.
+    /// - When stream header is invalid.
+    pub fn new<S>(mut inner: R, shared_key: &S) -> io::Result<Self>
+    where
+        S: AsSharedKey,
+    {
+        let mut stream_header = [0u8; STREAM_HEADER_SIZE];
+        inner.read_exact(&mut stream_header)?;
+        let decryptor = StreamDecryptor::new(shared_key, &stream_header).map_err(to_io_error)?;
+
+        Ok(Self {
+            inner,
+            decryptor,
+            plain: Zeroizing::new(Vec::new()),
+            pos: 0,
+            lookahead: None,
+        })
+    }
+
+    /// Returns inner reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Reads and decrypts next chunk into `self.plain`.
+    fn read_chunk(&mut self) -> io::Result<()> {
+        let mut encrypted = Vec::with_capacity(ENCRYPTED_CHUNK_SIZE + 1);
+        encrypted.extend(self.lookahead.take());
+
+        let mut limited =
+            (&mut self.inner).take((ENCRYPTED_CHUNK_SIZE + 1 - encrypted.len()) as u64);
+        limited.read_to_end(&mut encrypted)?;
+
+        let last = encrypted.len() <= ENCRYPTED_CHUNK_SIZE;
+        if !last {
+            self.lookahead = encrypted.pop();
+        }
+
+        self.plain = Zeroizing::new(
+            self.decryptor
+                .decrypt_chunk(&encrypted, last)
+                .map_err(to_io_error)?,
+        );
+        self.pos = 0;
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for DecryptingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.pos == self.plain.len() {
+            if self.decryptor.is_finished() || buf.is_empty() {
+                return Ok(0);
+            }
+            self.read_chunk()?;
+        }
+
+        let n = buf.len().min(self.plain.len() - self.pos);
+        buf[..n].copy_from_slice(&self.plain[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
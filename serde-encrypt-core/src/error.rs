@@ -26,6 +26,13 @@ impl Display for Error {
 #[cfg(feature = "std")]
 impl std::error::Error for Error {}
 
+#[cfg(feature = "std")]
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Self::io_error(&alloc::format!("{}", e))
+    }
+}
+
 impl Error {
     /// Ref to error kind.
     pub fn kind(&self) -> &ErrorKind {
@@ -58,6 +65,26 @@ impl Error {
     pub fn decryption_error(reason: &str) -> Self {
         Self::new(ErrorKind::DecryptionError, reason)
     }
+
+    #[doc(hidden)]
+    pub fn signature_error(reason: &str) -> Self {
+        Self::new(ErrorKind::SignatureError, reason)
+    }
+
+    #[doc(hidden)]
+    pub fn key_derivation_error(reason: &str) -> Self {
+        Self::new(ErrorKind::KeyDerivationError, reason)
+    }
+
+    #[doc(hidden)]
+    pub fn key_error(reason: &str) -> Self {
+        Self::new(ErrorKind::KeyError, reason)
+    }
+
+    #[doc(hidden)]
+    pub fn io_error(reason: &str) -> Self {
+        Self::new(ErrorKind::IoError, reason)
+    }
 }
 
 #[cfg(test)]
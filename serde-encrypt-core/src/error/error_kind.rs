@@ -3,14 +3,25 @@
 use core::fmt::Display;
 
 /// Kinds of errors.
+///
+/// New kinds may be added in minor releases, so matches on it need a wildcard arm.
 #[allow(missing_docs)]
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[non_exhaustive]
 pub enum ErrorKind {
     SerializationError,
     DeserializationError,
 
     EncryptionError,
     DecryptionError,
+
+    SignatureError,
+
+    KeyDerivationError,
+
+    KeyError,
+
+    IoError,
 }
 
 impl Display for ErrorKind {
@@ -24,6 +35,14 @@ impl Display for ErrorKind {
                 "EncryptionError: Failed to encrypt serialized data to send"
             }
             ErrorKind::DecryptionError => "DecryptionError: Failed to decrypt data received",
+            ErrorKind::SignatureError => {
+                "SignatureError: Failed to sign data or to verify signature of data received"
+            }
+            ErrorKind::KeyDerivationError => {
+                "KeyDerivationError: Failed to derive key from password"
+            }
+            ErrorKind::KeyError => "KeyError: Invalid key or key configuration",
+            ErrorKind::IoError => "IoError: Failed to read or write data",
         };
         write!(f, "{}", s)
     }
@@ -4,5 +4,9 @@
 pub mod combined_key;
 #[deny(missing_docs)]
 pub mod key_pair;
+#[deny(missing_docs)]
+pub mod password_key;
+#[deny(missing_docs)]
+pub mod signing_key_pair;
 
 pub mod as_shared_key;
@@ -1,9 +1,15 @@
 //! Keys for common key cryptosystem.
 
-use crate::random::RngSingleton;
+use crate::{error::Error, key::password_key::PasswordKdf, random::RngSingleton};
 use chacha20poly1305::Key as ChaChaKey;
-use core::{convert::TryInto, ops::DerefMut};
+use core::ops::DerefMut;
+use hkdf::Hkdf;
 use rand::RngCore;
+use sha2::Sha256;
+use zeroize::{Zeroize, Zeroizing};
+
+/// Domain separation label at the head of HKDF `info` in [derive_subkey()](AsSharedKey::derive_subkey).
+const SUBKEY_INFO_PREFIX: &[u8] = b"serde-encrypt subkey v1";
 
 /// 32-byte key shared among sender and receiver secretly.
 ///
@@ -34,26 +40,52 @@ pub trait AsSharedKey {
     {
         let mut rng = Self::R::instance();
 
-        let r0 = rng.deref_mut().next_u64();
-        let r1 = rng.deref_mut().next_u64();
-        let r2 = rng.deref_mut().next_u64();
-        let r3 = rng.deref_mut().next_u64();
-
-        let key = [
-            r0.to_le_bytes(),
-            r1.to_le_bytes(),
-            r2.to_le_bytes(),
-            r3.to_le_bytes(),
-        ]
-        .concat()
-        .try_into()
-        .expect("must be 32 bytes");
-
-        Self::from_array(key)
+        let mut key = [0u8; 32];
+        rng.deref_mut().fill_bytes(&mut key);
+
+        let shared_key = Self::from_array(key);
+        key.zeroize();
+        shared_key
+    }
+
+    /// Derives key from `password` and `salt` by password-based key derivation function (Argon2id or scrypt).
+    ///
+    /// The same `password`, `salt` and `kdf` always derive the same key.
+    /// `salt` should be random and at least 16 bytes long; it is not secret and must be stored for later derivation
+    /// (see [PasswordKeyHeader](crate::key::password_key::PasswordKeyHeader)).
+    ///
+    /// # Failures
+    ///
+    /// - [KeyDerivationError](crate::error::ErrorKind::KeyDerivationError) when `kdf` parameters or `salt` are invalid (e.g. Argon2id requires 8-byte salt at least).
+    fn derive_from_password(password: &[u8], salt: &[u8], kdf: &PasswordKdf) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        let key = kdf.derive_key(password, salt)?;
+        Ok(Self::from_array(*key))
+    }
+
+    /// Derives subkey for a purpose (`context`) from this master key by HKDF-SHA256.
+    ///
+    /// The same master key and `context` always derive the same subkey, while subkeys for different `context`s are independent:
+    /// a leaked subkey reveals neither the master key nor other subkeys.
+    ///
+    /// ```text
+    /// subkey = HKDF-SHA256(IKM = master key, salt = none, info = "serde-encrypt subkey v1" || context, L = 32)
+    /// ```
+    fn derive_subkey(&self, context: &[u8]) -> Self
+    where
+        Self: Sized,
+    {
+        let hkdf = Hkdf::<Sha256>::new(None, self.as_slice());
+        let mut subkey = Zeroizing::new([0u8; 32]);
+        hkdf.expand_multi_info(&[SUBKEY_INFO_PREFIX, context], subkey.as_mut())
+            .expect("32 bytes is a valid length for HKDF-SHA256");
+        Self::from_array(*subkey)
     }
 
     /// Makes `chacha20poly1305::Key`
     fn to_chacha_key(&self) -> &ChaChaKey {
-        ChaChaKey::from_slice(self.as_slice())
+        self.as_slice().into()
     }
 }
@@ -1,6 +1,9 @@
 //! X25519 private-key.
+//!
+//! Private keys are zeroized on drop (by inner `crypto_box::SecretKey`) and their `Debug` output is redacted.
 
 use crypto_box::SecretKey;
+use zeroize::Zeroize;
 
 /// Message sender's private key
 #[derive(Clone, Debug)]
@@ -18,6 +21,13 @@ impl From<SecretKey> for SenderPrivateKey {
     }
 }
 
+impl Zeroize for SenderPrivateKey {
+    fn zeroize(&mut self) {
+        // replaced SecretKey is zeroized on drop
+        self.0 = SecretKey::from([0u8; 32]);
+    }
+}
+
 /// Message receiver's private key
 #[derive(Clone, Debug)]
 pub struct ReceiverPrivateKey(SecretKey);
@@ -33,3 +43,10 @@ impl From<SecretKey> for ReceiverPrivateKey {
         Self(s)
     }
 }
+
+impl Zeroize for ReceiverPrivateKey {
+    fn zeroize(&mut self) {
+        // replaced SecretKey is zeroized on drop
+        self.0 = SecretKey::from([0u8; 32]);
+    }
+}
@@ -0,0 +1,404 @@
+//! Password-based key derivation (Argon2id or scrypt) for shared key.
+
+use core::{convert::TryInto, ops::DerefMut};
+
+use crate::{error::Error, random::RngSingleton};
+use alloc::format;
+use argon2::{Argon2, Params as Argon2Params, Version};
+use rand::RngCore;
+use scrypt::Params as ScryptParams;
+use zeroize::Zeroizing;
+
+/// Salt size in bytes generated by [PasswordKeyHeader::generate()](PasswordKeyHeader::generate).
+pub const SALT_SIZE: usize = 16;
+
+/// Size of [PasswordKeyHeader](PasswordKeyHeader) in bytes: `KDF ID (1) || 3 parameters (4 each, BE) || salt (16)`.
+pub const PASSWORD_KEY_HEADER_SIZE: usize = 1 + 4 * 3 + SALT_SIZE;
+
+/// Upper bound of memory (in bytes) used by a KDF: 1 GiB.
+///
+/// Costs are read from untrusted [PasswordKeyHeader](PasswordKeyHeader) on decryption,
+/// so KDF with larger costs is rejected before it allocates memory or burns CPU.
+pub const MAX_KDF_MEMORY: u64 = 1 << 30;
+
+/// Upper bound of Argon2id `t_cost`.
+pub const MAX_ARGON2ID_T_COST: u32 = 16;
+
+/// Upper bound of Argon2id `p_cost` and scrypt `p`.
+pub const MAX_KDF_PARALLELISM: u32 = 16;
+
+/// Key derivation function and its cost parameters to derive 32-byte shared key from password.
+///
+/// Costs are bounded by [MAX_KDF_MEMORY](MAX_KDF_MEMORY), [MAX_ARGON2ID_T_COST](MAX_ARGON2ID_T_COST) and [MAX_KDF_PARALLELISM](MAX_KDF_PARALLELISM).
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum PasswordKdf {
+    /// Argon2id (v0x13).
+    Argon2id {
+        /// Memory size in KiB.
+        m_cost: u32,
+        /// Number of iterations.
+        t_cost: u32,
+        /// Degree of parallelism.
+        p_cost: u32,
+    },
+    /// scrypt.
+    Scrypt {
+        /// log2 of CPU/memory cost parameter `N`.
+        log_n: u8,
+        /// Block size parameter.
+        r: u32,
+        /// Parallelization parameter.
+        p: u32,
+    },
+}
+
+impl Default for PasswordKdf {
+    /// Argon2id with 19 MiB memory, 2 iterations and 1 degree of parallelism (OWASP recommendation).
+    fn default() -> Self {
+        Self::Argon2id {
+            m_cost: 19 * 1024,
+            t_cost: 2,
+            p_cost: 1,
+        }
+    }
+}
+
+impl PasswordKdf {
+    /// scrypt with `N = 2^17`, `r = 8` and `p = 1` (OWASP recommendation).
+    pub fn scrypt() -> Self {
+        Self::Scrypt {
+            log_n: 17,
+            r: 8,
+            p: 1,
+        }
+    }
+
+    fn id(&self) -> u8 {
+        match self {
+            PasswordKdf::Argon2id { .. } => 1,
+            PasswordKdf::Scrypt { .. } => 2,
+        }
+    }
+
+    /// Fails if costs exceed upper bounds.
+    fn ensure_bounded_cost(&self) -> Result<(), Error> {
+        let (memory, parallelism, t_cost) = match *self {
+            PasswordKdf::Argon2id {
+                m_cost,
+                t_cost,
+                p_cost,
+            } => (u64::from(m_cost) * 1024, p_cost, t_cost),
+            // 128 * r * N bytes
+            PasswordKdf::Scrypt { log_n, r, p } => (
+                1u64.checked_shl(log_n.into())
+                    .and_then(|n| n.checked_mul(128 * u64::from(r)))
+                    .unwrap_or(u64::MAX),
+                p,
+                0,
+            ),
+        };
+
+        if memory > MAX_KDF_MEMORY {
+            Err(Error::key_derivation_error(&format!(
+                "KDF memory cost exceeds {} bytes",
+                MAX_KDF_MEMORY
+            )))
+        } else if parallelism > MAX_KDF_PARALLELISM {
+            Err(Error::key_derivation_error(&format!(
+                "KDF parallelism exceeds {}",
+                MAX_KDF_PARALLELISM
+            )))
+        } else if t_cost > MAX_ARGON2ID_T_COST {
+            Err(Error::key_derivation_error(&format!(
+                "Argon2id t_cost exceeds {}",
+                MAX_ARGON2ID_T_COST
+            )))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Derive 32-byte key from `password` and `salt`.
+    ///
+    /// # Failures
+    ///
+    /// - [KeyDerivationError](crate::error::ErrorKind::KeyDerivationError) when:
+    ///   - parameters or `salt` are invalid for the KDF.
+    ///   - costs exceed upper bounds (e.g. [MAX_KDF_MEMORY](MAX_KDF_MEMORY)).
+    pub fn derive_key(&self, password: &[u8], salt: &[u8]) -> Result<Zeroizing<[u8; 32]>, Error> {
+        self.ensure_bounded_cost()?;
+
+        let mut key = Zeroizing::new([0u8; 32]);
+        match *self {
+            PasswordKdf::Argon2id {
+                m_cost,
+                t_cost,
+                p_cost,
+            } => {
+                let params =
+                    Argon2Params::new(m_cost, t_cost, p_cost, Some(key.len())).map_err(|e| {
+                        Error::key_derivation_error(&format!("invalid Argon2id parameters: {}", e))
+                    })?;
+                Argon2::new(argon2::Algorithm::Argon2id, Version::V0x13, params)
+                    .hash_password_into(password, salt, key.as_mut())
+                    .map_err(|e| {
+                        Error::key_derivation_error(&format!("failed to run Argon2id: {}", e))
+                    })?;
+            }
+            PasswordKdf::Scrypt { log_n, r, p } => {
+                let params = ScryptParams::new(log_n, r, p).map_err(|e| {
+                    Error::key_derivation_error(&format!("invalid scrypt parameters: {}", e))
+                })?;
+                scrypt::scrypt(password, salt, &params, key.as_mut()).map_err(|e| {
+                    Error::key_derivation_error(&format!("failed to run scrypt: {}", e))
+                })?;
+            }
+        }
+        Ok(key)
+    }
+}
+
+/// KDF parameters and salt stored along with cipher-text, so that decryption only needs password.
+///
+/// ```text
+/// KDF ID (1: Argon2id, 2: scrypt) || parameters (m_cost, t_cost, p_cost / log_n, r, p as u32 BE) || salt (16 bytes)
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct PasswordKeyHeader {
+    kdf: PasswordKdf,
+    salt: [u8; SALT_SIZE],
+}
+
+impl PasswordKeyHeader {
+    /// Constructor
+    pub fn new(kdf: PasswordKdf, salt: [u8; SALT_SIZE]) -> Self {
+        Self { kdf, salt }
+    }
+
+    /// Header with random salt.
+    pub fn generate<R>(kdf: PasswordKdf) -> Self
+    where
+        R: RngSingleton,
+    {
+        let mut salt = [0u8; SALT_SIZE];
+        {
+            let mut rng = R::instance();
+            rng.deref_mut().fill_bytes(&mut salt);
+        }
+        Self::new(kdf, salt)
+    }
+
+    /// KDF and its parameters.
+    pub fn kdf(&self) -> &PasswordKdf {
+        &self.kdf
+    }
+
+    /// Salt.
+    pub fn salt(&self) -> &[u8; SALT_SIZE] {
+        &self.salt
+    }
+
+    /// Derive 32-byte key from `password` with this header's KDF and salt.
+    ///
+    /// # Failures
+    ///
+    /// - [KeyDerivationError](crate::error::ErrorKind::KeyDerivationError) when KDF parameters are invalid or exceed upper bounds.
+    pub fn derive_key(&self, password: &[u8]) -> Result<Zeroizing<[u8; 32]>, Error> {
+        self.kdf.derive_key(password, &self.salt)
+    }
+
+    /// Serialize into fixed-size bytes.
+    pub fn to_bytes(&self) -> [u8; PASSWORD_KEY_HEADER_SIZE] {
+        let params: [u32; 3] = match self.kdf {
+            PasswordKdf::Argon2id {
+                m_cost,
+                t_cost,
+                p_cost,
+            } => [m_cost, t_cost, p_cost],
+            PasswordKdf::Scrypt { log_n, r, p } => [log_n as u32, r, p],
+        };
+
+        let mut bytes = [0u8; PASSWORD_KEY_HEADER_SIZE];
+        bytes[0] = self.kdf.id();
+        for (i, param) in params.iter().enumerate() {
+            bytes[1 + 4 * i..1 + 4 * (i + 1)].copy_from_slice(&param.to_be_bytes());
+        }
+        bytes[1 + 4 * 3..].copy_from_slice(&self.salt);
+        bytes
+    }
+
+    /// Deserialize from bytes made by [to_bytes()](Self::to_bytes).
+    ///
+    /// # Failures
+    ///
+    /// - [DeserializationError](crate::error::ErrorKind::DeserializationError) when:
+    ///   - `bytes` is not [PASSWORD_KEY_HEADER_SIZE](PASSWORD_KEY_HEADER_SIZE) long.
+    ///   - KDF ID is unknown.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != PASSWORD_KEY_HEADER_SIZE {
+            return Err(Error::deserialization_error(&format!(
+                "password key header must be {} bytes",
+                PASSWORD_KEY_HEADER_SIZE
+            )));
+        }
+
+        let param = |i: usize| {
+            u32::from_be_bytes(
+                bytes[1 + 4 * i..1 + 4 * (i + 1)]
+                    .try_into()
+                    .expect("4 bytes"),
+            )
+        };
+        let kdf = match bytes[0] {
+            1 => PasswordKdf::Argon2id {
+                m_cost: param(0),
+                t_cost: param(1),
+                p_cost: param(2),
+            },
+            2 => PasswordKdf::Scrypt {
+                log_n: param(0).try_into().map_err(|_| {
+                    Error::deserialization_error("scrypt log_n in password key header is too large")
+                })?,
+                r: param(1),
+                p: param(2),
+            },
+            id => {
+                return Err(Error::deserialization_error(&format!(
+                    "unknown KDF ID in password key header: {}",
+                    id
+                )))
+            }
+        };
+        let salt = bytes[1 + 4 * 3..].try_into().expect("SALT_SIZE bytes");
+
+        Ok(Self::new(kdf, salt))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ARGON2ID: PasswordKdf = PasswordKdf::Argon2id {
+        m_cost: 64,
+        t_cost: 1,
+        p_cost: 1,
+    };
+    const SCRYPT: PasswordKdf = PasswordKdf::Scrypt {
+        log_n: 4,
+        r: 8,
+        p: 1,
+    };
+
+    #[test]
+    fn test_derive_key() -> Result<(), Error> {
+        for kdf in [ARGON2ID, SCRYPT] {
+            let header = PasswordKeyHeader::new(kdf, [1u8; SALT_SIZE]);
+            let key = header.derive_key(b"password")?;
+            assert_eq!(key, header.derive_key(b"password")?);
+            assert_ne!(key, header.derive_key(b"passw0rd")?);
+
+            let other_salt = PasswordKeyHeader::new(kdf, [2u8; SALT_SIZE]);
+            assert_ne!(key, other_salt.derive_key(b"password")?);
+        }
+        assert_ne!(
+            ARGON2ID.derive_key(b"password", &[1u8; SALT_SIZE])?,
+            SCRYPT.derive_key(b"password", &[1u8; SALT_SIZE])?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_scrypt_rfc7914_vector() -> Result<(), Error> {
+        // RFC 7914 section 12: scrypt(P="password", S="NaCl", N=1024, r=8, p=16), first 32 bytes of dkLen=64.
+        let kdf = PasswordKdf::Scrypt {
+            log_n: 10,
+            r: 8,
+            p: 16,
+        };
+        let key = kdf.derive_key(b"password", b"NaCl")?;
+        assert_eq!(
+            key.as_ref(),
+            &[
+                0xfd, 0xba, 0xbe, 0x1c, 0x9d, 0x34, 0x72, 0x00, 0x78, 0x56, 0xe7, 0x19, 0x0d, 0x01,
+                0xe9, 0xfe, 0x7c, 0x6a, 0xd7, 0xcb, 0xc8, 0x23, 0x78, 0x30, 0xe7, 0x73, 0x76, 0x63,
+                0x4b, 0x37, 0x31, 0x62,
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        let kdf = PasswordKdf::Argon2id {
+            m_cost: 1,
+            t_cost: 1,
+            p_cost: 1,
+        };
+        let e = kdf.derive_key(b"password", &[0u8; SALT_SIZE]).unwrap_err();
+        assert_eq!(e.kind(), &crate::error::ErrorKind::KeyDerivationError);
+
+        let e = ARGON2ID.derive_key(b"password", b"short").unwrap_err();
+        assert_eq!(e.kind(), &crate::error::ErrorKind::KeyDerivationError);
+    }
+
+    #[test]
+    fn test_cost_upper_bounds() {
+        for kdf in [
+            PasswordKdf::Argon2id {
+                m_cost: u32::MAX,
+                t_cost: 1,
+                p_cost: 1,
+            },
+            PasswordKdf::Argon2id {
+                m_cost: 64,
+                t_cost: MAX_ARGON2ID_T_COST + 1,
+                p_cost: 1,
+            },
+            PasswordKdf::Argon2id {
+                m_cost: 64,
+                t_cost: 1,
+                p_cost: MAX_KDF_PARALLELISM + 1,
+            },
+            PasswordKdf::Scrypt {
+                log_n: 40,
+                r: 8,
+                p: 1,
+            },
+            PasswordKdf::Scrypt {
+                log_n: 4,
+                r: u32::MAX,
+                p: 1,
+            },
+            PasswordKdf::Scrypt {
+                log_n: 4,
+                r: 8,
+                p: u32::MAX,
+            },
+        ] {
+            let e = kdf.derive_key(b"password", &[0u8; SALT_SIZE]).unwrap_err();
+            assert_eq!(e.kind(), &crate::error::ErrorKind::KeyDerivationError);
+        }
+
+        // recommended costs are within bounds
+        for kdf in [PasswordKdf::default(), PasswordKdf::scrypt()] {
+            assert!(kdf.ensure_bounded_cost().is_ok());
+        }
+    }
+
+    #[test]
+    fn test_header_bytes() -> Result<(), Error> {
+        for kdf in [PasswordKdf::default(), PasswordKdf::scrypt()] {
+            let header = PasswordKeyHeader::new(kdf, [7u8; SALT_SIZE]);
+            let bytes = header.to_bytes();
+            assert_eq!(PasswordKeyHeader::from_bytes(&bytes)?, header);
+
+            assert!(PasswordKeyHeader::from_bytes(&bytes[1..]).is_err());
+        }
+
+        let mut bytes = PasswordKeyHeader::new(ARGON2ID, [7u8; SALT_SIZE]).to_bytes();
+        bytes[0] = 0xff;
+        assert!(PasswordKeyHeader::from_bytes(&bytes).is_err());
+        Ok(())
+    }
+}
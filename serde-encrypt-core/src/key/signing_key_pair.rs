@@ -0,0 +1,146 @@
+//! Ed25519 key-pair (signing key and verifying key) to authenticate message sender's identity.
+
+use core::{
+    fmt::{self, Debug},
+    ops::DerefMut,
+};
+
+use crate::{error::Error, random::RngSingleton};
+use ed25519_dalek::{ExpandedSecretKey, PublicKey, SecretKey, Signature, SECRET_KEY_LENGTH};
+use rand::RngCore;
+use zeroize::Zeroizing;
+
+pub use ed25519_dalek::{PUBLIC_KEY_LENGTH, SIGNATURE_LENGTH};
+
+/// Ed25519 signing key (private) of message sender.
+///
+/// Zeroized on drop (by inner `ed25519_dalek::SecretKey`) and its `Debug` output is redacted.
+pub struct SigningPrivateKey(SecretKey);
+
+impl SigningPrivateKey {
+    /// Verifying key derived from this signing key.
+    pub fn verifying_key(&self) -> VerifyingKey {
+        VerifyingKey(PublicKey::from(&self.0))
+    }
+}
+
+impl AsRef<SecretKey> for SigningPrivateKey {
+    fn as_ref(&self) -> &SecretKey {
+        &self.0
+    }
+}
+
+impl From<SecretKey> for SigningPrivateKey {
+    fn from(s: SecretKey) -> Self {
+        Self(s)
+    }
+}
+
+impl Clone for SigningPrivateKey {
+    fn clone(&self) -> Self {
+        let bytes = Zeroizing::new(self.0.to_bytes());
+        Self(SecretKey::from_bytes(bytes.as_ref()).expect("valid length"))
+    }
+}
+
+impl Debug for SigningPrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SigningPrivateKey(...)")
+    }
+}
+
+/// Ed25519 verifying key (public) of message sender.
+///
+/// Receiver gets this key from sender via a trusted channel in advance.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct VerifyingKey(PublicKey);
+
+impl VerifyingKey {
+    /// Restore from 32 bytes made by [to_bytes()](Self::to_bytes).
+    ///
+    /// # Failures
+    ///
+    /// - [SignatureError](crate::error::ErrorKind::SignatureError) when `bytes` is not a valid Ed25519 public key.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        PublicKey::from_bytes(bytes)
+            .map(Self)
+            .map_err(|_| Error::signature_error("invalid Ed25519 verifying key"))
+    }
+
+    /// 32-byte representation.
+    pub fn to_bytes(&self) -> [u8; PUBLIC_KEY_LENGTH] {
+        self.0.to_bytes()
+    }
+
+    /// Verify `signature` of `message`.
+    ///
+    /// # Failures
+    ///
+    /// - [SignatureError](crate::error::ErrorKind::SignatureError) when `signature` is malformed or does not match `message`.
+    pub fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(), Error> {
+        let signature = Signature::from_bytes(signature)
+            .map_err(|_| Error::signature_error("malformed Ed25519 signature"))?;
+        self.0
+            .verify_strict(message, &signature)
+            .map_err(|_| Error::signature_error("Ed25519 signature does not match"))
+    }
+}
+
+impl AsRef<PublicKey> for VerifyingKey {
+    fn as_ref(&self) -> &PublicKey {
+        &self.0
+    }
+}
+
+impl From<PublicKey> for VerifyingKey {
+    fn from(p: PublicKey) -> Self {
+        Self(p)
+    }
+}
+
+/// Ed25519 key-pair generated by sender to sign messages.
+pub trait SigningKeyPairCore {
+    /// RNG singleton
+    type R: RngSingleton;
+
+    /// Constructor.
+    ///
+    /// Verifying key is derived from `signing_private_key` by [SigningPrivateKey::verifying_key()](SigningPrivateKey::verifying_key),
+    /// so that a key-pair never holds a verifying key of another signing key.
+    fn new(signing_private_key: SigningPrivateKey) -> Self
+    where
+        Self: Sized;
+
+    /// Generates a signing key-pair.
+    fn generate() -> Self
+    where
+        Self: Sized,
+    {
+        let mut seed = Zeroizing::new([0u8; SECRET_KEY_LENGTH]);
+        {
+            let mut rng = Self::R::instance();
+            rng.deref_mut().fill_bytes(seed.as_mut());
+        }
+        let secret_key = SecretKey::from_bytes(seed.as_ref()).expect("valid length");
+        Self::new(SigningPrivateKey::from(secret_key))
+    }
+
+    /// Ref to signing (private) key.
+    fn private_key(&self) -> &SigningPrivateKey;
+
+    /// Ref to verifying (public) key.
+    fn verifying_key(&self) -> &VerifyingKey;
+
+    /// Sign `message`.
+    ///
+    /// Public key mixed into the signature is always derived from [private_key()](Self::private_key),
+    /// not taken from [verifying_key()](Self::verifying_key), because signing with a mismatched public key leaks the private key
+    /// ([RUSTSEC-2022-0093](https://rustsec.org/advisories/RUSTSEC-2022-0093)).
+    fn sign(&self, message: &[u8]) -> [u8; SIGNATURE_LENGTH] {
+        let secret_key = self.private_key().as_ref();
+        let public_key = PublicKey::from(secret_key);
+        ExpandedSecretKey::from(secret_key)
+            .sign(message, &public_key)
+            .to_bytes()
+    }
+}
@@ -0,0 +1,104 @@
+//! Test if PlainMessageSharedKeyDeterministicCore:
+//!
+//! - derives the same nonce only from the same plain-text and associated data,
+//! - still decrypts cipher-text made with legacy fixed nonce.
+
+mod test_util;
+
+use chacha20poly1305::{
+    aead::{Aead, NewAead},
+    XChaCha20Poly1305,
+};
+use serde_encrypt_core::{
+    encrypt::{
+        encrypted_message::EncryptedMessage,
+        plain_message_shared_key::PlainMessageSharedKeyDeterministicCore,
+    },
+    error::{Error, ErrorKind},
+    key::as_shared_key::AsSharedKey,
+};
+use test_util::*;
+
+struct MySharedKey([u8; 32]);
+
+impl AsSharedKey for MySharedKey {
+    type R = TestRngSingleton;
+
+    fn from_array(key: [u8; 32]) -> Self {
+        Self(key)
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+#[derive(PartialEq, Debug)]
+struct PlainMessage(Vec<u8>);
+
+impl PlainMessageSharedKeyDeterministicCore for PlainMessage {
+    fn new(plain_message: Vec<u8>) -> Self {
+        Self(plain_message)
+    }
+
+    fn into_vec(self) -> Vec<u8> {
+        self.0
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+#[test]
+fn test_synthetic_nonce() -> Result<(), Error> {
+    let shared_key = MySharedKey::generate();
+
+    let enc1 = PlainMessage(b"same message".to_vec()).encrypt(&shared_key)?;
+    let enc2 = PlainMessage(b"same message".to_vec()).encrypt(&shared_key)?;
+    let enc3 = PlainMessage(b"same? message".to_vec()).encrypt(&shared_key)?;
+    let enc4 = PlainMessage(b"same message".to_vec()).encrypt_with_aad(&shared_key, b"aad")?;
+
+    assert_eq!(enc1.nonce(), enc2.nonce());
+    assert_ne!(enc1.nonce(), enc3.nonce());
+    assert_ne!(enc1.nonce(), enc4.nonce());
+
+    assert_eq!(
+        PlainMessage::decrypt(&enc1, &shared_key)?,
+        PlainMessage(b"same message".to_vec())
+    );
+    Ok(())
+}
+
+#[test]
+fn test_decryption_error_on_tampered_nonce() -> Result<(), Error> {
+    let shared_key = MySharedKey::generate();
+
+    let enc = PlainMessage(b"message".to_vec()).encrypt(&shared_key)?;
+    let mut nonce = [0u8; 24];
+    nonce.copy_from_slice(enc.nonce());
+    nonce[0] ^= 1;
+    let tampered = EncryptedMessage::new(enc.encrypted().to_vec(), nonce);
+
+    let e = PlainMessage::decrypt(&tampered, &shared_key).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+    Ok(())
+}
+
+#[test]
+fn test_decrypt_legacy_fixed_nonce() -> Result<(), Error> {
+    let shared_key = MySharedKey::generate();
+
+    let legacy_nonce = [255u8; 24];
+    let chacha = XChaCha20Poly1305::new(shared_key.to_chacha_key());
+    let encrypted = chacha
+        .encrypt(&legacy_nonce.into(), b"legacy message".as_ref())
+        .unwrap();
+    let legacy = EncryptedMessage::new(encrypted, legacy_nonce);
+
+    assert_eq!(
+        PlainMessage::decrypt(&legacy, &shared_key)?,
+        PlainMessage(b"legacy message".to_vec())
+    );
+    Ok(())
+}
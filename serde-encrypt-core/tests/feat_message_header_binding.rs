@@ -0,0 +1,124 @@
+//! Test if PlainMessageSharedKeyCore:
+//!
+//! - binds message header to cipher-text, so that tampered header makes decryption fail,
+//! - still decrypts version 0 (headerless) cipher-text, even if its random nonce starts with magic number.
+
+mod test_util;
+
+use chacha20poly1305::{
+    aead::{Aead, NewAead, Payload},
+    XChaCha20Poly1305 as XChaCha20Poly1305Cipher,
+};
+use serde_encrypt_core::{
+    encrypt::{
+        aead::XChaCha20Poly1305, encrypted_message::EncryptedMessage,
+        plain_message_shared_key::PlainMessageSharedKeyCore,
+    },
+    error::{Error, ErrorKind},
+    key::as_shared_key::AsSharedKey,
+};
+use test_util::*;
+
+struct MySharedKey([u8; 32]);
+
+impl AsSharedKey for MySharedKey {
+    type R = TestRngSingleton;
+
+    fn from_array(key: [u8; 32]) -> Self {
+        Self(key)
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+#[derive(PartialEq, Debug)]
+struct PlainMessage(Vec<u8>);
+
+impl PlainMessageSharedKeyCore for PlainMessage {
+    type R = TestRngSingleton;
+    type A = XChaCha20Poly1305;
+
+    fn new(plain_message: Vec<u8>) -> Self {
+        Self(plain_message)
+    }
+
+    fn into_vec(self) -> Vec<u8> {
+        self.0
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// Version 0 binary made by serde-encrypt 0.7 or earlier, whose associated data is `aad` alone.
+fn legacy_encrypt(plain: &[u8], shared_key: &MySharedKey, nonce: [u8; 24], aad: &[u8]) -> Vec<u8> {
+    let cipher = XChaCha20Poly1305Cipher::new(shared_key.as_slice().into());
+    let encrypted = cipher
+        .encrypt((&nonce).into(), Payload { msg: plain, aad })
+        .unwrap();
+    EncryptedMessage::new(encrypted, nonce).serialize()
+}
+
+#[test]
+fn test_legacy_message_with_magic_number_in_nonce() -> Result<(), Error> {
+    let shared_key = MySharedKey::generate();
+
+    // nonce looks like a valid header
+    let mut nonce = [42u8; 24];
+    nonce[..7].copy_from_slice(b"SENC\x01\x01\x01");
+    let bin = legacy_encrypt(b"legacy", &shared_key, nonce, b"aad");
+    let enc = EncryptedMessage::deserialize_versioned(bin)?;
+    assert!(enc.header().is_some());
+    assert_eq!(
+        PlainMessage::decrypt_with_aad(&enc, &shared_key, b"aad")?,
+        PlainMessage(b"legacy".to_vec())
+    );
+
+    // nonce looks like a malformed header
+    let mut nonce = [42u8; 24];
+    nonce[..7].copy_from_slice(b"SENC\xff\xff\xff");
+    let bin = legacy_encrypt(b"legacy", &shared_key, nonce, b"aad");
+    let enc = EncryptedMessage::deserialize_versioned(bin)?;
+    assert!(enc.header().is_none());
+    assert_eq!(
+        PlainMessage::decrypt_with_aad(&enc, &shared_key, b"aad")?,
+        PlainMessage(b"legacy".to_vec())
+    );
+    Ok(())
+}
+
+#[test]
+fn test_headerless_message() -> Result<(), Error> {
+    let shared_key = MySharedKey::generate();
+
+    let bin = PlainMessage(b"headerless".to_vec())
+        .encrypt_with_aad(&shared_key, b"aad")?
+        .serialize();
+    let enc = EncryptedMessage::deserialize_versioned(bin)?;
+    assert!(enc.header().is_none());
+    assert_eq!(
+        PlainMessage::decrypt_with_aad(&enc, &shared_key, b"aad")?,
+        PlainMessage(b"headerless".to_vec())
+    );
+    Ok(())
+}
+
+#[test]
+fn test_decryption_error_on_tampered_key_id() -> Result<(), Error> {
+    let shared_key = MySharedKey::generate();
+
+    let mut bin = PlainMessage(b"message".to_vec())
+        .encrypt_with_key_id(&shared_key, 1, b"aad")?
+        .serialize_versioned();
+    // first byte of key ID following 7-byte header
+    bin[7] ^= 1;
+    let enc = EncryptedMessage::deserialize_versioned(bin)?;
+    assert_ne!(enc.header().and_then(|h| h.key_id()), Some(1));
+
+    let e = PlainMessage::decrypt_with_aad(&enc, &shared_key, b"aad").unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+    Ok(())
+}
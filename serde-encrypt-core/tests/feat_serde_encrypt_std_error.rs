@@ -2,8 +2,6 @@
 
 #![cfg(feature = "std")]
 
-mod test_util;
-
 // https://rust-lang.github.io/api-guidelines/interoperability.html#error-types-are-meaningful-and-well-behaved-c-good-err
 #[test]
 fn test_api_guidelines_c_good_err() {
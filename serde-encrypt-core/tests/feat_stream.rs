@@ -0,0 +1,185 @@
+//! Test if stream encryption:
+//!
+//! - round-trips payloads of any size via `std::io::Write` / `std::io::Read` adapters,
+//! - detects truncated, reordered and tampered chunks.
+
+mod test_util;
+
+use std::io::{self, Read, Write};
+
+use serde_encrypt_core::{
+    encrypt::stream::{
+        DecryptingReader, EncryptingWriter, StreamDecryptor, StreamEncryptor, CHUNK_SIZE,
+        ENCRYPTED_CHUNK_SIZE, STREAM_HEADER_SIZE,
+    },
+    key::as_shared_key::AsSharedKey,
+};
+use test_util::*;
+
+struct MySharedKey([u8; 32]);
+
+impl AsSharedKey for MySharedKey {
+    type R = TestRngSingleton;
+
+    fn from_array(key: [u8; 32]) -> Self {
+        Self(key)
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+fn payload(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i % 251) as u8).collect()
+}
+
+fn encrypt(shared_key: &MySharedKey, plain: &[u8], write_size: usize) -> Vec<u8> {
+    let mut writer = EncryptingWriter::new(Vec::new(), shared_key);
+    for chunk in plain.chunks(write_size) {
+        writer.write_all(chunk).unwrap();
+    }
+    writer.finish().unwrap()
+}
+
+fn decrypt(shared_key: &MySharedKey, encrypted: &[u8]) -> io::Result<Vec<u8>> {
+    let mut reader = DecryptingReader::new(encrypted, shared_key)?;
+    let mut plain = Vec::new();
+    reader.read_to_end(&mut plain)?;
+    Ok(plain)
+}
+
+fn assert_invalid_data(r: io::Result<Vec<u8>>) {
+    assert_eq!(r.unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_round_trip() {
+    let shared_key = MySharedKey::generate();
+
+    for len in [
+        0,
+        1,
+        CHUNK_SIZE - 1,
+        CHUNK_SIZE,
+        CHUNK_SIZE + 1,
+        CHUNK_SIZE * 3,
+        CHUNK_SIZE * 3 + 5,
+    ] {
+        let plain = payload(len);
+        for write_size in [7, 4096, CHUNK_SIZE + 3] {
+            let encrypted = encrypt(&shared_key, &plain, write_size);
+            assert_eq!(
+                decrypt(&shared_key, &encrypted).unwrap(),
+                plain,
+                "len={}",
+                len
+            );
+        }
+    }
+}
+
+#[test]
+fn test_chunk_layout() {
+    let shared_key = MySharedKey::generate();
+    let encrypted = encrypt(&shared_key, &payload(CHUNK_SIZE * 2 + 5), 1000);
+    assert_eq!(
+        encrypted.len(),
+        STREAM_HEADER_SIZE + ENCRYPTED_CHUNK_SIZE * 2 + 5 + 16
+    );
+}
+
+#[test]
+fn test_error_on_truncation() {
+    let shared_key = MySharedKey::generate();
+    let encrypted = encrypt(&shared_key, &payload(CHUNK_SIZE * 2 + 5), 1000);
+
+    // truncated at chunk boundary
+    let truncated = &encrypted[..STREAM_HEADER_SIZE + ENCRYPTED_CHUNK_SIZE * 2];
+    assert_invalid_data(decrypt(&shared_key, truncated));
+
+    // truncated in the middle of chunk
+    let truncated = &encrypted[..encrypted.len() - 1];
+    assert_invalid_data(decrypt(&shared_key, truncated));
+
+    // header only
+    let truncated = &encrypted[..STREAM_HEADER_SIZE];
+    assert_invalid_data(decrypt(&shared_key, truncated));
+}
+
+#[test]
+fn test_error_without_finish() {
+    let shared_key = MySharedKey::generate();
+
+    let mut encrypted = Vec::new();
+    {
+        let mut writer = EncryptingWriter::new(&mut encrypted, &shared_key);
+        writer.write_all(&payload(CHUNK_SIZE * 2 + 5)).unwrap();
+    }
+    assert_invalid_data(decrypt(&shared_key, &encrypted));
+}
+
+#[test]
+fn test_error_on_reorder() {
+    let shared_key = MySharedKey::generate();
+    let encrypted = encrypt(&shared_key, &payload(CHUNK_SIZE * 2 + 5), 1000);
+
+    let header = &encrypted[..STREAM_HEADER_SIZE];
+    let chunk0 = &encrypted[STREAM_HEADER_SIZE..STREAM_HEADER_SIZE + ENCRYPTED_CHUNK_SIZE];
+    let chunk1 = &encrypted
+        [STREAM_HEADER_SIZE + ENCRYPTED_CHUNK_SIZE..STREAM_HEADER_SIZE + ENCRYPTED_CHUNK_SIZE * 2];
+    let last = &encrypted[STREAM_HEADER_SIZE + ENCRYPTED_CHUNK_SIZE * 2..];
+
+    let reordered = [header, chunk1, chunk0, last].concat();
+    assert_invalid_data(decrypt(&shared_key, &reordered));
+
+    let duplicated = [header, chunk0, chunk0, last].concat();
+    assert_invalid_data(decrypt(&shared_key, &duplicated));
+}
+
+#[test]
+fn test_error_on_tamper_and_wrong_key() {
+    let shared_key = MySharedKey::generate();
+    let mut encrypted = encrypt(&shared_key, &payload(100), 1000);
+
+    assert_invalid_data(decrypt(&MySharedKey::generate(), &encrypted));
+
+    let last = encrypted.len() - 1;
+    encrypted[last] ^= 1;
+    assert_invalid_data(decrypt(&shared_key, &encrypted));
+}
+
+#[test]
+fn test_error_on_tampered_header() {
+    let shared_key = MySharedKey::generate();
+    let mut encrypted = encrypt(&shared_key, &payload(100), 1000);
+
+    // nonce prefix
+    encrypted[STREAM_HEADER_SIZE - 1] ^= 1;
+    assert_invalid_data(decrypt(&shared_key, &encrypted));
+}
+
+#[test]
+fn test_sans_io_chunk_api() {
+    let shared_key = MySharedKey::generate();
+
+    let mut encryptor = StreamEncryptor::new(&shared_key);
+    let chunk0 = encryptor
+        .encrypt_chunk(&payload(CHUNK_SIZE), false)
+        .unwrap();
+    let chunk1 = encryptor.encrypt_chunk(b"last", true).unwrap();
+    assert!(encryptor.is_finished());
+    assert!(encryptor.encrypt_chunk(b"more", true).is_err());
+
+    let mut decryptor = StreamDecryptor::new(&shared_key, encryptor.stream_header()).unwrap();
+    // last flag mismatch
+    assert!(decryptor.decrypt_chunk(&chunk0, true).is_err());
+
+    let mut decryptor = StreamDecryptor::new(&shared_key, encryptor.stream_header()).unwrap();
+    assert_eq!(
+        decryptor.decrypt_chunk(&chunk0, false).unwrap(),
+        payload(CHUNK_SIZE)
+    );
+    assert_eq!(decryptor.decrypt_chunk(&chunk1, true).unwrap(), b"last");
+    assert!(decryptor.is_finished());
+}
@@ -0,0 +1,249 @@
+//! `#[serde_encrypt(...)]` attribute parser.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Attribute, LitStr};
+
+/// Serializer chosen by `serializer = "..."`.
+#[derive(Copy, Clone, Debug)]
+enum Serializer {
+    Bincode,
+    Postcard,
+    Ciborium,
+    Rmp,
+    Json,
+}
+
+/// AEAD algorithm chosen by `aead = "..."`.
+#[derive(Copy, Clone, Debug)]
+enum Aead {
+    XChaCha20Poly1305,
+    Aes256Gcm,
+    Aes256GcmSiv,
+}
+
+/// Public-key encryption construction chosen by `backend = "..."`.
+#[derive(Copy, Clone, Debug)]
+enum Backend {
+    ChaChaBox,
+    HpkeBase,
+    HpkeAuth,
+}
+
+/// Compressor chosen by `compression = "..."`.
+#[derive(Copy, Clone, Debug)]
+enum Compression {
+    Deflate,
+    Lz4,
+    Zstd,
+}
+
+/// Padding scheme chosen by `padding = "..."`.
+#[derive(Copy, Clone, Debug)]
+enum Padding {
+    Block(usize),
+    PowerOfTwo,
+    Padme,
+}
+
+/// Parsed `#[serde_encrypt(...)]` attributes.
+#[derive(Debug, Default)]
+pub(crate) struct Attrs {
+    serializer: Option<Serializer>,
+    aead: Option<Aead>,
+    backend: Option<Backend>,
+    compression: Option<Compression>,
+    padding: Option<Padding>,
+}
+
+impl Attrs {
+    pub(crate) fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut parsed = Self::default();
+
+        for attr in attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("serde_encrypt"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("serializer") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    let serializer = match lit.value().as_str() {
+                        "bincode" => Serializer::Bincode,
+                        "postcard" => Serializer::Postcard,
+                        // same bytes as deprecated `CborSerializer`
+                        "cbor" | "ciborium" => Serializer::Ciborium,
+                        "rmp" => Serializer::Rmp,
+                        "json" => Serializer::Json,
+                        other => {
+                            return Err(syn::Error::new_spanned(
+                                lit,
+                                format!(
+                                    "unknown serializer `{}`: expected one of \"bincode\", \"postcard\", \"cbor\", \"ciborium\", \"rmp\" and \"json\"",
+                                    other
+                                ),
+                            ))
+                        }
+                    };
+                    parsed.serializer = Some(serializer);
+                    Ok(())
+                } else if meta.path.is_ident("aead") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    let aead = match lit.value().as_str() {
+                        "xchacha20poly1305" => Aead::XChaCha20Poly1305,
+                        "aes256gcm" => Aead::Aes256Gcm,
+                        "aes256gcmsiv" => Aead::Aes256GcmSiv,
+                        other => {
+                            return Err(syn::Error::new_spanned(
+                                lit,
+                                format!(
+                                    "unknown aead `{}`: expected one of \"xchacha20poly1305\", \"aes256gcm\" and \"aes256gcmsiv\"",
+                                    other
+                                ),
+                            ))
+                        }
+                    };
+                    parsed.aead = Some(aead);
+                    Ok(())
+                } else if meta.path.is_ident("backend") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    let backend = match lit.value().as_str() {
+                        "chachabox" => Backend::ChaChaBox,
+                        "hpke_base" => Backend::HpkeBase,
+                        "hpke_auth" => Backend::HpkeAuth,
+                        other => {
+                            return Err(syn::Error::new_spanned(
+                                lit,
+                                format!(
+                                    "unknown backend `{}`: expected one of \"chachabox\", \"hpke_base\" and \"hpke_auth\"",
+                                    other
+                                ),
+                            ))
+                        }
+                    };
+                    parsed.backend = Some(backend);
+                    Ok(())
+                } else if meta.path.is_ident("compression") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    let compression = match lit.value().as_str() {
+                        "deflate" => Compression::Deflate,
+                        "lz4" => Compression::Lz4,
+                        "zstd" => Compression::Zstd,
+                        other => {
+                            return Err(syn::Error::new_spanned(
+                                lit,
+                                format!(
+                                    "unknown compression `{}`: expected one of \"deflate\", \"lz4\" and \"zstd\"",
+                                    other
+                                ),
+                            ))
+                        }
+                    };
+                    parsed.compression = Some(compression);
+                    Ok(())
+                } else if meta.path.is_ident("padding") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    let value = lit.value();
+                    let block_size = value
+                        .strip_prefix("block_")
+                        .and_then(|n| n.parse::<usize>().ok())
+                        .filter(|n| *n > 0);
+                    let padding = match (value.as_str(), block_size) {
+                        (_, Some(n)) => Padding::Block(n),
+                        ("power_of_two", _) => Padding::PowerOfTwo,
+                        ("padme", _) => Padding::Padme,
+                        (other, _) => {
+                            return Err(syn::Error::new_spanned(
+                                lit,
+                                format!(
+                                    "unknown padding `{}`: expected one of \"block_<size>\" (e.g. \"block_256\"), \"power_of_two\" and \"padme\"",
+                                    other
+                                ),
+                            ))
+                        }
+                    };
+                    parsed.padding = Some(padding);
+                    Ok(())
+                } else {
+                    Err(meta.error(
+                        "unknown serde_encrypt attribute: expected `serializer`, `aead`, `backend`, `compression` or `padding`",
+                    ))
+                }
+            })?;
+        }
+
+        Ok(parsed)
+    }
+
+    /// Serializer type for `type S`, wrapped by `Compressed` if `compression` is given
+    /// and then by `Padded` if `padding` is given.
+    pub(crate) fn serializer(&self) -> TokenStream {
+        let serializer = self.compressed_serializer();
+        match self.padding {
+            Some(Padding::Block(n)) => quote!(
+                ::serde_encrypt::serialize::impls::Padded<#serializer, ::serde_encrypt::serialize::padding::Block<#n>>
+            ),
+            Some(Padding::PowerOfTwo) => quote!(
+                ::serde_encrypt::serialize::impls::Padded<#serializer, ::serde_encrypt::serialize::padding::PowerOfTwo>
+            ),
+            Some(Padding::Padme) => quote!(
+                ::serde_encrypt::serialize::impls::Padded<#serializer, ::serde_encrypt::serialize::padding::Padme>
+            ),
+            None => serializer,
+        }
+    }
+
+    fn compressed_serializer(&self) -> TokenStream {
+        let serializer = self.inner_serializer();
+        match self.compression {
+            Some(Compression::Deflate) => quote!(
+                ::serde_encrypt::serialize::impls::Compressed<#serializer, ::serde_encrypt::serialize::compress::Deflate>
+            ),
+            Some(Compression::Lz4) => quote!(
+                ::serde_encrypt::serialize::impls::Compressed<#serializer, ::serde_encrypt::serialize::compress::Lz4>
+            ),
+            Some(Compression::Zstd) => quote!(
+                ::serde_encrypt::serialize::impls::Compressed<#serializer, ::serde_encrypt::serialize::compress::Zstd>
+            ),
+            None => serializer,
+        }
+    }
+
+    fn inner_serializer(&self) -> TokenStream {
+        match self.serializer {
+            Some(Serializer::Bincode) => {
+                quote!(::serde_encrypt::serialize::impls::BincodeSerializer<Self>)
+            }
+            Some(Serializer::Postcard) => {
+                quote!(::serde_encrypt::serialize::impls::PostcardSerializer<Self>)
+            }
+            Some(Serializer::Ciborium) => {
+                quote!(::serde_encrypt::serialize::impls::CiboriumSerializer<Self>)
+            }
+            Some(Serializer::Rmp) => {
+                quote!(::serde_encrypt::serialize::impls::RmpSerializer<Self>)
+            }
+            Some(Serializer::Json) => {
+                quote!(::serde_encrypt::serialize::impls::JsonSerializer<Self>)
+            }
+            None => quote!(::serde_encrypt::serialize::impls::DefaultSerializer<Self>),
+        }
+    }
+
+    /// AEAD algorithm type for `type A`.
+    pub(crate) fn aead(&self) -> TokenStream {
+        match self.aead.unwrap_or(Aead::XChaCha20Poly1305) {
+            Aead::XChaCha20Poly1305 => quote!(::serde_encrypt::aead::XChaCha20Poly1305),
+            Aead::Aes256Gcm => quote!(::serde_encrypt::aead::Aes256Gcm),
+            Aead::Aes256GcmSiv => quote!(::serde_encrypt::aead::Aes256GcmSiv),
+        }
+    }
+
+    /// Public-key encryption construction for `type B`.
+    pub(crate) fn backend(&self) -> TokenStream {
+        match self.backend.unwrap_or(Backend::ChaChaBox) {
+            Backend::ChaChaBox => quote!(::serde_encrypt::public_key_backend::ChaChaBox),
+            Backend::HpkeBase => quote!(::serde_encrypt::public_key_backend::HpkeBase),
+            Backend::HpkeAuth => quote!(::serde_encrypt::public_key_backend::HpkeAuth),
+        }
+    }
+}
@@ -0,0 +1,182 @@
+//! Derive macros for [serde-encrypt](https://docs.rs/serde-encrypt) traits.
+//!
+//! Normally, users do not depend on this crate directly but enable `derive` feature of serde-encrypt.
+//!
+//! ```ignore
+//! use serde::{Deserialize, Serialize};
+//! use serde_encrypt::traits::{SerdeEncryptPublicKey, SerdeEncryptSharedKey};
+//!
+//! #[derive(Serialize, Deserialize, SerdeEncryptSharedKey, SerdeEncryptPublicKey)]
+//! #[serde_encrypt(serializer = "cbor", aead = "aes256gcm")]
+//! struct Message {
+//!     content: String,
+//! }
+//! ```
+//!
+//! # `#[serde_encrypt(...)]` attributes
+//!
+//! - `serializer`: one of `"bincode"`, `"postcard"`, `"cbor"`, `"ciborium"`, `"rmp"` and `"json"`.
+//!   `"cbor"` is the same as `"ciborium"` ([CiboriumSerializer](https://docs.rs/serde-encrypt/latest/serde_encrypt/serialize/impls/struct.CiboriumSerializer.html)),
+//!   which needs `ciborium` feature in serde-encrypt. `"json"` needs `json` feature.
+//!   Defaults to [DefaultSerializer](https://docs.rs/serde-encrypt/latest/serde_encrypt/serialize/impls/type.DefaultSerializer.html).
+//! - `aead`: one of `"xchacha20poly1305"`, `"aes256gcm"` and `"aes256gcmsiv"`.
+//!   Defaults to `"xchacha20poly1305"`. Only used by `SerdeEncryptSharedKey` and `SerdeEncryptEnvelope`.
+//! - `backend`: one of `"chachabox"`, `"hpke_base"` and `"hpke_auth"`.
+//!   Defaults to `"chachabox"`. Only used by `SerdeEncryptPublicKey`.
+//! - `compression`: one of `"deflate"`, `"lz4"` and `"zstd"`, which needs the feature of the same name in serde-encrypt.
+//!   Wraps the serializer by [Compressed](https://docs.rs/serde-encrypt/latest/serde_encrypt/serialize/impls/struct.Compressed.html).
+//!   Defaults to no compression.
+//! - `padding`: one of `"block_<size>"` (e.g. `"block_256"`), `"power_of_two"` and `"padme"`.
+//!   Wraps the serializer (after compression) by [Padded](https://docs.rs/serde-encrypt/latest/serde_encrypt/serialize/impls/struct.Padded.html).
+//!   Defaults to no padding.
+
+#![deny(missing_debug_implementations, missing_docs)]
+
+mod attr;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, DeriveInput};
+
+use crate::attr::Attrs;
+
+/// Derives `serde_encrypt::traits::SerdeEncryptSharedKey`.
+#[proc_macro_derive(SerdeEncryptSharedKey, attributes(serde_encrypt))]
+pub fn derive_serde_encrypt_shared_key(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(
+        &input,
+        quote!(::serde_encrypt::traits::SerdeEncryptSharedKey),
+        |attrs| {
+            let s = attrs.serializer();
+            let a = attrs.aead();
+            quote! {
+                type S = #s;
+                type A = #a;
+            }
+        },
+    )
+}
+
+/// Derives `serde_encrypt::traits::SerdeEncryptSharedKeyDeterministic`.
+#[proc_macro_derive(SerdeEncryptSharedKeyDeterministic, attributes(serde_encrypt))]
+pub fn derive_serde_encrypt_shared_key_deterministic(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(
+        &input,
+        quote!(::serde_encrypt::traits::SerdeEncryptSharedKeyDeterministic),
+        |attrs| {
+            let s = attrs.serializer();
+            quote! {
+                type S = #s;
+            }
+        },
+    )
+}
+
+/// Derives `serde_encrypt::traits::SerdeEncryptPublicKey`.
+#[proc_macro_derive(SerdeEncryptPublicKey, attributes(serde_encrypt))]
+pub fn derive_serde_encrypt_public_key(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(
+        &input,
+        quote!(::serde_encrypt::traits::SerdeEncryptPublicKey),
+        |attrs| {
+            let s = attrs.serializer();
+            let b = attrs.backend();
+            quote! {
+                type S = #s;
+                type B = #b;
+            }
+        },
+    )
+}
+
+/// Derives `serde_encrypt::traits::SerdeEncryptSealedBox`.
+#[proc_macro_derive(SerdeEncryptSealedBox, attributes(serde_encrypt))]
+pub fn derive_serde_encrypt_sealed_box(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(
+        &input,
+        quote!(::serde_encrypt::traits::SerdeEncryptSealedBox),
+        |attrs| {
+            let s = attrs.serializer();
+            quote! {
+                type S = #s;
+            }
+        },
+    )
+}
+
+/// Derives `serde_encrypt::traits::SerdeEncryptMultiRecipient`.
+#[proc_macro_derive(SerdeEncryptMultiRecipient, attributes(serde_encrypt))]
+pub fn derive_serde_encrypt_multi_recipient(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(
+        &input,
+        quote!(::serde_encrypt::traits::SerdeEncryptMultiRecipient),
+        |attrs| {
+            let s = attrs.serializer();
+            quote! {
+                type S = #s;
+            }
+        },
+    )
+}
+
+/// Derives `serde_encrypt::traits::SerdeEncryptSignedPublicKey`.
+#[proc_macro_derive(SerdeEncryptSignedPublicKey, attributes(serde_encrypt))]
+pub fn derive_serde_encrypt_signed_public_key(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(
+        &input,
+        quote!(::serde_encrypt::traits::SerdeEncryptSignedPublicKey),
+        |attrs| {
+            let s = attrs.serializer();
+            quote! {
+                type S = #s;
+            }
+        },
+    )
+}
+
+/// Derives `serde_encrypt::traits::SerdeEncryptEnvelope`.
+#[proc_macro_derive(SerdeEncryptEnvelope, attributes(serde_encrypt))]
+pub fn derive_serde_encrypt_envelope(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(
+        &input,
+        quote!(::serde_encrypt::traits::SerdeEncryptEnvelope),
+        |attrs| {
+            let s = attrs.serializer();
+            let a = attrs.aead();
+            quote! {
+                type S = #s;
+                type A = #a;
+            }
+        },
+    )
+}
+
+/// Generates `impl #trait_path for #type { #items }`, keeping generics (including lifetimes) of the input type.
+fn expand<F>(input: &DeriveInput, trait_path: TokenStream2, items: F) -> TokenStream
+where
+    F: FnOnce(&Attrs) -> TokenStream2,
+{
+    let attrs = match Attrs::parse(&input.attrs) {
+        Ok(attrs) => attrs,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let items = items(&attrs);
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let expanded = quote! {
+        impl #impl_generics #trait_path for #name #ty_generics #where_clause {
+            #items
+        }
+    };
+    expanded.into()
+}
@@ -0,0 +1,285 @@
+//! Test if derive macros implement:
+//!
+//! - SerdeEncryptSharedKey
+//! - SerdeEncryptSharedKeyDeterministic
+//! - SerdeEncryptPublicKey
+//! - SerdeEncryptSealedBox
+//! - SerdeEncryptMultiRecipient
+//! - SerdeEncryptSignedPublicKey
+//! - SerdeEncryptEnvelope
+//!
+//! with chosen serializer, AEAD algorithm, public-key backend, compression and padding.
+
+use serde::{Deserialize, Serialize};
+use serde_encrypt::{
+    key::{
+        key_pair::{ReceiverKeyPair, SenderKeyPair},
+        signing_key_pair::SigningKeyPair,
+    },
+    serialize::TypedSerialized,
+    shared_key::SharedKey,
+    traits::{
+        SerdeEncryptEnvelope, SerdeEncryptMultiRecipient, SerdeEncryptPublicKey,
+        SerdeEncryptSealedBox, SerdeEncryptSharedKey, SerdeEncryptSharedKeyDeterministic,
+        SerdeEncryptSignedPublicKey,
+    },
+    Algorithm, AsSharedKey, EncryptionMode, Error, ReceiverCombinedKey, ReceiverKeyPairCore,
+    SenderCombinedKey, SenderKeyPairCore, SigningKeyPairCore,
+};
+
+#[derive(
+    PartialEq,
+    Debug,
+    Serialize,
+    Deserialize,
+    SerdeEncryptSharedKey,
+    SerdeEncryptSharedKeyDeterministic,
+    SerdeEncryptPublicKey,
+)]
+struct DefaultMessage(String);
+
+#[derive(PartialEq, Debug, Serialize, Deserialize, SerdeEncryptSharedKey)]
+#[serde_encrypt(serializer = "cbor", aead = "aes256gcm")]
+struct CborAesMessage {
+    content: String,
+    n: u32,
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize, SerdeEncryptSharedKeyDeterministic)]
+#[serde_encrypt(serializer = "postcard")]
+enum PostcardMessage {
+    A(i64),
+    B,
+}
+
+#[derive(Debug, Serialize, Deserialize, SerdeEncryptPublicKey)]
+#[serde_encrypt(serializer = "ciborium")]
+struct MessageWithReference<'a> {
+    title: &'a str,
+    sender: &'a str,
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize, SerdeEncryptPublicKey)]
+#[serde_encrypt(serializer = "rmp", backend = "hpke_auth")]
+struct HpkeMessage(String);
+
+#[derive(Debug, Serialize, Deserialize, SerdeEncryptSealedBox)]
+#[serde_encrypt(serializer = "json")]
+struct AnonymousMessage<'a> {
+    title: &'a str,
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize, SerdeEncryptMultiRecipient)]
+#[serde_encrypt(serializer = "postcard")]
+struct BroadcastMessage(String);
+
+#[derive(PartialEq, Debug, Serialize, Deserialize, SerdeEncryptSharedKey)]
+#[serde_encrypt(serializer = "postcard", compression = "lz4")]
+struct CompressedMessage(String);
+
+#[derive(PartialEq, Debug, Serialize, Deserialize, SerdeEncryptSharedKey)]
+#[serde_encrypt(compression = "lz4", padding = "block_64")]
+enum PaddedMessage {
+    Short,
+    Long(String),
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize, SerdeEncryptSignedPublicKey)]
+#[serde_encrypt(serializer = "postcard")]
+struct SignedMessage(String);
+
+#[derive(PartialEq, Debug, Serialize, Deserialize, SerdeEncryptEnvelope)]
+#[serde_encrypt(serializer = "cbor", aead = "aes256gcmsiv")]
+struct EnvelopeMessage(String);
+
+#[test]
+fn test_derive_default() -> Result<(), Error> {
+    let shared_key = SharedKey::generate();
+    let msg = DefaultMessage("hello".into());
+
+    let enc = SerdeEncryptSharedKey::encrypt(&msg, &shared_key)?;
+    let dec = <DefaultMessage as SerdeEncryptSharedKey>::decrypt_owned(&enc, &shared_key)?;
+    assert_eq!(dec, msg);
+    assert_eq!(
+        enc.header().unwrap().algorithm(),
+        Algorithm::XChaCha20Poly1305
+    );
+
+    let enc = SerdeEncryptSharedKeyDeterministic::encrypt(&msg, &shared_key)?;
+    let dec =
+        <DefaultMessage as SerdeEncryptSharedKeyDeterministic>::decrypt_owned(&enc, &shared_key)?;
+    assert_eq!(dec, msg);
+
+    Ok(())
+}
+
+#[test]
+fn test_derive_serializer_and_aead() -> Result<(), Error> {
+    let shared_key = SharedKey::generate();
+
+    let msg = CborAesMessage {
+        content: "hello".into(),
+        n: 42,
+    };
+    let enc = msg.encrypt(&shared_key)?;
+    assert_eq!(enc.header().unwrap().algorithm(), Algorithm::Aes256Gcm);
+    let dec = CborAesMessage::decrypt_owned(&enc, &shared_key)?;
+    assert_eq!(dec, msg);
+
+    let msg = PostcardMessage::A(-1);
+    let enc = msg.encrypt(&shared_key)?;
+    let dec = PostcardMessage::decrypt_owned(&enc, &shared_key)?;
+    assert_eq!(dec, msg);
+    let enc = PostcardMessage::B.encrypt(&shared_key)?;
+    assert_eq!(
+        PostcardMessage::decrypt_owned(&enc, &shared_key)?,
+        PostcardMessage::B
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_derive_compression() -> Result<(), Error> {
+    let shared_key = SharedKey::generate();
+
+    let msg = CompressedMessage("a".repeat(1000));
+    let enc = msg.encrypt(&shared_key)?;
+    assert!(enc.len() < 100);
+    let dec = CompressedMessage::decrypt_owned(&enc, &shared_key)?;
+    assert_eq!(dec, msg);
+
+    Ok(())
+}
+
+#[test]
+fn test_derive_padding() -> Result<(), Error> {
+    let shared_key = SharedKey::generate();
+
+    let short = PaddedMessage::Short.encrypt(&shared_key)?;
+    let long = PaddedMessage::Long("hello".into()).encrypt(&shared_key)?;
+    assert_eq!(short.len(), long.len());
+    assert_eq!(
+        PaddedMessage::decrypt_owned(&long, &shared_key)?,
+        PaddedMessage::Long("hello".into())
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_derive_with_lifetime() -> Result<(), Error> {
+    let alice_key_pair = SenderKeyPair::generate();
+    let bob_key_pair = ReceiverKeyPair::generate();
+    let alice_combined_key =
+        SenderCombinedKey::new(alice_key_pair.private_key(), bob_key_pair.public_key());
+    let bob_combined_key =
+        ReceiverCombinedKey::new(alice_key_pair.public_key(), bob_key_pair.private_key());
+
+    let msg = MessageWithReference {
+        title: "my heart",
+        sender: "Alice",
+    };
+    let enc = msg.encrypt(&alice_combined_key)?;
+
+    let decrypted = MessageWithReference::decrypt_ref(&enc, &bob_combined_key)?;
+    let revealed = decrypted.deserialize()?;
+    assert_eq!(revealed.title, "my heart");
+    assert_eq!(revealed.sender, "Alice");
+
+    Ok(())
+}
+
+#[test]
+fn test_derive_backend() -> Result<(), Error> {
+    let alice_key_pair = SenderKeyPair::generate();
+    let bob_key_pair = ReceiverKeyPair::generate();
+    let alice_combined_key =
+        SenderCombinedKey::new(alice_key_pair.private_key(), bob_key_pair.public_key());
+    let bob_combined_key =
+        ReceiverCombinedKey::new(alice_key_pair.public_key(), bob_key_pair.private_key());
+
+    let msg = HpkeMessage("hello".into());
+    let enc = msg.encrypt(&alice_combined_key)?;
+    assert_eq!(enc.header().unwrap().algorithm(), Algorithm::HpkeAuth);
+    let dec = HpkeMessage::decrypt_owned(&enc, &bob_combined_key)?;
+    assert_eq!(dec, msg);
+
+    Ok(())
+}
+
+#[test]
+fn test_derive_sealed_box_with_lifetime() -> Result<(), Error> {
+    let bob_key_pair = ReceiverKeyPair::generate();
+
+    let msg = AnonymousMessage { title: "anonymous" };
+    let enc = msg.encrypt(bob_key_pair.public_key())?;
+
+    let decrypted = AnonymousMessage::decrypt_ref(&enc, &bob_key_pair)?;
+    let revealed = decrypted.deserialize()?;
+    assert_eq!(revealed.title, "anonymous");
+
+    Ok(())
+}
+
+#[test]
+fn test_derive_multi_recipient() -> Result<(), Error> {
+    let alice_key_pair = SenderKeyPair::generate();
+    let bob_key_pair = ReceiverKeyPair::generate();
+    let carol_key_pair = ReceiverKeyPair::generate();
+
+    let msg = BroadcastMessage("to Bob and Carol".into());
+    let enc = msg.encrypt(
+        &alice_key_pair,
+        &[bob_key_pair.public_key(), carol_key_pair.public_key()],
+    )?;
+
+    let carol_combined_key =
+        ReceiverCombinedKey::new(alice_key_pair.public_key(), carol_key_pair.private_key());
+    let decrypted = BroadcastMessage::decrypt_owned(&enc, &carol_combined_key)?;
+    assert_eq!(decrypted, msg);
+
+    Ok(())
+}
+
+#[test]
+fn test_derive_signed_public_key() -> Result<(), Error> {
+    let alice_key_pair = SenderKeyPair::generate();
+    let alice_signing_key_pair = SigningKeyPair::generate();
+    let bob_key_pair = ReceiverKeyPair::generate();
+    let alice_combined_key =
+        SenderCombinedKey::new(alice_key_pair.private_key(), bob_key_pair.public_key());
+    let bob_combined_key =
+        ReceiverCombinedKey::new(alice_key_pair.public_key(), bob_key_pair.private_key());
+
+    let msg = SignedMessage("signed by Alice".into());
+    let enc = msg.encrypt(&alice_combined_key, &alice_signing_key_pair)?;
+    assert_eq!(
+        enc.header().unwrap().mode(),
+        EncryptionMode::SignedPublicKey
+    );
+    let dec = SignedMessage::decrypt_owned(
+        &enc,
+        &bob_combined_key,
+        alice_signing_key_pair.verifying_key(),
+    )?;
+    assert_eq!(dec, msg);
+
+    Ok(())
+}
+
+#[test]
+fn test_derive_envelope() -> Result<(), Error> {
+    let kek = SharedKey::generate();
+
+    let msg = EnvelopeMessage("large dataset".into());
+    let enc = msg.encrypt(&kek)?;
+    assert_eq!(
+        enc.encrypted_message().header().unwrap().algorithm(),
+        Algorithm::Aes256GcmSiv
+    );
+    let dec = EnvelopeMessage::decrypt_owned(&enc, &kek)?;
+    assert_eq!(dec, msg);
+
+    Ok(())
+}
@@ -0,0 +1,258 @@
+//! tokio `AsyncWrite` / `AsyncRead` adapters of [stream encryption](crate::stream) (only `tokio` feature).
+//!
+//! Encrypts uploads and downloads chunk by chunk without blocking or buffering whole payloads.
+//! Cipher-text is in the same format as [EncryptingWriter](crate::stream::EncryptingWriter),
+//! so it can be decrypted by [DecryptingReader](crate::stream::DecryptingReader) and vice versa.
+
+use alloc::vec::Vec;
+use core::{
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+use std::io;
+
+use serde_encrypt_core::encrypt::stream::{
+    StreamDecryptor, StreamEncryptor, CHUNK_SIZE, ENCRYPTED_CHUNK_SIZE, STREAM_HEADER_SIZE,
+};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::{shared_key::SharedKey, Error};
+
+fn to_io_error(e: Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+/// `tokio::io::AsyncWrite` adapter which encrypts everything written into it and writes cipher-text into inner writer.
+///
+/// `shutdown()` (`tokio::io::AsyncWriteExt::shutdown`) must be called after all data is written,
+/// which encrypts the last chunk and shuts down inner writer.
+/// Otherwise, the stream lacks its last chunk and readers report it as truncated.
+#[derive(Debug)]
+pub struct AsyncEncryptingWriter<W> {
+    inner: W,
+    encryptor: StreamEncryptor,
+    /// Plain-text not yet encrypted (up to `CHUNK_SIZE` bytes).
+    buf: Zeroizing<Vec<u8>>,
+    /// Cipher-text (including stream header) not yet written into inner writer.
+    pending: Vec<u8>,
+    pending_pos: usize,
+}
+
+impl<W> AsyncEncryptingWriter<W>
+where
+    W: AsyncWrite + Unpin,
+{
+    /// Constructor.
+    pub fn new(inner: W, shared_key: &SharedKey) -> Self {
+        let encryptor = StreamEncryptor::new(shared_key);
+        let pending = encryptor.stream_header().to_vec();
+        Self {
+            inner,
+            encryptor,
+            buf: Zeroizing::new(Vec::with_capacity(CHUNK_SIZE)),
+            pending,
+            pending_pos: 0,
+        }
+    }
+
+    /// Returns inner writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    fn encrypt_buf(&mut self, last: bool) -> io::Result<()> {
+        let encrypted = self
+            .encryptor
+            .encrypt_chunk(&self.buf, last)
+            .map_err(to_io_error)?;
+        self.buf.zeroize();
+        self.pending.extend_from_slice(&encrypted);
+        Ok(())
+    }
+
+    fn poll_write_pending(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while self.pending_pos < self.pending.len() {
+            let n = ready!(
+                Pin::new(&mut self.inner).poll_write(cx, &self.pending[self.pending_pos..])
+            )?;
+            if n == 0 {
+                return Poll::Ready(Err(io::ErrorKind::WriteZero.into()));
+            }
+            self.pending_pos += n;
+        }
+        self.pending.clear();
+        self.pending_pos = 0;
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<W> AsyncWrite for AsyncEncryptingWriter<W>
+where
+    W: AsyncWrite + Unpin,
+{
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        data: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if data.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        if this.encryptor.is_finished() {
+            return Poll::Ready(Err(io::Error::other("write after shutdown")));
+        }
+
+        ready!(this.poll_write_pending(cx))?;
+
+        // Full buffer is encrypted only when more data arrives, so that the last chunk is never empty unless whole stream is empty.
+        if this.buf.len() == CHUNK_SIZE {
+            this.encrypt_buf(false)?;
+        }
+
+        let n = data.len().min(CHUNK_SIZE - this.buf.len());
+        this.buf.extend_from_slice(&data[..n]);
+        Poll::Ready(Ok(n))
+    }
+
+    /// Writes encrypted chunks into inner writer and flushes it.
+    /// Buffered plain-text is not encrypted until a chunk fills up or shutdown.
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_write_pending(cx))?;
+        Pin::new(&mut this.inner).poll_flush(cx)
+    }
+
+    /// Encrypts remaining data as the last chunk, writes it and shuts down inner writer.
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if !this.encryptor.is_finished() {
+            this.encrypt_buf(true)?;
+        }
+        ready!(this.poll_write_pending(cx))?;
+        Pin::new(&mut this.inner).poll_shutdown(cx)
+    }
+}
+
+/// `tokio::io::AsyncRead` adapter which reads cipher-text from inner reader and returns decrypted plain-text.
+///
+/// Reading fails with `std::io::ErrorKind::InvalidData` when the stream is tampered, reordered or truncated.
+/// Plain-text of a chunk is returned only after the chunk is authenticated.
+#[derive(Debug)]
+pub struct AsyncDecryptingReader<R> {
+    inner: R,
+    shared_key: SharedKey,
+    /// `None` until stream header is read.
+    decryptor: Option<StreamDecryptor>,
+    /// Stream header or cipher-text of a chunk (and the first byte of the next chunk) being read.
+    encrypted: Vec<u8>,
+    filled: usize,
+    eof: bool,
+    /// Decrypted plain-text of the current chunk.
+    plain: Zeroizing<Vec<u8>>,
+    pos: usize,
+}
+
+impl<R> AsyncDecryptingReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Constructor. Stream header is read on the first read.
+    pub fn new(inner: R, shared_key: &SharedKey) -> Self {
+        Self {
+            inner,
+            shared_key: shared_key.clone(),
+            decryptor: None,
+            encrypted: vec![0u8; STREAM_HEADER_SIZE],
+            filled: 0,
+            eof: false,
+            plain: Zeroizing::new(Vec::new()),
+            pos: 0,
+        }
+    }
+
+    /// Returns inner reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Fills `self.encrypted` until it is full or inner reader reaches EOF.
+    fn poll_fill(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while self.filled < self.encrypted.len() && !self.eof {
+            let mut read_buf = ReadBuf::new(&mut self.encrypted[self.filled..]);
+            ready!(Pin::new(&mut self.inner).poll_read(cx, &mut read_buf))?;
+            let n = read_buf.filled().len();
+            if n == 0 {
+                self.eof = true;
+            }
+            self.filled += n;
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    /// Reads stream header or next chunk.
+    fn poll_next_chunk(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        ready!(self.poll_fill(cx))?;
+
+        match self.decryptor.as_mut() {
+            None => {
+                if self.filled < STREAM_HEADER_SIZE {
+                    return Poll::Ready(Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "stream header is truncated",
+                    )));
+                }
+                self.decryptor = Some(
+                    StreamDecryptor::new(&self.shared_key, &self.encrypted).map_err(to_io_error)?,
+                );
+                self.encrypted = vec![0u8; ENCRYPTED_CHUNK_SIZE + 1];
+                self.filled = 0;
+            }
+            Some(decryptor) => {
+                let last = self.filled <= ENCRYPTED_CHUNK_SIZE;
+                let chunk_len = self.filled.min(ENCRYPTED_CHUNK_SIZE);
+                self.plain = Zeroizing::new(
+                    decryptor
+                        .decrypt_chunk(&self.encrypted[..chunk_len], last)
+                        .map_err(to_io_error)?,
+                );
+                self.pos = 0;
+
+                // keep the first byte of the next chunk
+                self.encrypted[0] = self.encrypted[ENCRYPTED_CHUNK_SIZE];
+                self.filled -= chunk_len;
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<R> AsyncRead for AsyncDecryptingReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        while this.pos == this.plain.len() {
+            let finished = this
+                .decryptor
+                .as_ref()
+                .map(|d| d.is_finished())
+                .unwrap_or(false);
+            if finished || buf.remaining() == 0 {
+                return Poll::Ready(Ok(()));
+            }
+            ready!(this.poll_next_chunk(cx))?;
+        }
+
+        let n = buf.remaining().min(this.plain.len() - this.pos);
+        buf.put_slice(&this.plain[this.pos..this.pos + n]);
+        this.pos += n;
+        Poll::Ready(Ok(()))
+    }
+}
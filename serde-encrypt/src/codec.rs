@@ -0,0 +1,100 @@
+//! tokio-util codec for framed encrypted serde messages over a byte stream (only `codec` feature).
+
+use core::marker::PhantomData;
+
+use bytes::{Bytes, BytesMut};
+use serde::{de::DeserializeOwned, Serialize};
+use tokio_util::codec::{Decoder, Encoder, LengthDelimitedCodec};
+
+use crate::{shared_key::SharedKey, traits::SerdeEncryptSharedKey, EncryptedMessage, Error};
+
+/// Default max frame length (16 MiB).
+pub const DEFAULT_MAX_FRAME_LENGTH: usize = 16 * 1024 * 1024;
+
+/// `tokio_util::codec::{Encoder, Decoder}` which encrypts each `T` by [SerdeEncryptSharedKey](crate::traits::SerdeEncryptSharedKey)
+/// and frames it with 4-byte big-endian length prefix.
+///
+/// Each frame is [versioned](crate::EncryptedMessage::serialize_versioned) EncryptedMessage binary.
+///
+/// Use it with `tokio_util::codec::Framed` (or `FramedRead` / `FramedWrite`) over TCP and Unix sockets.
+///
+/// # Failures
+///
+/// Decoder returns:
+///
+/// - [IoError](serde_encrypt_core::error::ErrorKind::IoError) when underlying stream fails or a frame exceeds max frame length.
+/// - [DecryptionError](serde_encrypt_core::error::ErrorKind::DecryptionError) / [DeserializationError](serde_encrypt_core::error::ErrorKind::DeserializationError)
+///   when a frame cannot be decrypted or deserialized into `T`.
+#[derive(Debug)]
+pub struct SharedKeyCodec<T> {
+    shared_key: SharedKey,
+    framing: LengthDelimitedCodec,
+    _type: PhantomData<fn(T) -> T>,
+}
+
+impl<T> SharedKeyCodec<T>
+where
+    T: SerdeEncryptSharedKey,
+{
+    /// Constructor with [DEFAULT_MAX_FRAME_LENGTH](self::DEFAULT_MAX_FRAME_LENGTH).
+    pub fn new(shared_key: SharedKey) -> Self {
+        Self::with_max_frame_length(shared_key, DEFAULT_MAX_FRAME_LENGTH)
+    }
+
+    /// Constructor with max frame length in bytes.
+    /// Frames longer than it fail to be encoded / decoded.
+    pub fn with_max_frame_length(shared_key: SharedKey, max_frame_length: usize) -> Self {
+        let framing = LengthDelimitedCodec::builder()
+            .length_field_length(4)
+            .big_endian()
+            .max_frame_length(max_frame_length)
+            .new_codec();
+        Self {
+            shared_key,
+            framing,
+            _type: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for SharedKeyCodec<T> {
+    fn clone(&self) -> Self {
+        Self {
+            shared_key: self.shared_key.clone(),
+            framing: self.framing.clone(),
+            _type: PhantomData,
+        }
+    }
+}
+
+impl<T> Encoder<T> for SharedKeyCodec<T>
+where
+    T: SerdeEncryptSharedKey + Serialize,
+{
+    type Error = Error;
+
+    fn encode(&mut self, item: T, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        let encrypted = item.encrypt(&self.shared_key)?;
+        let frame = Bytes::from(encrypted.serialize_versioned());
+        self.framing.encode(frame, dst)?;
+        Ok(())
+    }
+}
+
+impl<T> Decoder for SharedKeyCodec<T>
+where
+    T: SerdeEncryptSharedKey + DeserializeOwned,
+{
+    type Item = T;
+    type Error = Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        match self.framing.decode(src)? {
+            Some(frame) => {
+                let encrypted = EncryptedMessage::deserialize_versioned(frame.to_vec())?;
+                T::decrypt_owned(&encrypted, &self.shared_key).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+}
@@ -1,5 +1,8 @@
 //! Encryption implementation
 
+pub mod plain_message_multi_recipient;
 pub mod plain_message_public_key;
+pub mod plain_message_sealed_box;
 pub mod plain_message_shared_key;
 pub mod plain_message_shared_key_deterministic;
+pub mod plain_message_signed_public_key;
@@ -1,29 +1,52 @@
 //! Public key encryption
 
 use alloc::vec::Vec;
-use serde_encrypt_core::encrypt::plain_message_public_key::PlainMessagePublicKeyCore;
+use core::marker::PhantomData;
+use serde_encrypt_core::encrypt::{
+    plain_message_public_key::PlainMessagePublicKeyCore,
+    public_key_backend::{ChaChaBox, PublicKeyBackend},
+};
+use zeroize::Zeroize;
 
 use crate::random::RngSingletonImpl;
 
 /// Plain message structure serialized via serde.
+///
+/// Zeroized on drop.
 #[derive(Clone, Eq, PartialEq, Debug)]
-pub struct PlainMessagePublicKey(Vec<u8>);
+pub struct PlainMessagePublicKey<B = ChaChaBox>(Vec<u8>, PhantomData<B>);
 
-impl PlainMessagePublicKeyCore for PlainMessagePublicKey {
+impl<B> PlainMessagePublicKeyCore for PlainMessagePublicKey<B>
+where
+    B: PublicKeyBackend,
+{
     type R = RngSingletonImpl;
+    type B = B;
 
     fn new(plain_message: Vec<u8>) -> Self
     where
         Self: Sized,
     {
-        Self(plain_message)
+        Self(plain_message, PhantomData)
     }
 
-    fn into_vec(self) -> Vec<u8> {
-        self.0
+    fn into_vec(mut self) -> Vec<u8> {
+        core::mem::take(&mut self.0)
     }
 
     fn as_slice(&self) -> &[u8] {
         &self.0
     }
 }
+
+impl<B> Zeroize for PlainMessagePublicKey<B> {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl<B> Drop for PlainMessagePublicKey<B> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
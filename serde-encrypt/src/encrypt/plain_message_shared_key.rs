@@ -1,29 +1,52 @@
 //! Shared key encryption
 
 use alloc::vec::Vec;
-use serde_encrypt_core::encrypt::plain_message_shared_key::PlainMessageSharedKeyCore;
+use core::marker::PhantomData;
+use serde_encrypt_core::encrypt::{
+    aead::{AeadAlgorithm, XChaCha20Poly1305},
+    plain_message_shared_key::PlainMessageSharedKeyCore,
+};
+use zeroize::Zeroize;
 
 use crate::random::RngSingletonImpl;
 
 /// Plain message structure serialized via serde.
+///
+/// Zeroized on drop.
 #[derive(Clone, Eq, PartialEq, Debug)]
-pub struct PlainMessageSharedKey(Vec<u8>);
+pub struct PlainMessageSharedKey<A = XChaCha20Poly1305>(Vec<u8>, PhantomData<A>);
 
-impl PlainMessageSharedKeyCore for PlainMessageSharedKey {
+impl<A> PlainMessageSharedKeyCore for PlainMessageSharedKey<A>
+where
+    A: AeadAlgorithm,
+{
     type R = RngSingletonImpl;
+    type A = A;
 
     fn new(plain_message: Vec<u8>) -> Self
     where
         Self: Sized,
     {
-        Self(plain_message)
+        Self(plain_message, PhantomData)
     }
 
-    fn into_vec(self) -> Vec<u8> {
-        self.0
+    fn into_vec(mut self) -> Vec<u8> {
+        core::mem::take(&mut self.0)
     }
 
     fn as_slice(&self) -> &[u8] {
         &self.0
     }
 }
+
+impl<A> Zeroize for PlainMessageSharedKey<A> {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl<A> Drop for PlainMessageSharedKey<A> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
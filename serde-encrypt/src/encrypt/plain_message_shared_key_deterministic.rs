@@ -2,8 +2,11 @@
 
 use alloc::vec::Vec;
 use serde_encrypt_core::encrypt::plain_message_shared_key::PlainMessageSharedKeyDeterministicCore;
+use zeroize::Zeroize;
 
 /// Plain message structure serialized via serde.
+///
+/// Zeroized on drop.
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub struct PlainMessageSharedKeyDeterministic(Vec<u8>);
 
@@ -15,11 +18,23 @@ impl PlainMessageSharedKeyDeterministicCore for PlainMessageSharedKeyDeterminist
         Self(plain_message)
     }
 
-    fn into_vec(self) -> Vec<u8> {
-        self.0
+    fn into_vec(mut self) -> Vec<u8> {
+        core::mem::take(&mut self.0)
     }
 
     fn as_slice(&self) -> &[u8] {
         &self.0
     }
 }
+
+impl Zeroize for PlainMessageSharedKeyDeterministic {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Drop for PlainMessageSharedKeyDeterministic {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
@@ -0,0 +1,44 @@
+//! Sign-then-encrypt public key encryption
+
+use alloc::vec::Vec;
+use serde_encrypt_core::encrypt::plain_message_signed_public_key::PlainMessageSignedPublicKeyCore;
+use zeroize::Zeroize;
+
+use crate::random::RngSingletonImpl;
+
+/// Plain message structure serialized via serde.
+///
+/// Zeroized on drop.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct PlainMessageSignedPublicKey(Vec<u8>);
+
+impl PlainMessageSignedPublicKeyCore for PlainMessageSignedPublicKey {
+    type R = RngSingletonImpl;
+
+    fn new(plain_message: Vec<u8>) -> Self
+    where
+        Self: Sized,
+    {
+        Self(plain_message)
+    }
+
+    fn into_vec(mut self) -> Vec<u8> {
+        core::mem::take(&mut self.0)
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Zeroize for PlainMessageSignedPublicKey {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Drop for PlainMessageSignedPublicKey {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
@@ -0,0 +1,141 @@
+//! Typed cipher-text.
+
+use core::{fmt, marker::PhantomData};
+
+use serde::{de::DeserializeOwned, Serialize};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serializer};
+
+use crate::{shared_key::SharedKey, traits::SerdeEncryptSharedKey, EncryptedMessage, Error};
+
+/// [EncryptedMessage](crate::EncryptedMessage) which remembers the type of its plain-text.
+///
+/// `Encrypted<Payment>` cannot be opened as `User`, so mix-ups of cipher-texts are caught at compile time.
+///
+/// It implements `Serialize` / `Deserialize` in the same way as [EncryptedMessage](crate::EncryptedMessage) (only `serde` feature),
+/// so it can be stored inside other serde structs and database rows.
+///
+/// # Examples
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use serde_encrypt::{
+///     aead::XChaCha20Poly1305, serialize::impls::BincodeSerializer, shared_key::SharedKey,
+///     traits::SerdeEncryptSharedKey, AsSharedKey, Encrypted,
+/// };
+///
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// struct Payment {
+///     amount: u64,
+/// }
+///
+/// impl SerdeEncryptSharedKey for Payment {
+///     type S = BincodeSerializer<Self>;
+///     type A = XChaCha20Poly1305;
+/// }
+///
+/// let shared_key = SharedKey::generate();
+///
+/// let payment = Payment { amount: 100 };
+/// let sealed = Encrypted::seal(&payment, &shared_key).unwrap();
+/// assert_eq!(sealed.open(&shared_key).unwrap(), payment);
+/// ```
+pub struct Encrypted<T> {
+    message: EncryptedMessage,
+    _type: PhantomData<fn() -> T>,
+}
+
+impl<T> Encrypted<T>
+where
+    T: SerdeEncryptSharedKey,
+{
+    /// Serialize and encrypt `value`.
+    ///
+    /// # Failures
+    ///
+    /// - [SerializationError](serde_encrypt_core::error::ErrorKind::SerializationError) when failed to serialize message.
+    /// - [EncryptionError](serde_encrypt_core::error::ErrorKind::EncryptionError) when failed to encrypt serialized message.
+    pub fn seal(value: &T, shared_key: &SharedKey) -> Result<Self, Error>
+    where
+        T: Serialize,
+    {
+        let message = value.encrypt(shared_key)?;
+        Ok(Self::from_message(message))
+    }
+
+    /// Decrypt and deserialize into `T`.
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](serde_encrypt_core::error::ErrorKind::DecryptionError) when failed to decrypt message.
+    /// - [DeserializationError](serde_encrypt_core::error::ErrorKind::DeserializationError) when failed to deserialize decrypted message.
+    pub fn open(&self, shared_key: &SharedKey) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        T::decrypt_owned(&self.message, shared_key)
+    }
+}
+
+impl<T> Encrypted<T> {
+    /// Treats untyped `message` as cipher-text of `T`.
+    ///
+    /// Caller is responsible for `message` really being encrypted from `T`.
+    pub fn from_message(message: EncryptedMessage) -> Self {
+        Self {
+            message,
+            _type: PhantomData,
+        }
+    }
+
+    /// Ref to untyped message.
+    pub fn as_message(&self) -> &EncryptedMessage {
+        &self.message
+    }
+
+    /// Into untyped message.
+    pub fn into_message(self) -> EncryptedMessage {
+        self.message
+    }
+}
+
+impl<T> Clone for Encrypted<T> {
+    fn clone(&self) -> Self {
+        Self::from_message(self.message.clone())
+    }
+}
+
+impl<T> PartialEq for Encrypted<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.message == other.message
+    }
+}
+
+impl<T> Eq for Encrypted<T> {}
+
+impl<T> fmt::Debug for Encrypted<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Encrypted").field(&self.message).finish()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> Serialize for Encrypted<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        Serialize::serialize(&self.message, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> Deserialize<'de> for Encrypted<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let message = <EncryptedMessage as Deserialize>::deserialize(deserializer)?;
+        Ok(Self::from_message(message))
+    }
+}
@@ -0,0 +1,172 @@
+//! Envelope encryption: message is encrypted by a fresh data-encryption key (DEK),
+//! which is wrapped by a key-encryption key (KEK).
+
+use alloc::{format, vec::Vec};
+use core::convert::TryInto;
+
+use serde_encrypt_core::{
+    encrypt::{
+        aead::XChaCha20Poly1305, plain_message_sealed_box::PlainMessageSealedBoxCore,
+        plain_message_shared_key::PlainMessageSharedKeyCore,
+    },
+    key::key_pair::public_key::ReceiverPublicKey,
+};
+
+use crate::{
+    encrypt::{
+        plain_message_sealed_box::PlainMessageSealedBox,
+        plain_message_shared_key::PlainMessageSharedKey,
+    },
+    key::key_pair::ReceiverKeyPair,
+    shared_key::SharedKey,
+    AsSharedKey, EncryptedMessage, Error,
+};
+
+/// Key-encryption key (KEK) which wraps data-encryption keys (DEKs).
+///
+/// Implemented by [SharedKey](crate::shared_key::SharedKey) and [ReceiverPublicKey](crate::ReceiverPublicKey).
+/// Implement this (and [KeyDecryptionKey](KeyDecryptionKey)) for a key in an external KMS to wrap DEKs by the KMS.
+pub trait KeyEncryptionKey {
+    /// Wrap (encrypt) `dek`. Returned bytes are stored in [EnvelopeEncrypted](EnvelopeEncrypted) as they are.
+    ///
+    /// # Failures
+    ///
+    /// - [EncryptionError](serde_encrypt_core::error::ErrorKind::EncryptionError) when failed to wrap `dek`.
+    fn wrap_key(&self, dek: &SharedKey) -> Result<Vec<u8>, Error>;
+}
+
+/// Key which unwraps data-encryption keys (DEKs) wrapped by corresponding [KeyEncryptionKey](KeyEncryptionKey).
+///
+/// Implemented by [SharedKey](crate::shared_key::SharedKey) and [ReceiverKeyPair](crate::key::key_pair::ReceiverKeyPair)
+/// (for DEKs wrapped by its [ReceiverPublicKey](crate::ReceiverPublicKey)).
+pub trait KeyDecryptionKey {
+    /// Unwrap (decrypt) `wrapped_dek` made by [KeyEncryptionKey::wrap_key()](KeyEncryptionKey::wrap_key).
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](serde_encrypt_core::error::ErrorKind::DecryptionError) when failed to unwrap `wrapped_dek`.
+    fn unwrap_key(&self, wrapped_dek: &[u8]) -> Result<SharedKey, Error>;
+}
+
+/// DEK wrapped by XChaCha20-Poly1305 ([SerdeEncryptSharedKey](crate::traits::SerdeEncryptSharedKey) format).
+impl KeyEncryptionKey for SharedKey {
+    fn wrap_key(&self, dek: &SharedKey) -> Result<Vec<u8>, Error> {
+        let plain_msg = PlainMessageSharedKey::<XChaCha20Poly1305>::new(dek.as_slice().to_vec());
+        Ok(plain_msg.encrypt(self)?.serialize_versioned())
+    }
+}
+
+impl KeyDecryptionKey for SharedKey {
+    fn unwrap_key(&self, wrapped_dek: &[u8]) -> Result<SharedKey, Error> {
+        let encrypted_message = EncryptedMessage::deserialize_versioned(wrapped_dek.to_vec())
+            .map_err(|_| Error::decryption_error("malformed wrapped data-encryption key"))?;
+        let plain_msg =
+            PlainMessageSharedKey::<XChaCha20Poly1305>::decrypt(&encrypted_message, self)?;
+        dek_from_slice(plain_msg.as_slice())
+    }
+}
+
+/// DEK wrapped by sealed box ([SerdeEncryptSealedBox](crate::traits::SerdeEncryptSealedBox) format).
+impl KeyEncryptionKey for ReceiverPublicKey {
+    fn wrap_key(&self, dek: &SharedKey) -> Result<Vec<u8>, Error> {
+        let plain_msg = PlainMessageSealedBox::new(dek.as_slice().to_vec());
+        Ok(plain_msg.encrypt(self)?.serialize_versioned())
+    }
+}
+
+impl KeyDecryptionKey for ReceiverKeyPair {
+    fn unwrap_key(&self, wrapped_dek: &[u8]) -> Result<SharedKey, Error> {
+        let encrypted_message = EncryptedMessage::deserialize_versioned(wrapped_dek.to_vec())
+            .map_err(|_| Error::decryption_error("malformed wrapped data-encryption key"))?;
+        let plain_msg = PlainMessageSealedBox::decrypt(&encrypted_message, self)?;
+        dek_from_slice(plain_msg.as_slice())
+    }
+}
+
+fn dek_from_slice(dek: &[u8]) -> Result<SharedKey, Error> {
+    let dek: [u8; 32] = dek
+        .try_into()
+        .map_err(|_| Error::decryption_error("unwrapped data-encryption key is not 32 bytes"))?;
+    Ok(SharedKey::new(dek))
+}
+
+/// Message encrypted by a fresh data-encryption key (DEK), with the DEK wrapped by a key-encryption key (KEK).
+///
+/// Made by [SerdeEncryptEnvelope](crate::traits::SerdeEncryptEnvelope).
+///
+/// ```text
+/// wrapped DEK length (u32 BE) || wrapped DEK || encrypted message (versioned format)
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct EnvelopeEncrypted {
+    wrapped_dek: Vec<u8>,
+    encrypted_message: EncryptedMessage,
+}
+
+impl EnvelopeEncrypted {
+    #[doc(hidden)]
+    pub fn new(wrapped_dek: Vec<u8>, encrypted_message: EncryptedMessage) -> Self {
+        Self {
+            wrapped_dek,
+            encrypted_message,
+        }
+    }
+
+    /// Ref to DEK wrapped by KEK.
+    pub fn wrapped_dek(&self) -> &[u8] {
+        &self.wrapped_dek
+    }
+
+    /// Ref to message encrypted by DEK.
+    pub fn encrypted_message(&self) -> &EncryptedMessage {
+        &self.encrypted_message
+    }
+
+    /// Serialize into binary.
+    ///
+    /// # Failures
+    ///
+    /// - [SerializationError](serde_encrypt_core::error::ErrorKind::SerializationError) when wrapped DEK is too long for its 4-byte length prefix.
+    pub fn serialize(self) -> Result<Vec<u8>, Error> {
+        let wrapped_dek_len: u32 = self
+            .wrapped_dek
+            .len()
+            .try_into()
+            .map_err(|_| Error::serialization_error("wrapped DEK is too long"))?;
+        let encrypted_message = self.encrypted_message.serialize_versioned();
+        let mut serialized =
+            Vec::with_capacity(4 + self.wrapped_dek.len() + encrypted_message.len());
+        serialized.extend_from_slice(&wrapped_dek_len.to_be_bytes());
+        serialized.extend_from_slice(&self.wrapped_dek);
+        serialized.extend_from_slice(&encrypted_message);
+        Ok(serialized)
+    }
+
+    /// Deserialize from binary made by [serialize()](Self::serialize).
+    ///
+    /// # Failures
+    ///
+    /// - [DeserializationError](serde_encrypt_core::error::ErrorKind::DeserializationError) when wrapped DEK or message header is truncated.
+    /// - [DecryptionError](serde_encrypt_core::error::ErrorKind::DecryptionError) when encrypted message does not have nonce.
+    pub fn deserialize(mut serialized: Vec<u8>) -> Result<Self, Error> {
+        let wrapped_dek_len: [u8; 4] = serialized
+            .get(..4)
+            .ok_or_else(|| Error::deserialization_error("wrapped DEK length is truncated"))?
+            .try_into()
+            .expect("4 bytes");
+        let wrapped_dek_len = u32::from_be_bytes(wrapped_dek_len) as usize;
+        if serialized.len() - 4 < wrapped_dek_len {
+            return Err(Error::deserialization_error(&format!(
+                "wrapped DEK is truncated (expected {} bytes)",
+                wrapped_dek_len
+            )));
+        }
+
+        let encrypted_message = serialized.split_off(4 + wrapped_dek_len);
+        let wrapped_dek = serialized.split_off(4);
+        Ok(Self {
+            wrapped_dek,
+            encrypted_message: EncryptedMessage::deserialize_versioned(encrypted_message)?,
+        })
+    }
+}
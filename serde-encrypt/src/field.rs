@@ -0,0 +1,95 @@
+//! Field-level encryption via serde `with` adapters (only `std` feature).
+//!
+//! Encrypts only sensitive fields of a larger struct, leaving the rest readable.
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use serde_encrypt::{
+//!     aead::XChaCha20Poly1305, field, serialize::impls::BincodeSerializer,
+//!     shared_key::SharedKey, traits::SerdeEncryptSharedKey, AsSharedKey,
+//! };
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Ssn(String);
+//!
+//! impl SerdeEncryptSharedKey for Ssn {
+//!     type S = BincodeSerializer<Self>;
+//!     type A = XChaCha20Poly1305;
+//! }
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct User {
+//!     name: String,
+//!     #[serde(with = "serde_encrypt::field::shared_key")]
+//!     ssn: Ssn,
+//! }
+//!
+//! let shared_key = SharedKey::generate();
+//! let user = User {
+//!     name: "Alice".to_string(),
+//!     ssn: Ssn("123-45-6789".to_string()),
+//! };
+//!
+//! let json = field::with_shared_key(&shared_key, || serde_json::to_string(&user)).unwrap();
+//! assert!(!json.contains("123-45-6789"));
+//!
+//! let decoded: User = field::with_shared_key(&shared_key, || serde_json::from_str(&json)).unwrap();
+//! assert_eq!(decoded, user);
+//! ```
+//!
+//! # Key context
+//!
+//! Adapters read [SharedKey](crate::shared_key::SharedKey) from a thread-local context:
+//!
+//! - [with_shared_key()](self::with_shared_key) sets the key only while the given closure runs (per scope).
+//! - [set_shared_key()](self::set_shared_key) sets the key until [clear_shared_key()](self::clear_shared_key) is called (per thread).
+//!
+//! Serialization fails when no key is set.
+
+pub mod shared_key;
+
+use std::cell::RefCell;
+
+use crate::shared_key::SharedKey;
+
+thread_local! {
+    static SHARED_KEY: RefCell<Option<SharedKey>> = const { RefCell::new(None) };
+}
+
+/// Sets shared key used by field adapters in the current thread.
+pub fn set_shared_key(shared_key: SharedKey) {
+    SHARED_KEY.with(|k| *k.borrow_mut() = Some(shared_key));
+}
+
+/// Clears shared key set by [set_shared_key()](self::set_shared_key) in the current thread.
+pub fn clear_shared_key() {
+    SHARED_KEY.with(|k| *k.borrow_mut() = None);
+}
+
+/// Runs `f` with `shared_key` used by field adapters.
+///
+/// The previous key (if any) is restored after `f` returns (or panics), so scopes can be nested.
+pub fn with_shared_key<F, R>(shared_key: &SharedKey, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    struct Restore(Option<SharedKey>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let prev = self.0.take();
+            SHARED_KEY.with(|k| *k.borrow_mut() = prev);
+        }
+    }
+
+    let prev = SHARED_KEY.with(|k| k.borrow_mut().replace(shared_key.clone()));
+    let _restore = Restore(prev);
+    f()
+}
+
+/// Runs `f` with the shared key in context, or returns `None` if no key is set.
+fn with_current_shared_key<F, R>(f: F) -> Option<R>
+where
+    F: FnOnce(&SharedKey) -> R,
+{
+    SHARED_KEY.with(|k| k.borrow().as_ref().map(f))
+}
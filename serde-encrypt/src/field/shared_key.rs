@@ -0,0 +1,53 @@
+//! `#[serde(with = "serde_encrypt::field::shared_key")]` adapter.
+//!
+//! Encrypts a field by [SerdeEncryptSharedKey](crate::traits::SerdeEncryptSharedKey)
+//! and emits base64 string of versioned [EncryptedMessage](crate::EncryptedMessage) binary.
+//! Key is read from context (see [field](crate::field) module).
+
+use alloc::{format, string::String};
+
+use serde::{de, de::DeserializeOwned, ser, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::with_current_shared_key;
+use crate::{traits::SerdeEncryptSharedKey, EncryptedMessage};
+
+const NO_KEY: &str =
+    "no shared key is set for serde_encrypt::field (use with_shared_key() or set_shared_key())";
+
+/// Encrypts `value` and serializes it as base64 string.
+///
+/// # Failures
+///
+/// - When no shared key is set in context.
+/// - When failed to serialize or encrypt `value`.
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: SerdeEncryptSharedKey + Serialize,
+    S: Serializer,
+{
+    let encrypted = with_current_shared_key(|shared_key| value.encrypt(shared_key))
+        .ok_or_else(|| ser::Error::custom(NO_KEY))?
+        .map_err(ser::Error::custom)?;
+    base64::encode(encrypted.serialize_versioned()).serialize(serializer)
+}
+
+/// Deserializes base64 string and decrypts it.
+///
+/// # Failures
+///
+/// - When no shared key is set in context.
+/// - When input is not base64 string of EncryptedMessage binary.
+/// - When failed to decrypt or deserialize.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: SerdeEncryptSharedKey + DeserializeOwned,
+    D: Deserializer<'de>,
+{
+    let encoded = String::deserialize(deserializer)?;
+    let bin = base64::decode(&encoded)
+        .map_err(|e| de::Error::custom(format!("invalid base64 of encrypted field: {}", e)))?;
+    let encrypted = EncryptedMessage::deserialize_versioned(bin).map_err(de::Error::custom)?;
+    with_current_shared_key(|shared_key| T::decrypt_owned(&encrypted, shared_key))
+        .ok_or_else(|| de::Error::custom(NO_KEY))?
+        .map_err(de::Error::custom)
+}
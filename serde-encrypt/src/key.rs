@@ -2,3 +2,5 @@
 
 #[deny(missing_docs)]
 pub mod key_pair;
+#[deny(missing_docs)]
+pub mod signing_key_pair;
@@ -0,0 +1,36 @@
+//! Ed25519 key-pair (signing key and verifying key).
+
+use serde_encrypt_core::key::signing_key_pair::{
+    SigningKeyPairCore, SigningPrivateKey, VerifyingKey,
+};
+
+use crate::random::RngSingletonImpl;
+
+/// Message sender's Ed25519 identity key pair
+#[derive(Clone, Debug)]
+pub struct SigningKeyPair {
+    signing_private_key: SigningPrivateKey,
+    verifying_key: VerifyingKey,
+}
+
+impl SigningKeyPairCore for SigningKeyPair {
+    type R = RngSingletonImpl;
+
+    fn new(signing_private_key: SigningPrivateKey) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            verifying_key: signing_private_key.verifying_key(),
+            signing_private_key,
+        }
+    }
+
+    fn private_key(&self) -> &SigningPrivateKey {
+        &self.signing_private_key
+    }
+
+    fn verifying_key(&self) -> &VerifyingKey {
+        &self.verifying_key
+    }
+}
@@ -0,0 +1,112 @@
+//! Key ring of shared keys indexed by key ID, for key rotation.
+
+use alloc::{collections::BTreeMap, format};
+
+use crate::{shared_key::SharedKey, EncryptedMessage, Error};
+
+/// Several [SharedKey](crate::shared_key::SharedKey)s indexed by key ID, one of which is primary.
+///
+/// [SerdeEncryptSharedKey::encrypt_with_key_ring()](crate::traits::SerdeEncryptSharedKey::encrypt_with_key_ring)
+/// encrypts with the primary key and records its key ID in [MessageHeader](crate::MessageHeader),
+/// so that decryption chooses the right key even after the primary key is rotated.
+///
+/// Store messages by [EncryptedMessage::serialize_versioned()](crate::EncryptedMessage::serialize_versioned).
+/// [EncryptedMessage::serialize()](crate::EncryptedMessage::serialize) writes version 0 binary without header,
+/// so the key ID is lost and the message cannot be decrypted with a key ring.
+///
+/// # Key rotation
+///
+/// 1. [rotate()](Self::rotate) adds a new key and makes it primary. New messages are encrypted with it.
+/// 2. Old messages are still decrypted by old keys in the ring, and [re-encrypted](crate::traits::SerdeEncryptSharedKey::reencrypt_with_key_ring) under the new primary key in background.
+/// 3. After all messages are re-encrypted, [remove()](Self::remove) old keys.
+#[derive(Clone, Debug)]
+pub struct KeyRing {
+    keys: BTreeMap<u32, SharedKey>,
+    primary_key_id: u32,
+}
+
+impl KeyRing {
+    /// Key ring with only the primary key.
+    pub fn new(primary_key_id: u32, primary_key: SharedKey) -> Self {
+        let mut keys = BTreeMap::new();
+        keys.insert(primary_key_id, primary_key);
+        Self {
+            keys,
+            primary_key_id,
+        }
+    }
+
+    /// Adds a non-primary key (e.g. an old key to decrypt stored messages).
+    ///
+    /// # Failures
+    ///
+    /// - [KeyError](serde_encrypt_core::error::ErrorKind::KeyError) when `key_id` is already used, because messages with `key_id` would be decrypted by a wrong key.
+    pub fn insert(&mut self, key_id: u32, key: SharedKey) -> Result<(), Error> {
+        if self.keys.contains_key(&key_id) {
+            return Err(Error::key_error(&format!(
+                "key ID {} is already used in key ring",
+                key_id
+            )));
+        }
+        self.keys.insert(key_id, key);
+        Ok(())
+    }
+
+    /// Adds a new key and makes it primary. Previous primary key remains in the ring to decrypt old messages.
+    ///
+    /// # Failures
+    ///
+    /// - [KeyError](serde_encrypt_core::error::ErrorKind::KeyError) when `key_id` is already used.
+    pub fn rotate(&mut self, key_id: u32, key: SharedKey) -> Result<(), Error> {
+        self.insert(key_id, key)?;
+        self.primary_key_id = key_id;
+        Ok(())
+    }
+
+    /// Removes a non-primary key. Returns `None` if `key_id` is not found or is the primary key's.
+    pub fn remove(&mut self, key_id: u32) -> Option<SharedKey> {
+        if key_id == self.primary_key_id {
+            return None;
+        }
+        self.keys.remove(&key_id)
+    }
+
+    /// ID of the primary key.
+    pub fn primary_key_id(&self) -> u32 {
+        self.primary_key_id
+    }
+
+    /// Primary key, which encrypts new messages.
+    pub fn primary_key(&self) -> &SharedKey {
+        self.keys
+            .get(&self.primary_key_id)
+            .expect("primary key is always in key ring")
+    }
+
+    /// Key of `key_id`.
+    pub fn get(&self, key_id: u32) -> Option<&SharedKey> {
+        self.keys.get(&key_id)
+    }
+
+    /// Iterator of key IDs in ascending order.
+    pub fn key_ids(&self) -> impl Iterator<Item = u32> + '_ {
+        self.keys.keys().copied()
+    }
+
+    /// Key which encrypted `encrypted_message`, chosen by key ID in its header.
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](serde_encrypt_core::error::ErrorKind::DecryptionError) when:
+    ///   - `encrypted_message` does not have key ID (e.g. encrypted without key ring).
+    ///   - key of the key ID is not in this key ring.
+    pub fn key_for(&self, encrypted_message: &EncryptedMessage) -> Result<&SharedKey, Error> {
+        let key_id = encrypted_message
+            .header()
+            .and_then(|h| h.key_id())
+            .ok_or_else(|| Error::decryption_error("encrypted message does not have key ID"))?;
+        self.get(key_id).ok_or_else(|| {
+            Error::decryption_error(&format!("key ID {} is not found in key ring", key_id))
+        })
+    }
+}
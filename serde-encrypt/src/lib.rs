@@ -7,14 +7,25 @@
 
 extern crate alloc;
 
+#[cfg(feature = "tokio")]
+pub mod async_io;
+#[cfg(feature = "codec")]
+pub mod codec;
 pub mod encrypt;
+pub mod envelope;
+#[cfg(feature = "std")]
+pub mod field;
 pub mod key;
+pub mod key_ring;
 pub mod serialize;
 pub mod shared_key;
 pub mod traits;
 
+mod encrypted;
 mod random;
 
+pub use encrypted::Encrypted;
+
 cfg_if::cfg_if! {
     if #[cfg(feature = "std")] {
         use once_cell::sync::Lazy;
@@ -25,11 +36,19 @@ cfg_if::cfg_if! {
 }
 
 pub use serde_encrypt_core::{
-    encrypt::encrypted_message::EncryptedMessage,
+    encrypt::aead,
+    encrypt::encrypted_message::{Algorithm, EncryptedMessage, EncryptionMode, MessageHeader},
+    encrypt::public_key_backend,
+    encrypt::stream,
     error::{Error, ErrorKind},
     key::{
         as_shared_key::AsSharedKey,
         combined_key::{ReceiverCombinedKey, SenderCombinedKey},
-        key_pair::{ReceiverKeyPairCore, SenderKeyPairCore},
+        key_pair::{
+            public_key::{ReceiverPublicKey, SenderPublicKey},
+            ReceiverKeyPairCore, SenderKeyPairCore,
+        },
+        password_key::{PasswordKdf, PasswordKeyHeader},
+        signing_key_pair::{SigningKeyPairCore, VerifyingKey},
     },
 };
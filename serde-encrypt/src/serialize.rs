@@ -1,6 +1,8 @@
 //! Serializer trait and default implementation.
 
+pub mod compress;
 pub mod impls;
+pub mod padding;
 
 use crate::Error;
 use alloc::vec::Vec;
@@ -9,6 +11,9 @@ use serde::{Deserialize, Serialize};
 /// Serialization abstract with type to serialize.
 ///
 /// Serializer implementations must implement this trait.
+///
+/// Serialized data is plain-text before encryption (and after decryption).
+/// Implementations should zeroize it on drop, as the ones in [impls](crate::serialize::impls) do.
 pub trait TypedSerialized {
     /// Type to serialize
     type T;
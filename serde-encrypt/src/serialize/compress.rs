@@ -0,0 +1,114 @@
+//! Compressors applied to serialized plain-text before encryption, by [Compressed](crate::serialize::impls::Compressed) serializer.
+//!
+//! Each compressor is behind a feature flag:
+//!
+//! | Compressor | Feature | ID | `no_std` |
+//! |------------|---------|----|----------|
+//! | [Deflate](self::Deflate) (raw DEFLATE by [miniz_oxide](https://docs.rs/miniz_oxide)) | `deflate` | 1 | yes |
+//! | [Lz4](self::Lz4) (LZ4 block by [lz4_flex](https://docs.rs/lz4_flex)) | `lz4` | 2 | yes |
+//! | [Zstd](self::Zstd) (Zstandard by [zstd](https://docs.rs/zstd)) | `zstd` | 3 | no |
+//!
+//! Compressed plain-text starts with the compressor ID, so that decryption undoes the compression
+//! even after a type switches to another built-in compressor (as long as the old compressor's feature is enabled).
+//!
+//! Decompressed size is bounded by [MAX_DECOMPRESSED_SIZE](self::MAX_DECOMPRESSED_SIZE).
+
+use crate::Error;
+use alloc::vec::Vec;
+
+#[cfg(feature = "deflate")]
+mod deflate;
+#[cfg(feature = "lz4")]
+mod lz4;
+#[cfg(feature = "zstd")]
+mod zstd;
+
+#[cfg(feature = "zstd")]
+pub use self::zstd::Zstd;
+#[cfg(feature = "deflate")]
+pub use deflate::Deflate;
+#[cfg(feature = "lz4")]
+pub use lz4::Lz4;
+
+/// Upper bound of decompressed size (64 MiB).
+///
+/// Compressed plain-text may come from anyone who has the receiver's public key (e.g. [SerdeEncryptSealedBox](crate::traits::SerdeEncryptSealedBox)),
+/// so decompression stops at this size before a few bytes expand into gigabytes (decompression bomb).
+/// Larger plain-text is not compressed but rejected.
+pub const MAX_DECOMPRESSED_SIZE: usize = 64 << 20;
+
+/// Compression algorithm.
+///
+/// Implement this to use another compression algorithm. Choose an ID not used by the built-in compressors (e.g. >= 128).
+pub trait Compressor {
+    /// Compressor ID recorded in front of compressed plain-text.
+    const ID: u8;
+
+    /// # Failures
+    ///
+    /// - [SerializationError](serde_encrypt_core::error::ErrorKind::SerializationError) when failed to compress.
+    fn compress(plain: &[u8]) -> Result<Vec<u8>, Error>;
+
+    /// Must fail before allocating more than [MAX_DECOMPRESSED_SIZE](MAX_DECOMPRESSED_SIZE) bytes.
+    ///
+    /// # Failures
+    ///
+    /// - [DeserializationError](serde_encrypt_core::error::ErrorKind::DeserializationError) when failed to decompress
+    ///   or decompressed size exceeds [MAX_DECOMPRESSED_SIZE](MAX_DECOMPRESSED_SIZE).
+    fn decompress(compressed: &[u8]) -> Result<Vec<u8>, Error>;
+}
+
+/// Compresses `plain` by `C` and prepends `C::ID`.
+pub(crate) fn compress<C: Compressor>(plain: &[u8]) -> Result<Vec<u8>, Error> {
+    if plain.len() > MAX_DECOMPRESSED_SIZE {
+        return Err(Error::serialization_error(&alloc::format!(
+            "plain-text to compress exceeds {} bytes",
+            MAX_DECOMPRESSED_SIZE
+        )));
+    }
+    let compressed = C::compress(plain)?;
+
+    let mut with_id = Vec::with_capacity(1 + compressed.len());
+    with_id.push(C::ID);
+    with_id.extend_from_slice(&compressed);
+    Ok(with_id)
+}
+
+/// Decompresses by `C` or by the built-in compressor whose ID is in front of `with_id`.
+pub(crate) fn decompress<C: Compressor>(with_id: &[u8]) -> Result<Vec<u8>, Error> {
+    let (id, compressed) = with_id
+        .split_first()
+        .ok_or_else(|| Error::deserialization_error("compressed data lacks compressor ID"))?;
+
+    let decompressed = match *id {
+        id if id == C::ID => C::decompress(compressed),
+        #[cfg(feature = "deflate")]
+        Deflate::ID => Deflate::decompress(compressed),
+        #[cfg(feature = "lz4")]
+        Lz4::ID => Lz4::decompress(compressed),
+        #[cfg(feature = "zstd")]
+        Zstd::ID => Zstd::decompress(compressed),
+        _ => Err(Error::deserialization_error(&alloc::format!(
+            "unknown compressor ID {} (is its feature enabled?)",
+            id
+        ))),
+    }?;
+
+    // in case a custom compressor does not bound its output
+    if decompressed.len() > MAX_DECOMPRESSED_SIZE {
+        return Err(too_large());
+    }
+    Ok(decompressed)
+}
+
+/// Error on decompressed size exceeding [MAX_DECOMPRESSED_SIZE](MAX_DECOMPRESSED_SIZE).
+#[cfg_attr(
+    not(any(feature = "deflate", feature = "lz4", feature = "zstd")),
+    allow(dead_code)
+)]
+fn too_large() -> Error {
+    Error::deserialization_error(&alloc::format!(
+        "decompressed data exceeds {} bytes",
+        MAX_DECOMPRESSED_SIZE
+    ))
+}
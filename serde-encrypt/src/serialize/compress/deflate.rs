@@ -0,0 +1,32 @@
+use super::{too_large, Compressor, MAX_DECOMPRESSED_SIZE};
+use crate::Error;
+use alloc::{format, vec::Vec};
+
+/// Raw DEFLATE ([RFC 1951](https://www.rfc-editor.org/rfc/rfc1951)) by [miniz_oxide](https://docs.rs/miniz_oxide), at compression level 6.
+///
+/// Pure Rust and available in `no_std`.
+#[derive(Clone, Copy, Debug)]
+pub struct Deflate;
+
+impl Compressor for Deflate {
+    const ID: u8 = 1;
+
+    fn compress(plain: &[u8]) -> Result<Vec<u8>, Error> {
+        Ok(miniz_oxide::deflate::compress_to_vec(plain, 6))
+    }
+
+    fn decompress(compressed: &[u8]) -> Result<Vec<u8>, Error> {
+        use miniz_oxide::inflate::{self, TINFLStatus};
+
+        inflate::decompress_to_vec_with_limit(compressed, MAX_DECOMPRESSED_SIZE).map_err(|e| {
+            match e.status {
+                TINFLStatus::HasMoreOutput => too_large(),
+                // not `e`, which holds partially decompressed plain-text
+                status => Error::deserialization_error(&format!(
+                    "failed to decompress by DEFLATE: {:?}",
+                    status
+                )),
+            }
+        })
+    }
+}
@@ -0,0 +1,35 @@
+use super::{too_large, Compressor, MAX_DECOMPRESSED_SIZE};
+use crate::Error;
+use alloc::{format, vec::Vec};
+use core::convert::TryInto;
+
+/// LZ4 block format with uncompressed size prepended, by [lz4_flex](https://docs.rs/lz4_flex).
+///
+/// Pure Rust and available in `no_std`. Faster but compresses less than [Deflate](super::Deflate).
+#[derive(Clone, Copy, Debug)]
+pub struct Lz4;
+
+impl Compressor for Lz4 {
+    const ID: u8 = 2;
+
+    fn compress(plain: &[u8]) -> Result<Vec<u8>, Error> {
+        Ok(lz4_flex::compress_prepend_size(plain))
+    }
+
+    fn decompress(compressed: &[u8]) -> Result<Vec<u8>, Error> {
+        // Checks size prefix before `lz4_flex::decompress_size_prepended()` allocates that much.
+        let size: [u8; 4] = compressed
+            .get(..4)
+            .ok_or_else(|| Error::deserialization_error("LZ4 size prefix is truncated"))?
+            .try_into()
+            .expect("4 bytes");
+        let size = u32::from_le_bytes(size) as usize;
+        if size > MAX_DECOMPRESSED_SIZE {
+            return Err(too_large());
+        }
+
+        lz4_flex::decompress(&compressed[4..], size).map_err(|e| {
+            Error::deserialization_error(&format!("failed to decompress by LZ4: {:?}", e))
+        })
+    }
+}
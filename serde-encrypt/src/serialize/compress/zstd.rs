@@ -0,0 +1,31 @@
+use super::{too_large, Compressor, MAX_DECOMPRESSED_SIZE};
+use crate::Error;
+use alloc::{format, vec::Vec};
+
+/// Zstandard ([RFC 8878](https://www.rfc-editor.org/rfc/rfc8878)) by [zstd](https://docs.rs/zstd), at default compression level (3).
+///
+/// Best compression ratio of the compressors, but binds C library and needs `std`.
+#[derive(Clone, Copy, Debug)]
+pub struct Zstd;
+
+impl Compressor for Zstd {
+    const ID: u8 = 3;
+
+    fn compress(plain: &[u8]) -> Result<Vec<u8>, Error> {
+        ::zstd::bulk::compress(plain, ::zstd::DEFAULT_COMPRESSION_LEVEL).map_err(|e| {
+            Error::serialization_error(&format!("failed to compress by zstd: {:?}", e))
+        })
+    }
+
+    fn decompress(compressed: &[u8]) -> Result<Vec<u8>, Error> {
+        // Content size in frame header (written by `compress()`) avoids allocating the upper bound.
+        let capacity = match ::zstd::zstd_safe::get_frame_content_size(compressed) {
+            Ok(Some(size)) if size > MAX_DECOMPRESSED_SIZE as u64 => return Err(too_large()),
+            Ok(Some(size)) => size as usize,
+            _ => MAX_DECOMPRESSED_SIZE,
+        };
+        ::zstd::bulk::decompress(compressed, capacity).map_err(|e| {
+            Error::deserialization_error(&format!("failed to decompress by zstd: {:?}", e))
+        })
+    }
+}
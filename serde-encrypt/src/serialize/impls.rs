@@ -3,12 +3,40 @@
 mod postcard_serializer;
 pub use postcard_serializer::PostcardSerializer;
 
+#[cfg(feature = "serde_cbor")]
 mod cbor_serializer;
+#[cfg(feature = "serde_cbor")]
+#[allow(deprecated)]
 pub use cbor_serializer::CborSerializer;
 
+#[cfg(feature = "ciborium")]
+mod ciborium_serializer;
+#[cfg(feature = "ciborium")]
+pub use ciborium_serializer::CiboriumSerializer;
+
+#[cfg(feature = "json")]
+mod json_serializer;
+#[cfg(feature = "json")]
+pub use json_serializer::JsonSerializer;
+
+mod compressed;
+pub use compressed::Compressed;
+
+mod padded;
+pub use padded::Padded;
+
 cfg_if::cfg_if! {
     if #[cfg(feature = "std")] {
         mod bincode_serializer;
         pub use bincode_serializer::BincodeSerializer;
+
+        mod rmp_serializer;
+        pub use rmp_serializer::RmpSerializer;
+
+        /// Serializer used when not specified (e.g. by derive macros): [BincodeSerializer](self::BincodeSerializer) with `std` feature.
+        pub type DefaultSerializer<T> = BincodeSerializer<T>;
+    } else {
+        /// Serializer used when not specified (e.g. by derive macros): [PostcardSerializer](self::PostcardSerializer) without `std` feature.
+        pub type DefaultSerializer<T> = PostcardSerializer<T>;
     }
 }
@@ -2,6 +2,7 @@ use crate::{serialize::TypedSerialized, Error};
 use alloc::{format, vec::Vec};
 use core::marker::PhantomData;
 use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
 
 /// [bincode](https://docs.rs/bincode) serializer
 #[derive(Debug)]
@@ -19,7 +20,7 @@ impl<T> TypedSerialized for BincodeSerializer<T> {
     {
         Self {
             serialized,
-            _type: PhantomData::default(),
+            _type: PhantomData,
         }
     }
 
@@ -27,8 +28,8 @@ impl<T> TypedSerialized for BincodeSerializer<T> {
         &self.serialized
     }
 
-    fn into_vec(self) -> Vec<u8> {
-        self.serialized
+    fn into_vec(mut self) -> Vec<u8> {
+        core::mem::take(&mut self.serialized)
     }
 
     fn serialize(v: &Self::T) -> Result<Self, Error>
@@ -55,6 +56,18 @@ impl<T> TypedSerialized for BincodeSerializer<T> {
     }
 }
 
+impl<T> Zeroize for BincodeSerializer<T> {
+    fn zeroize(&mut self) {
+        self.serialized.zeroize();
+    }
+}
+
+impl<T> Drop for BincodeSerializer<T> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,9 +1,16 @@
+#![allow(deprecated)]
+
 use crate::{serialize::TypedSerialized, Error};
 use alloc::{format, vec::Vec};
 use core::marker::PhantomData;
 use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
 
-/// [CBOR](https://docs.rs/serde_cbor) serializer
+/// [CBOR](https://docs.rs/serde_cbor) serializer (only `serde_cbor` feature)
+///
+/// `serde_cbor` is no longer maintained. `CiboriumSerializer` (`ciborium` feature) produces the same bytes
+/// and decrypts messages made by this serializer.
+#[deprecated(note = "use CiboriumSerializer, which produces the same bytes")]
 #[derive(Debug)]
 pub struct CborSerializer<T> {
     serialized: Vec<u8>,
@@ -19,7 +26,7 @@ impl<T> TypedSerialized for CborSerializer<T> {
     {
         Self {
             serialized,
-            _type: PhantomData::default(),
+            _type: PhantomData,
         }
     }
 
@@ -27,8 +34,8 @@ impl<T> TypedSerialized for CborSerializer<T> {
         &self.serialized
     }
 
-    fn into_vec(self) -> Vec<u8> {
-        self.serialized
+    fn into_vec(mut self) -> Vec<u8> {
+        core::mem::take(&mut self.serialized)
     }
 
     /// # Failures
@@ -61,6 +68,18 @@ impl<T> TypedSerialized for CborSerializer<T> {
     }
 }
 
+impl<T> Zeroize for CborSerializer<T> {
+    fn zeroize(&mut self) {
+        self.serialized.zeroize();
+    }
+}
+
+impl<T> Drop for CborSerializer<T> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
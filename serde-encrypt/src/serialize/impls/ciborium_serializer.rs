@@ -0,0 +1,202 @@
+mod de;
+
+use crate::{serialize::TypedSerialized, Error};
+use alloc::{format, vec::Vec};
+use ciborium::Value;
+use core::{fmt, marker::PhantomData};
+use serde::{Deserialize, Serialize};
+use spin::Once;
+use zeroize::Zeroize;
+
+/// [CBOR](https://docs.rs/ciborium) serializer
+///
+/// Produces the same bytes as [CborSerializer](super::CborSerializer) and deserializes them in the same way,
+/// so that messages encrypted with either serializer are decrypted with the other.
+/// Prefer this one since `serde_cbor`, on which `CborSerializer` is based, is no longer maintained.
+pub struct CiboriumSerializer<T> {
+    serialized: Vec<u8>,
+    /// Decoded on the first [deserialize()](Self::deserialize).
+    ///
+    /// Kept in `self` because types borrowing strings or bytes (e.g. `&'de str`) are deserialized
+    /// with references to this value, which must live as long as `&'de self`.
+    decoded: Once<Result<Value, Error>>,
+    _type: PhantomData<T>,
+}
+
+/// Error messages are cached with the decoded value, so they only tell where decoding failed and do not hold plain text.
+fn decode(serialized: &[u8]) -> Result<Value, Error> {
+    ciborium::de::from_reader(serialized).map_err(|e| {
+        let reason = match e {
+            ciborium::de::Error::Io(_) => "unexpected end of input".into(),
+            ciborium::de::Error::Syntax(offset) => format!("syntax error at offset {}", offset),
+            ciborium::de::Error::Semantic(Some(offset), _) => {
+                format!("semantic error at offset {}", offset)
+            }
+            ciborium::de::Error::Semantic(None, _) => "semantic error".into(),
+            ciborium::de::Error::RecursionLimitExceeded => "recursion limit exceeded".into(),
+        };
+        Error::deserialization_error(&format!(
+            "error on ciborium deserialization after decryption: {}",
+            reason
+        ))
+    })
+}
+
+impl<T> fmt::Debug for CiboriumSerializer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CiboriumSerializer")
+            .field("serialized", &self.serialized)
+            .finish()
+    }
+}
+
+impl<T> TypedSerialized for CiboriumSerializer<T> {
+    type T = T;
+
+    /// From CBOR bytes.
+    /// Decoding error is returned by [deserialize()](Self::deserialize).
+    fn new(serialized: Vec<u8>) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            serialized,
+            decoded: Once::new(),
+            _type: PhantomData,
+        }
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        &self.serialized
+    }
+
+    fn into_vec(mut self) -> Vec<u8> {
+        core::mem::take(&mut self.serialized)
+    }
+
+    /// # Failures
+    ///
+    /// - [SerializationError](serde_encrypt_core::error::ErrorKind::SerializationError) when failed to serialize message.
+    fn serialize(v: &Self::T) -> Result<Self, Error>
+    where
+        Self: Sized,
+        Self::T: Serialize,
+    {
+        let mut serialized = Vec::new();
+        ciborium::ser::into_writer(v, &mut serialized).map_err(|e| {
+            Error::serialization_error(&format!("failed to serialize data by ciborium: {:?}", e))
+        })?;
+        Ok(Self {
+            serialized,
+            decoded: Once::new(),
+            _type: PhantomData,
+        })
+    }
+
+    /// # Failures
+    ///
+    /// - [DeserializationError](serde_encrypt_core::error::ErrorKind::DeserializationError) when failed to deserialize decrypted message.
+    fn deserialize<'de>(&'de self) -> Result<Self::T, Error>
+    where
+        Self::T: Deserialize<'de>,
+    {
+        let value = self
+            .decoded
+            .call_once(|| decode(&self.serialized))
+            .as_ref()
+            .map_err(Clone::clone)?;
+        T::deserialize(de::ValueDeserializer(value)).map_err(|e| {
+            Error::deserialization_error(&format!(
+                "error on ciborium deserialization after decryption: {:?}",
+                e
+            ))
+        })
+    }
+}
+
+fn zeroize_value(value: &mut Value) {
+    match value {
+        Value::Integer(i) => *i = 0.into(),
+        Value::Bytes(b) => b.zeroize(),
+        Value::Float(f) => f.zeroize(),
+        Value::Text(s) => s.zeroize(),
+        Value::Bool(b) => b.zeroize(),
+        Value::Tag(tag, v) => {
+            tag.zeroize();
+            zeroize_value(v);
+        }
+        Value::Array(a) => a.iter_mut().for_each(zeroize_value),
+        Value::Map(m) => m.iter_mut().for_each(|(k, v)| {
+            zeroize_value(k);
+            zeroize_value(v);
+        }),
+        _ => {}
+    }
+    *value = Value::Null;
+}
+
+impl<T> Zeroize for CiboriumSerializer<T> {
+    fn zeroize(&mut self) {
+        self.serialized.zeroize();
+        if let Some(Ok(value)) = self.decoded.get_mut() {
+            zeroize_value(value);
+        }
+    }
+}
+
+impl<T> Drop for CiboriumSerializer<T> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ciborium_serializer() -> Result<(), Error> {
+        #[derive(PartialEq, Debug, Serialize, Deserialize)]
+        struct Message(i32);
+
+        let msg = Message(42);
+
+        let serialized_msg = CiboriumSerializer::serialize(&msg)?;
+        let deserialized_msg = serialized_msg.deserialize()?;
+
+        assert_eq!(msg, deserialized_msg);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ciborium_serializer_borrowed_str() -> Result<(), Error> {
+        #[derive(PartialEq, Debug, Serialize, Deserialize)]
+        struct Message<'a> {
+            #[serde(borrow)]
+            s: &'a str,
+        }
+
+        let msg = Message { s: "secret" };
+
+        let serialized_msg = CiboriumSerializer::serialize(&msg)?;
+        let received = CiboriumSerializer::<Message<'_>>::new(serialized_msg.into_vec());
+        let deserialized_msg = received.deserialize()?;
+
+        assert_eq!(msg, deserialized_msg);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ciborium_serializer_is_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<CiboriumSerializer<i32>>();
+    }
+
+    #[test]
+    fn test_broken_cbor() {
+        let received = CiboriumSerializer::<i32>::new(alloc::vec![0xff]);
+        assert!(received.deserialize().is_err());
+    }
+}
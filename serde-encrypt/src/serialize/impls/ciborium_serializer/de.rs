@@ -0,0 +1,298 @@
+//! Deserializer from [ciborium::Value] which behaves as `serde_cbor`'s one.
+//!
+//! Unlike `ciborium::Value::deserialized()`, it:
+//!
+//! - visits `null` as unit (not none) in `deserialize_any()`, so that unit structs in internally tagged or untagged enums are deserialized,
+//! - lends strings and bytes to types borrowing them (e.g. `&'a str`).
+
+use ciborium::value::{Error, Value};
+use core::{convert::TryFrom, slice};
+use serde::de::{
+    self, DeserializeSeed, Deserializer, EnumAccess, MapAccess, SeqAccess, Unexpected,
+    VariantAccess, Visitor,
+};
+
+pub(super) struct ValueDeserializer<'de>(pub(super) &'de Value);
+
+impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.0 {
+            Value::Integer(i) => {
+                let i = i128::from(*i);
+                if let Ok(u) = u64::try_from(i) {
+                    visitor.visit_u64(u)
+                } else if let Ok(i) = i64::try_from(i) {
+                    visitor.visit_i64(i)
+                } else {
+                    visitor.visit_i128(i)
+                }
+            }
+            Value::Bytes(b) => visitor.visit_borrowed_bytes(b),
+            Value::Float(f) => visitor.visit_f64(*f),
+            Value::Text(s) => visitor.visit_borrowed_str(s),
+            Value::Bool(b) => visitor.visit_bool(*b),
+            Value::Null => visitor.visit_unit(),
+            Value::Tag(_, v) => ValueDeserializer(v).deserialize_any(visitor),
+            Value::Array(a) => {
+                let mut seq = SeqDeserializer(a.iter());
+                let v = visitor.visit_seq(&mut seq)?;
+                seq.end(a.len())?;
+                Ok(v)
+            }
+            Value::Map(m) => {
+                let mut map = MapDeserializer {
+                    entries: m.iter(),
+                    value: None,
+                };
+                let v = visitor.visit_map(&mut map)?;
+                map.end(m.len())?;
+                Ok(v)
+            }
+            v => Err(de::Error::invalid_type(unexpected(v), &visitor)),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.0 {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &str, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    /// Unit variants are encoded as just the variant identifier, others as a map of the variant identifier to the content.
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.0 {
+            Value::Tag(_, v) => ValueDeserializer(v).deserialize_enum(name, variants, visitor),
+            Value::Map(m) => match m.as_slice() {
+                [(variant, value)] => visitor.visit_enum(EnumDeserializer {
+                    variant,
+                    value: Some(value),
+                }),
+                _ => Err(de::Error::invalid_length(m.len(), &"map with a single key")),
+            },
+            Value::Array(_) => Err(de::Error::invalid_type(unexpected(self.0), &"enum")),
+            variant => visitor.visit_enum(EnumDeserializer {
+                variant,
+                value: None,
+            }),
+        }
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+fn unexpected(v: &Value) -> Unexpected<'_> {
+    match v {
+        Value::Integer(i) => {
+            let i = i128::from(*i);
+            if let Ok(u) = u64::try_from(i) {
+                Unexpected::Unsigned(u)
+            } else if let Ok(i) = i64::try_from(i) {
+                Unexpected::Signed(i)
+            } else {
+                Unexpected::Other("integer")
+            }
+        }
+        Value::Bytes(b) => Unexpected::Bytes(b),
+        Value::Float(f) => Unexpected::Float(*f),
+        Value::Text(s) => Unexpected::Str(s),
+        Value::Bool(b) => Unexpected::Bool(*b),
+        Value::Null => Unexpected::Unit,
+        Value::Tag(..) => Unexpected::Other("tag"),
+        Value::Array(_) => Unexpected::Seq,
+        Value::Map(_) => Unexpected::Map,
+        _ => Unexpected::Other("unknown CBOR value"),
+    }
+}
+
+struct SeqDeserializer<'de>(slice::Iter<'de, Value>);
+
+impl SeqDeserializer<'_> {
+    fn end(&self, len: usize) -> Result<(), Error> {
+        match self.0.len() {
+            0 => Ok(()),
+            remaining => Err(de::Error::invalid_length(
+                len,
+                &ExpectedLen(len - remaining),
+            )),
+        }
+    }
+}
+
+impl<'de> SeqAccess<'de> for SeqDeserializer<'de> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        self.0
+            .next()
+            .map(|v| seed.deserialize(ValueDeserializer(v)))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+}
+
+struct MapDeserializer<'de> {
+    entries: slice::Iter<'de, (Value, Value)>,
+    value: Option<&'de Value>,
+}
+
+impl MapDeserializer<'_> {
+    fn end(&self, len: usize) -> Result<(), Error> {
+        match self.entries.len() {
+            0 => Ok(()),
+            remaining => Err(de::Error::invalid_length(
+                len,
+                &ExpectedLen(len - remaining),
+            )),
+        }
+    }
+}
+
+impl<'de> MapAccess<'de> for MapDeserializer<'de> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.entries.next() {
+            Some((k, v)) => {
+                self.value = Some(v);
+                seed.deserialize(ValueDeserializer(k)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let v = self
+            .value
+            .take()
+            .ok_or_else(|| de::Error::custom("value is missing"))?;
+        seed.deserialize(ValueDeserializer(v))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+struct EnumDeserializer<'de> {
+    variant: &'de Value,
+    value: Option<&'de Value>,
+}
+
+impl<'de> EnumAccess<'de> for EnumDeserializer<'de> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self), Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let variant = seed.deserialize(ValueDeserializer(self.variant))?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for EnumDeserializer<'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        match self.value {
+            Some(v) => de::Deserialize::deserialize(ValueDeserializer(v)),
+            None => Ok(()),
+        }
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.value {
+            Some(v) => seed.deserialize(ValueDeserializer(v)),
+            None => Err(de::Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"newtype variant",
+            )),
+        }
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Some(v) => ValueDeserializer(v).deserialize_any(visitor),
+            None => Err(de::Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"tuple variant",
+            )),
+        }
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Some(v) => ValueDeserializer(v).deserialize_any(visitor),
+            None => Err(de::Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"struct variant",
+            )),
+        }
+    }
+}
+
+struct ExpectedLen(usize);
+
+impl de::Expected for ExpectedLen {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} elements", self.0)
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{
+    serialize::{
+        compress::{self, Compressor},
+        TypedSerialized,
+    },
+    Error,
+};
+use alloc::vec::Vec;
+use core::{fmt, marker::PhantomData};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+/// Wrapping serializer which compresses output of serializer `S` by compressor `C` before encryption.
+///
+/// ```ignore
+/// impl SerdeEncryptSharedKey for Message {
+///     type S = Compressed<BincodeSerializer<Self>, Lz4>;
+///     type A = XChaCha20Poly1305;
+/// }
+/// ```
+///
+/// Plain-text is `compressor ID (1 byte) || compressed`, so that decryption works whichever built-in compressor in
+/// [compress](crate::serialize::compress) module (or `C`) made it.
+///
+/// # Warning
+///
+/// Compression makes cipher-text length depend on plain-text content, not only on its length.
+/// Do not compress attacker-controlled data together with secrets (e.g. CRIME / BREACH attacks).
+/// [Padded](super::Padded) (`Padded<Compressed<S, C>, P>`) makes the length coarser but does not remove this leak.
+pub struct Compressed<S, C> {
+    compressed: Vec<u8>,
+    decompressed: Result<S, Error>,
+    _compressor: PhantomData<C>,
+}
+
+impl<S, C> fmt::Debug for Compressed<S, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Compressed")
+            .field("compressed", &self.compressed)
+            .finish()
+    }
+}
+
+impl<S, C> TypedSerialized for Compressed<S, C>
+where
+    S: TypedSerialized,
+    C: Compressor,
+{
+    type T = S::T;
+
+    /// From compressed plain-text.
+    /// Decompression error is returned by [deserialize()](Self::deserialize).
+    fn new(compressed: Vec<u8>) -> Self
+    where
+        Self: Sized,
+    {
+        let decompressed = compress::decompress::<C>(&compressed).map(S::new);
+        Self {
+            compressed,
+            decompressed,
+            _compressor: PhantomData,
+        }
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        &self.compressed
+    }
+
+    fn into_vec(mut self) -> Vec<u8> {
+        core::mem::take(&mut self.compressed)
+    }
+
+    /// # Failures
+    ///
+    /// - [SerializationError](serde_encrypt_core::error::ErrorKind::SerializationError) when failed to serialize or compress message.
+    fn serialize(v: &Self::T) -> Result<Self, Error>
+    where
+        Self: Sized,
+        Self::T: Serialize,
+    {
+        let serialized = S::serialize(v)?;
+        let compressed = compress::compress::<C>(serialized.as_slice())?;
+        Ok(Self {
+            compressed,
+            decompressed: Ok(serialized),
+            _compressor: PhantomData,
+        })
+    }
+
+    /// # Failures
+    ///
+    /// - [DeserializationError](serde_encrypt_core::error::ErrorKind::DeserializationError) when failed to decompress or deserialize decrypted message.
+    fn deserialize<'de>(&'de self) -> Result<Self::T, Error>
+    where
+        Self::T: Deserialize<'de>,
+    {
+        match &self.decompressed {
+            Ok(serialized) => serialized.deserialize(),
+            Err(e) => Err(e.clone()),
+        }
+    }
+}
+
+impl<S, C> Zeroize for Compressed<S, C> {
+    fn zeroize(&mut self) {
+        self.compressed.zeroize();
+    }
+}
+
+impl<S, C> Drop for Compressed<S, C> {
+    fn drop(&mut self) {
+        // decompressed `S` zeroizes itself on drop
+        self.zeroize();
+    }
+}
+
+#[cfg(all(test, feature = "lz4"))]
+mod tests {
+    use super::*;
+    use crate::serialize::{compress::Lz4, impls::PostcardSerializer};
+
+    #[test]
+    fn test_compressed_serializer() -> Result<(), Error> {
+        #[derive(PartialEq, Debug, Serialize, Deserialize)]
+        struct Message(alloc::string::String);
+
+        let msg = Message("a".repeat(1000));
+
+        let serialized_msg = Compressed::<PostcardSerializer<Message>, Lz4>::serialize(&msg)?;
+        assert_eq!(serialized_msg.as_slice()[0], Lz4::ID);
+        assert!(serialized_msg.as_slice().len() < 100);
+
+        let received =
+            Compressed::<PostcardSerializer<Message>, Lz4>::new(serialized_msg.into_vec());
+        assert_eq!(received.deserialize()?, msg);
+
+        let broken =
+            Compressed::<PostcardSerializer<Message>, Lz4>::new(alloc::vec![Lz4::ID, 0xff]);
+        assert!(broken.deserialize().is_err());
+
+        Ok(())
+    }
+}
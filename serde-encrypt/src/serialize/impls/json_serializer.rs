@@ -0,0 +1,98 @@
+use crate::{serialize::TypedSerialized, Error};
+use alloc::{format, vec::Vec};
+use core::marker::PhantomData;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+/// [JSON](https://docs.rs/serde_json) serializer.
+///
+/// Decrypted plain-text is human-readable and easy to produce / consume by peers in other languages, at the cost of message size.
+/// Available without `std`.
+#[derive(Debug)]
+pub struct JsonSerializer<T> {
+    serialized: Vec<u8>,
+    _type: PhantomData<T>,
+}
+
+impl<T> TypedSerialized for JsonSerializer<T> {
+    type T = T;
+
+    fn new(serialized: Vec<u8>) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            serialized,
+            _type: PhantomData,
+        }
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        &self.serialized
+    }
+
+    fn into_vec(mut self) -> Vec<u8> {
+        core::mem::take(&mut self.serialized)
+    }
+
+    /// # Failures
+    ///
+    /// - [SerializationError](serde_encrypt_core::error::ErrorKind::SerializationError) when failed to serialize message.
+    fn serialize(v: &Self::T) -> Result<Self, Error>
+    where
+        Self: Sized,
+        Self::T: Serialize,
+    {
+        let serialized = serde_json::to_vec(v).map_err(|e| {
+            Error::serialization_error(&format!("failed to serialize data by serde_json: {:?}", e))
+        })?;
+        Ok(Self::new(serialized))
+    }
+
+    /// # Failures
+    ///
+    /// - [DeserializationError](serde_encrypt_core::error::ErrorKind::DeserializationError) when failed to deserialize decrypted message.
+    fn deserialize<'de>(&'de self) -> Result<Self::T, Error>
+    where
+        Self::T: Deserialize<'de>,
+    {
+        serde_json::from_slice(self.as_slice()).map_err(|e| {
+            Error::deserialization_error(&format!(
+                "error on serde_json deserialization after decryption: {:?}",
+                e
+            ))
+        })
+    }
+}
+
+impl<T> Zeroize for JsonSerializer<T> {
+    fn zeroize(&mut self) {
+        self.serialized.zeroize();
+    }
+}
+
+impl<T> Drop for JsonSerializer<T> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_serializer() -> Result<(), Error> {
+        #[derive(PartialEq, Debug, Serialize, Deserialize)]
+        struct Message(i32);
+
+        let msg = Message(42);
+
+        let serialized_msg = JsonSerializer::serialize(&msg)?;
+        let deserialized_msg = serialized_msg.deserialize()?;
+
+        assert_eq!(msg, deserialized_msg);
+
+        Ok(())
+    }
+}
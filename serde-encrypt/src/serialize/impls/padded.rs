@@ -0,0 +1,110 @@
+use crate::{
+    serialize::{
+        padding::{self, Padding},
+        TypedSerialized,
+    },
+    Error,
+};
+use alloc::vec::Vec;
+use core::{fmt, marker::PhantomData};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+/// Wrapping serializer which pads output of serializer `S` by padding scheme `P` before encryption,
+/// so that cipher-text length does not reveal exact plain-text length.
+///
+/// ```ignore
+/// impl SerdeEncryptSharedKey for Answer {
+///     type S = Padded<BincodeSerializer<Self>, Block<16>>;
+///     type A = XChaCha20Poly1305;
+/// }
+/// ```
+///
+/// Padding is removed in `decrypt_owned()` / `decrypt_ref()`.
+/// With [Compressed](super::Compressed), wrap it by `Padded` (`Padded<Compressed<S, C>, P>`) to pad compressed data.
+pub struct Padded<S, P> {
+    padded: Vec<u8>,
+    unpadded: Result<S, Error>,
+    _padding: PhantomData<P>,
+}
+
+impl<S, P> fmt::Debug for Padded<S, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Padded")
+            .field("padded", &self.padded)
+            .finish()
+    }
+}
+
+impl<S, P> TypedSerialized for Padded<S, P>
+where
+    S: TypedSerialized,
+    P: Padding,
+{
+    type T = S::T;
+
+    /// From padded plain-text.
+    /// Invalid padding is reported by [deserialize()](Self::deserialize).
+    fn new(padded: Vec<u8>) -> Self
+    where
+        Self: Sized,
+    {
+        let unpadded = padding::unpad(padded.clone()).map(S::new);
+        Self {
+            padded,
+            unpadded,
+            _padding: PhantomData,
+        }
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        &self.padded
+    }
+
+    fn into_vec(mut self) -> Vec<u8> {
+        core::mem::take(&mut self.padded)
+    }
+
+    /// # Failures
+    ///
+    /// - [SerializationError](serde_encrypt_core::error::ErrorKind::SerializationError) when failed to serialize message.
+    fn serialize(v: &Self::T) -> Result<Self, Error>
+    where
+        Self: Sized,
+        Self::T: Serialize,
+    {
+        let serialized = S::serialize(v)?;
+        let padded = padding::pad::<P>(serialized.as_slice());
+        Ok(Self {
+            padded,
+            unpadded: Ok(serialized),
+            _padding: PhantomData,
+        })
+    }
+
+    /// # Failures
+    ///
+    /// - [DeserializationError](serde_encrypt_core::error::ErrorKind::DeserializationError) when padding is invalid or failed to deserialize decrypted message.
+    fn deserialize<'de>(&'de self) -> Result<Self::T, Error>
+    where
+        Self::T: Deserialize<'de>,
+    {
+        match &self.unpadded {
+            Ok(serialized) => serialized.deserialize(),
+            Err(e) => Err(e.clone()),
+        }
+    }
+}
+
+impl<S, P> Zeroize for Padded<S, P> {
+    fn zeroize(&mut self) {
+        self.padded.zeroize();
+    }
+}
+
+impl<S, P> Drop for Padded<S, P> {
+    fn drop(&mut self) {
+        // unpadded `S` zeroizes itself on drop
+        self.zeroize();
+    }
+}
@@ -2,6 +2,7 @@ use crate::{serialize::TypedSerialized, Error};
 use alloc::{format, vec::Vec};
 use core::marker::PhantomData;
 use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
 
 /// [postcard](https://docs.rs/postcard) serializer
 #[derive(Debug)]
@@ -19,7 +20,7 @@ impl<T> TypedSerialized for PostcardSerializer<T> {
     {
         Self {
             serialized,
-            _type: PhantomData::default(),
+            _type: PhantomData,
         }
     }
 
@@ -27,8 +28,8 @@ impl<T> TypedSerialized for PostcardSerializer<T> {
         &self.serialized
     }
 
-    fn into_vec(self) -> Vec<u8> {
-        self.serialized
+    fn into_vec(mut self) -> Vec<u8> {
+        core::mem::take(&mut self.serialized)
     }
 
     /// # Failures
@@ -61,6 +62,18 @@ impl<T> TypedSerialized for PostcardSerializer<T> {
     }
 }
 
+impl<T> Zeroize for PostcardSerializer<T> {
+    fn zeroize(&mut self) {
+        self.serialized.zeroize();
+    }
+}
+
+impl<T> Drop for PostcardSerializer<T> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
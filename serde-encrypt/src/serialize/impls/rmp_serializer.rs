@@ -0,0 +1,93 @@
+use crate::{serialize::TypedSerialized, Error};
+use alloc::{format, vec::Vec};
+use core::marker::PhantomData;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+/// [MessagePack](https://msgpack.org/) serializer by [rmp-serde](https://docs.rs/rmp-serde).
+///
+/// Structs are serialized as maps with field names (`rmp_serde::to_vec_named()`), as other MessagePack implementations expect.
+///
+/// Unit structs are serialized as empty arrays, so `#[serde(untagged)]` enums cannot tell variants apart by unit-struct fields.
+#[derive(Debug)]
+pub struct RmpSerializer<T> {
+    serialized: Vec<u8>,
+    _type: PhantomData<T>,
+}
+
+impl<T> TypedSerialized for RmpSerializer<T> {
+    type T = T;
+
+    fn new(serialized: Vec<u8>) -> Self
+    where
+        Self: Sized,
+    {
+        Self {
+            serialized,
+            _type: PhantomData,
+        }
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        &self.serialized
+    }
+
+    fn into_vec(mut self) -> Vec<u8> {
+        core::mem::take(&mut self.serialized)
+    }
+
+    fn serialize(v: &Self::T) -> Result<Self, Error>
+    where
+        Self: Sized,
+        Self::T: Serialize,
+    {
+        let serialized = rmp_serde::to_vec_named(v).map_err(|e| {
+            Error::serialization_error(&format!("failed to serialize data by rmp-serde: {:?}", e))
+        })?;
+        Ok(Self::new(serialized))
+    }
+
+    fn deserialize<'de>(&'de self) -> Result<Self::T, Error>
+    where
+        Self::T: Deserialize<'de>,
+    {
+        rmp_serde::from_slice(self.as_slice()).map_err(|e| {
+            Error::deserialization_error(&format!(
+                "error on rmp-serde deserialization after decryption: {:?}",
+                e
+            ))
+        })
+    }
+}
+
+impl<T> Zeroize for RmpSerializer<T> {
+    fn zeroize(&mut self) {
+        self.serialized.zeroize();
+    }
+}
+
+impl<T> Drop for RmpSerializer<T> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rmp_serializer() -> Result<(), Error> {
+        #[derive(PartialEq, Debug, Serialize, Deserialize)]
+        struct Message(i32);
+
+        let msg = Message(42);
+
+        let serialized_msg = RmpSerializer::serialize(&msg)?;
+        let deserialized_msg = serialized_msg.deserialize()?;
+
+        assert_eq!(msg, deserialized_msg);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,150 @@
+//! Padding schemes applied to serialized plain-text before encryption, by [Padded](crate::serialize::impls::Padded) serializer.
+//!
+//! Cipher-text length otherwise reveals plain-text length (e.g. which enum variant was sent).
+//! Padding rounds it up so that messages of similar lengths are indistinguishable.
+//!
+//! | Scheme | Padded length | Overhead |
+//! |--------|---------------|----------|
+//! | [Block<N>](self::Block) | multiple of `N` | < `N` bytes |
+//! | [PowerOfTwo](self::PowerOfTwo) | power of two | < 100% |
+//! | [Padme](self::Padme) | [Padmé](https://lbarman.ch/blog/padme/): `floor(log2 L)` significant bits at most | < 12% |
+//!
+//! Padding bytes are `0x80` followed by zeros (ISO/IEC 7816-4, as libsodium's `sodium_pad()`),
+//! so at least one byte is added.
+
+use crate::Error;
+use alloc::vec::Vec;
+
+/// Padding scheme, which decides padded length.
+pub trait Padding {
+    /// Padded length for `len` bytes of plain-text plus 1-byte padding marker (`len + 1`).
+    ///
+    /// Must be at least `len + 1`.
+    fn padded_len(len: usize) -> usize;
+}
+
+/// Pads to a multiple of `N` bytes.
+///
+/// `N` must be positive; `Block<0>` fails to compile once used.
+///
+/// ```compile_fail
+/// use serde_encrypt::serialize::padding::{Block, Padding};
+///
+/// Block::<0>::padded_len(42);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Block<const N: usize>;
+
+impl<const N: usize> Block<N> {
+    /// Evaluated at compile time where [padded_len()](Padding::padded_len) is instantiated.
+    const BLOCK_SIZE: usize = {
+        assert!(N > 0, "block size must be positive");
+        N
+    };
+}
+
+impl<const N: usize> Padding for Block<N> {
+    fn padded_len(len: usize) -> usize {
+        let min_len = len + 1;
+        min_len.div_ceil(Self::BLOCK_SIZE) * Self::BLOCK_SIZE
+    }
+}
+
+/// Pads to a power of two.
+///
+/// Hides length well but may double the length.
+#[derive(Clone, Copy, Debug)]
+pub struct PowerOfTwo;
+
+impl Padding for PowerOfTwo {
+    fn padded_len(len: usize) -> usize {
+        (len + 1).next_power_of_two()
+    }
+}
+
+/// [Padmé](https://lbarman.ch/blog/padme/) padding: leaks `O(log log L)` bits of length `L` with at most 12% overhead.
+#[derive(Clone, Copy, Debug)]
+pub struct Padme;
+
+impl Padding for Padme {
+    fn padded_len(len: usize) -> usize {
+        let min_len = len + 1;
+        if min_len < 2 {
+            return min_len;
+        }
+        let e = usize::BITS - 1 - min_len.leading_zeros(); // floor(log2 L)
+        let s = u32::BITS - e.leading_zeros(); // floor(log2 E) + 1
+        let last_bits = e - s;
+        let bit_mask = (1usize << last_bits) - 1;
+        (min_len + bit_mask) & !bit_mask
+    }
+}
+
+/// Appends `0x80` and zeros up to `P::padded_len()`.
+pub(crate) fn pad<P: Padding>(plain: &[u8]) -> Vec<u8> {
+    let padded_len = P::padded_len(plain.len());
+    debug_assert!(padded_len > plain.len());
+
+    let mut padded = Vec::with_capacity(padded_len);
+    padded.extend_from_slice(plain);
+    padded.push(0x80);
+    padded.resize(padded_len, 0);
+    padded
+}
+
+/// Removes trailing zeros and `0x80` appended by [pad()](pad).
+pub(crate) fn unpad(mut padded: Vec<u8>) -> Result<Vec<u8>, Error> {
+    let marker = padded
+        .iter()
+        .rposition(|b| *b != 0)
+        .filter(|i| padded[*i] == 0x80)
+        .ok_or_else(|| Error::deserialization_error("invalid padding"))?;
+    padded.truncate(marker);
+    Ok(padded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_padded_len() {
+        assert_eq!(Block::<16>::padded_len(0), 16);
+        assert_eq!(Block::<16>::padded_len(15), 16);
+        assert_eq!(Block::<16>::padded_len(16), 32);
+
+        assert_eq!(PowerOfTwo::padded_len(0), 1);
+        assert_eq!(PowerOfTwo::padded_len(100), 128);
+        assert_eq!(PowerOfTwo::padded_len(127), 128);
+        assert_eq!(PowerOfTwo::padded_len(128), 256);
+
+        // Padmé of L = 1, 2, 8, ...
+        let padme: Vec<usize> = [1, 2, 8, 9, 10, 100, 1000, 1025, 10_000, 1_000_000]
+            .iter()
+            .map(|l| Padme::padded_len(l - 1))
+            .collect();
+        assert_eq!(
+            padme,
+            vec![1, 2, 8, 10, 10, 104, 1024, 1088, 10240, 1_015_808]
+        );
+        for l in 0..100_000 {
+            let padded = Padme::padded_len(l);
+            assert!(padded > l);
+            assert!((padded - l - 1) * 100 <= (l + 1) * 12);
+        }
+    }
+
+    #[test]
+    fn test_pad_unpad() -> Result<(), Error> {
+        for plain in [&b""[..], b"\x00", b"\x80", b"abc\x00\x00", &[0x80; 100]] {
+            let padded = pad::<Padme>(plain);
+            assert_eq!(padded.len(), Padme::padded_len(plain.len()));
+            assert_eq!(unpad(padded)?, plain);
+        }
+
+        assert!(unpad(vec![]).is_err());
+        assert!(unpad(vec![0, 0]).is_err());
+        assert!(unpad(vec![1, 0x81, 0]).is_err());
+        Ok(())
+    }
+}
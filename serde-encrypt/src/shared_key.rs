@@ -1,15 +1,21 @@
 //! serde-serializable shared key.
 
+use core::fmt;
+
 use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
 
 use crate::{AsSharedKey, random::RngSingletonImpl};
 use crate::traits::SerdeEncryptPublicKey;
+use crate::public_key_backend::ChaChaBox;
 
 /// 32-byte key shared among sender and receiver secretly.
 ///
 /// It is a good practice to use [SerdeEncryptPublicKey](crate::traits::SerdeEncryptPublicKey)
 /// to exchange this shared key.
-#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+///
+/// Key material is zeroized on drop and hidden from `Debug` output.
+#[derive(Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct SharedKey([u8; 32]);
 
 impl SharedKey {
@@ -24,6 +30,24 @@ impl SharedKey {
     }
 }
 
+impl fmt::Debug for SharedKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedKey(...)")
+    }
+}
+
+impl Zeroize for SharedKey {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Drop for SharedKey {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
 impl AsSharedKey for SharedKey {
     type R = RngSingletonImpl;
 
@@ -41,11 +65,13 @@ cfg_if::cfg_if! {
         use crate::serialize::impls::BincodeSerializer;
         impl SerdeEncryptPublicKey for SharedKey {
             type S = BincodeSerializer<Self>;
+            type B = ChaChaBox;
         }
     } else {
         use crate::serialize::impls::PostcardSerializer;
         impl SerdeEncryptPublicKey for SharedKey {
             type S = PostcardSerializer<Self>;
+            type B = ChaChaBox;
         }
     }
 }
@@ -1,9 +1,25 @@
 //! Traits to enable encrypted-serialization to your struct/enum.
+//!
+//! With `derive` feature, derive macros of the same names are also exported from this module.
 
+mod serde_encrypt_envelope;
+mod serde_encrypt_multi_recipient;
 mod serde_encrypt_public_key;
+mod serde_encrypt_sealed_box;
 mod serde_encrypt_shared_key;
 mod serde_encrypt_shared_key_deterministic;
+mod serde_encrypt_signed_public_key;
 
+pub use serde_encrypt_envelope::SerdeEncryptEnvelope;
+pub use serde_encrypt_multi_recipient::SerdeEncryptMultiRecipient;
 pub use serde_encrypt_public_key::SerdeEncryptPublicKey;
+pub use serde_encrypt_sealed_box::SerdeEncryptSealedBox;
 pub use serde_encrypt_shared_key::SerdeEncryptSharedKey;
 pub use serde_encrypt_shared_key_deterministic::SerdeEncryptSharedKeyDeterministic;
+pub use serde_encrypt_signed_public_key::SerdeEncryptSignedPublicKey;
+
+#[cfg(feature = "derive")]
+pub use serde_encrypt_derive::{
+    SerdeEncryptEnvelope, SerdeEncryptMultiRecipient, SerdeEncryptPublicKey, SerdeEncryptSealedBox,
+    SerdeEncryptSharedKey, SerdeEncryptSharedKeyDeterministic, SerdeEncryptSignedPublicKey,
+};
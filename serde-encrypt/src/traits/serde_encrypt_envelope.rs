@@ -0,0 +1,154 @@
+use crate::encrypt::plain_message_shared_key::PlainMessageSharedKey;
+use crate::envelope::{EnvelopeEncrypted, KeyDecryptionKey, KeyEncryptionKey};
+use crate::serialize::TypedSerialized;
+use crate::{shared_key::SharedKey, AsSharedKey, Error};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_encrypt_core::encrypt::{
+    aead::AeadAlgorithm, plain_message_shared_key::PlainMessageSharedKeyCore,
+};
+
+/// Envelope encryption for serde-serializable types.
+///
+/// Each message is encrypted by a fresh data-encryption key (DEK), and the DEK is wrapped by a key-encryption key (KEK).
+/// [EnvelopeEncrypted](crate::envelope::EnvelopeEncrypted) stores the wrapped DEK next to the cipher-text.
+///
+/// KEK is a [SharedKey](crate::shared_key::SharedKey), a [ReceiverPublicKey](crate::ReceiverPublicKey)
+/// or any type implementing [KeyEncryptionKey](crate::envelope::KeyEncryptionKey) (e.g. a key in an external KMS).
+///
+/// # Features
+///
+/// - KEK only wraps 32-byte DEKs, so that a remote KMS does not receive large messages.
+/// - Rotating KEK only needs re-wrapping DEKs, not re-encrypting messages.
+/// - Message authentication.
+/// - Associated data (AAD) binding via `*_with_aad()` functions.
+///
+/// # Anti-features
+///
+/// - Identity authentication of sender nor receiver.
+///
+/// # Popular use cases
+///
+/// - Large datasets or files encrypted under keys managed by a KMS.
+///
+/// # Algorithm
+///
+/// - DEK: random 32-byte key per message
+/// - Message encryption: chosen by [A](Self::A) associated type from [aead](crate::aead) module
+/// - DEK wrapping: chosen by KEK (XChaCha20-Poly1305 for `SharedKey`, sealed box for `ReceiverPublicKey`)
+pub trait SerdeEncryptEnvelope {
+    /// Serializer implementation
+    type S: TypedSerialized<T = Self>;
+
+    /// AEAD algorithm implementation to encrypt message by DEK
+    type A: AeadAlgorithm;
+
+    /// Serialize and encrypt by a fresh DEK, which is wrapped by `kek`.
+    ///
+    /// # Failures
+    ///
+    /// - [SerializationError](serde_encrypt_core::error::ErrorKind::SerializationError) when failed to serialize message.
+    /// - [EncryptionError](serde_encrypt_core::error::ErrorKind::EncryptionError) when failed to encrypt serialized message or to wrap DEK.
+    fn encrypt<K>(&self, kek: &K) -> Result<EnvelopeEncrypted, Error>
+    where
+        Self: Serialize,
+        K: KeyEncryptionKey + ?Sized,
+    {
+        self.encrypt_with_aad(kek, &[])
+    }
+
+    /// Serialize and encrypt by a fresh DEK, binding associated data (`aad`) to the cipher-text.
+    ///
+    /// `aad` is authenticated but neither encrypted nor included in [EnvelopeEncrypted](crate::envelope::EnvelopeEncrypted).
+    ///
+    /// # Failures
+    ///
+    /// - [SerializationError](serde_encrypt_core::error::ErrorKind::SerializationError) when failed to serialize message.
+    /// - [EncryptionError](serde_encrypt_core::error::ErrorKind::EncryptionError) when failed to encrypt serialized message or to wrap DEK.
+    fn encrypt_with_aad<K>(&self, kek: &K, aad: &[u8]) -> Result<EnvelopeEncrypted, Error>
+    where
+        Self: Serialize,
+        K: KeyEncryptionKey + ?Sized,
+    {
+        let serialized = Self::S::serialize(self)?;
+        let plain_msg = PlainMessageSharedKey::<Self::A>::new(serialized.into_vec());
+
+        let dek = SharedKey::generate();
+        let encrypted_message = plain_msg.encrypt_with_aad(&dek, aad)?;
+        let wrapped_dek = kek.wrap_key(&dek)?;
+
+        Ok(EnvelopeEncrypted::new(wrapped_dek, encrypted_message))
+    }
+
+    /// Unwrap DEK by `kdk`, decrypt and deserialize into DeserializeOwned type.
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](serde_encrypt_core::error::ErrorKind::DecryptionError) when failed to unwrap DEK or to decrypt message.
+    /// - [DeserializationError](serde_encrypt_core::error::ErrorKind::DeserializationError) when failed to deserialize decrypted message.
+    fn decrypt_owned<K>(envelope: &EnvelopeEncrypted, kdk: &K) -> Result<Self, Error>
+    where
+        Self: DeserializeOwned,
+        K: KeyDecryptionKey + ?Sized,
+    {
+        Self::decrypt_owned_with_aad(envelope, kdk, &[])
+    }
+
+    /// Unwrap DEK by `kdk`, decrypt and deserialize into DeserializeOwned type.
+    /// `aad` must be the same as the one given to [encrypt_with_aad()](Self::encrypt_with_aad).
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](serde_encrypt_core::error::ErrorKind::DecryptionError) when failed to unwrap DEK or to decrypt message (including `aad` mismatch).
+    /// - [DeserializationError](serde_encrypt_core::error::ErrorKind::DeserializationError) when failed to deserialize decrypted message.
+    fn decrypt_owned_with_aad<K>(
+        envelope: &EnvelopeEncrypted,
+        kdk: &K,
+        aad: &[u8],
+    ) -> Result<Self, Error>
+    where
+        Self: DeserializeOwned,
+        K: KeyDecryptionKey + ?Sized,
+    {
+        let serialized = Self::decrypt_ref_with_aad(envelope, kdk, aad)?;
+        serialized.deserialize()
+    }
+
+    /// Just unwraps DEK and decrypts cipher-text. Returned data must be deserialized later.
+    /// Types implementing `serde::Deserialize<'de>` (not `serde::de::DeserializeOwned`) should use
+    /// this function to resolve lifetime.
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](serde_encrypt_core::error::ErrorKind::DecryptionError) when failed to unwrap DEK or to decrypt message.
+    fn decrypt_ref<'de, K>(envelope: &EnvelopeEncrypted, kdk: &K) -> Result<Self::S, Error>
+    where
+        Self: Deserialize<'de>,
+        K: KeyDecryptionKey + ?Sized,
+    {
+        Self::decrypt_ref_with_aad(envelope, kdk, &[])
+    }
+
+    /// Just unwraps DEK and decrypts cipher-text created by [encrypt_with_aad()](Self::encrypt_with_aad).
+    /// Returned data must be deserialized later.
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](serde_encrypt_core::error::ErrorKind::DecryptionError) when failed to unwrap DEK or to decrypt message (including `aad` mismatch).
+    fn decrypt_ref_with_aad<'de, K>(
+        envelope: &EnvelopeEncrypted,
+        kdk: &K,
+        aad: &[u8],
+    ) -> Result<Self::S, Error>
+    where
+        Self: Deserialize<'de>,
+        K: KeyDecryptionKey + ?Sized,
+    {
+        let dek = kdk.unwrap_key(envelope.wrapped_dek())?;
+        let plain_msg = PlainMessageSharedKey::<Self::A>::decrypt_with_aad(
+            envelope.encrypted_message(),
+            &dek,
+            aad,
+        )?;
+        Ok(Self::S::new(plain_msg.into_vec()))
+    }
+}
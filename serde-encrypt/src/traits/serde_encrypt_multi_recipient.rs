@@ -0,0 +1,164 @@
+use crate::encrypt::plain_message_multi_recipient::PlainMessageMultiRecipient;
+use crate::key::key_pair::SenderKeyPair;
+use crate::serialize::TypedSerialized;
+use crate::{EncryptedMessage, Error, ReceiverCombinedKey, ReceiverPublicKey, SenderKeyPairCore};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_encrypt_core::encrypt::plain_message_multi_recipient::PlainMessageMultiRecipientCore;
+
+/// Public-key authenticated encryption of a message for multiple receivers.
+///
+/// Serialized message is encrypted once under a random content key,
+/// and the content key is wrapped for each receiver's public key by the same X25519 key exchange as [SerdeEncryptPublicKey](crate::traits::SerdeEncryptPublicKey).
+/// Each of the receivers decrypts with its [ReceiverCombinedKey](crate::ReceiverCombinedKey).
+///
+/// # Features
+///
+/// - Cipher-text size grows by only 56 bytes per receiver (plain message is encrypted once).
+/// - Receiver finds its wrapped key by binary search on key ID (prefix of SHA-256 of its public key).
+/// - Message authentication.
+/// - Associated data (AAD) binding via `*_with_aad()` functions.
+/// - Different cipher-text for the same plain-text to avoid attacks such as statistical analysis of cipher-text.
+///
+/// # Anti-features
+///
+/// - Identity authentication of sender nor receiver.
+/// - Hiding the number of receivers.
+/// - Authentication among receivers: any receiver knows the content key and can forge a message for other receivers.
+///
+/// # Popular use cases
+///
+/// - Notification to a set of devices (e.g. on-call operators' devices).
+///
+/// # Algorithm
+///
+/// - Public-key exchange: X25519
+/// - Content key wrapping: XChaCha20-Poly1305 (ChaChaBox)
+/// - Encryption: XChaCha20
+/// - Message authentication: Poly1305 MAC
+/// - Recipient table is bound to cipher-text as associated data.
+pub trait SerdeEncryptMultiRecipient {
+    /// Serializer implementation
+    type S: TypedSerialized<T = Self>;
+
+    /// Serialize and encrypt for each of `receiver_public_keys`.
+    ///
+    /// # Failures
+    ///
+    /// - [SerializationError](serde_encrypt_core::error::ErrorKind::SerializationError) when failed to serialize message.
+    /// - [EncryptionError](serde_encrypt_core::error::ErrorKind::EncryptionError) when:
+    ///   - `receiver_public_keys` is empty.
+    ///   - failed to encrypt serialized message.
+    fn encrypt(
+        &self,
+        sender_key_pair: &SenderKeyPair,
+        receiver_public_keys: &[&ReceiverPublicKey],
+    ) -> Result<EncryptedMessage, Error>
+    where
+        Self: Serialize,
+    {
+        self.encrypt_with_aad(sender_key_pair, receiver_public_keys, &[])
+    }
+
+    /// Serialize and encrypt for each of `receiver_public_keys`, binding associated data (`aad`) to the cipher-text.
+    ///
+    /// `aad` is authenticated but neither encrypted nor included in [EncryptedMessage](crate::EncryptedMessage).
+    ///
+    /// # Failures
+    ///
+    /// - [SerializationError](serde_encrypt_core::error::ErrorKind::SerializationError) when failed to serialize message.
+    /// - [EncryptionError](serde_encrypt_core::error::ErrorKind::EncryptionError) when:
+    ///   - `receiver_public_keys` is empty.
+    ///   - failed to encrypt serialized message.
+    fn encrypt_with_aad(
+        &self,
+        sender_key_pair: &SenderKeyPair,
+        receiver_public_keys: &[&ReceiverPublicKey],
+        aad: &[u8],
+    ) -> Result<EncryptedMessage, Error>
+    where
+        Self: Serialize,
+    {
+        let serialized = Self::S::serialize(self)?;
+        let plain_msg = PlainMessageMultiRecipient::new(serialized.into_vec());
+        plain_msg.encrypt_with_aad(sender_key_pair.private_key(), receiver_public_keys, aad)
+    }
+
+    /// Decrypt and deserialize into DeserializeOwned type.
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](serde_encrypt_core::error::ErrorKind::DecryptionError) when:
+    ///   - receiver is not listed in the message.
+    ///   - failed to decrypt message.
+    /// - [DeserializationError](serde_encrypt_core::error::ErrorKind::DeserializationError) when failed to deserialize decrypted message.
+    fn decrypt_owned(
+        encrypted_message: &EncryptedMessage,
+        combined_key: &ReceiverCombinedKey,
+    ) -> Result<Self, Error>
+    where
+        Self: DeserializeOwned,
+    {
+        Self::decrypt_owned_with_aad(encrypted_message, combined_key, &[])
+    }
+
+    /// Decrypt and deserialize into DeserializeOwned type.
+    /// `aad` must be the same as the one given to [encrypt_with_aad()](Self::encrypt_with_aad).
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](serde_encrypt_core::error::ErrorKind::DecryptionError) when:
+    ///   - receiver is not listed in the message.
+    ///   - failed to decrypt message (including `aad` mismatch).
+    /// - [DeserializationError](serde_encrypt_core::error::ErrorKind::DeserializationError) when failed to deserialize decrypted message.
+    fn decrypt_owned_with_aad(
+        encrypted_message: &EncryptedMessage,
+        combined_key: &ReceiverCombinedKey,
+        aad: &[u8],
+    ) -> Result<Self, Error>
+    where
+        Self: DeserializeOwned,
+    {
+        let serialized = Self::decrypt_ref_with_aad(encrypted_message, combined_key, aad)?;
+        serialized.deserialize()
+    }
+
+    /// Just decrypts cipher-text. Returned data must be deserialized later.
+    /// Types implementing `serde::Deserialize<'de>` (not `serde::de::DeserializeOwned`) should use
+    /// this function to resolve lifetime.
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](serde_encrypt_core::error::ErrorKind::DecryptionError) when:
+    ///   - receiver is not listed in the message.
+    ///   - failed to decrypt message.
+    fn decrypt_ref<'de>(
+        encrypted_message: &EncryptedMessage,
+        combined_key: &ReceiverCombinedKey,
+    ) -> Result<Self::S, Error>
+    where
+        Self: Deserialize<'de>,
+    {
+        Self::decrypt_ref_with_aad(encrypted_message, combined_key, &[])
+    }
+
+    /// Just decrypts cipher-text created by [encrypt_with_aad()](Self::encrypt_with_aad).
+    /// Returned data must be deserialized later.
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](serde_encrypt_core::error::ErrorKind::DecryptionError) when:
+    ///   - receiver is not listed in the message.
+    ///   - failed to decrypt message (including `aad` mismatch).
+    fn decrypt_ref_with_aad<'de>(
+        encrypted_message: &EncryptedMessage,
+        combined_key: &ReceiverCombinedKey,
+        aad: &[u8],
+    ) -> Result<Self::S, Error>
+    where
+        Self: Deserialize<'de>,
+    {
+        let plain_msg =
+            PlainMessageMultiRecipient::decrypt_with_aad(encrypted_message, combined_key, aad)?;
+        Ok(Self::S::new(plain_msg.into_vec()))
+    }
+}
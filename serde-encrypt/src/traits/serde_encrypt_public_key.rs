@@ -2,7 +2,9 @@ use crate::encrypt::plain_message_public_key::PlainMessagePublicKey;
 use crate::serialize::TypedSerialized;
 use crate::{EncryptedMessage, Error, ReceiverCombinedKey, SenderCombinedKey};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use serde_encrypt_core::encrypt::plain_message_public_key::PlainMessagePublicKeyCore;
+use serde_encrypt_core::encrypt::{
+    plain_message_public_key::PlainMessagePublicKeyCore, public_key_backend::PublicKeyBackend,
+};
 
 /// Public-key authenticated encryption for serde-serializable types.
 ///
@@ -10,11 +12,12 @@ use serde_encrypt_core::encrypt::plain_message_public_key::PlainMessagePublicKey
 ///
 /// - Safe and bidirectional public-key exchange.
 /// - Message authentication.
+/// - Associated data (AAD) binding via `*_with_aad()` functions.
 /// - Different cipher-text for the same plain-text to avoid attacks such as statistical analysis of cipher-text.
 ///
 /// # Anti-features
 ///
-/// - Identity authentication of sender nor receiver.
+/// - Identity authentication of sender nor receiver (use [SerdeEncryptSignedPublicKey](crate::traits::SerdeEncryptSignedPublicKey) to authenticate sender).
 ///
 /// # Popular use cases
 ///
@@ -33,13 +36,22 @@ use serde_encrypt_core::encrypt::plain_message_public_key::PlainMessagePublicKey
 ///
 /// # Algorithm
 ///
-/// - Public-key exchange: X25519
-/// - Encryption: XChaCha20
-/// - Message authentication: Poly1305 MAC
+/// Chosen by `type B` ([PublicKeyBackend](crate::public_key_backend::PublicKeyBackend)):
+///
+/// - [ChaChaBox](crate::public_key_backend::ChaChaBox)
+///   - Public-key exchange: X25519
+///   - Encryption: XChaCha20
+///   - Message authentication: Poly1305 MAC
+/// - [HpkeAuth](crate::public_key_backend::HpkeAuth) / [HpkeBase](crate::public_key_backend::HpkeBase)
+///   - HPKE (RFC 9180) auth / base mode with DHKEM(X25519, HKDF-SHA256), HKDF-SHA256 and ChaCha20Poly1305, interoperable with other HPKE implementations.
+///   - `HpkeBase` does not authenticate sender.
 pub trait SerdeEncryptPublicKey {
     /// Serializer implementation
     type S: TypedSerialized<T = Self>;
 
+    /// Public-key encryption construction
+    type B: PublicKeyBackend;
+
     /// Serialize and encrypt.
     ///
     /// # Failures
@@ -47,12 +59,33 @@ pub trait SerdeEncryptPublicKey {
     /// - [SerializationError](serde_encrypt_core::error::ErrorKind::SerializationError) when failed to serialize message.
     /// - [EncryptionError](serde_encrypt_core::error::ErrorKind::EncryptionError) when failed to encrypt serialized message.
     fn encrypt(&self, combined_key: &SenderCombinedKey) -> Result<EncryptedMessage, Error>
+    where
+        Self: Serialize,
+    {
+        self.encrypt_with_aad(combined_key, &[])
+    }
+
+    /// Serialize and encrypt, binding associated data (`aad`) to the cipher-text.
+    ///
+    /// `aad` is authenticated but neither encrypted nor included in [EncryptedMessage](crate::EncryptedMessage).
+    /// Typical `aad` is a record ID, tenant ID or table column the message belongs to.
+    /// Decryption with a different `aad` fails, so a cipher-text moved to another context cannot be decrypted.
+    ///
+    /// # Failures
+    ///
+    /// - [SerializationError](serde_encrypt_core::error::ErrorKind::SerializationError) when failed to serialize message.
+    /// - [EncryptionError](serde_encrypt_core::error::ErrorKind::EncryptionError) when failed to encrypt serialized message.
+    fn encrypt_with_aad(
+        &self,
+        combined_key: &SenderCombinedKey,
+        aad: &[u8],
+    ) -> Result<EncryptedMessage, Error>
     where
         Self: Serialize,
     {
         let serialized = Self::S::serialize(self)?;
-        let plain_msg = PlainMessagePublicKey::new(serialized.into_vec());
-        plain_msg.encrypt(combined_key)
+        let plain_msg = PlainMessagePublicKey::<Self::B>::new(serialized.into_vec());
+        plain_msg.encrypt_with_aad(combined_key, aad)
     }
 
     /// Decrypt and deserialize into DeserializeOwned type.
@@ -68,7 +101,25 @@ pub trait SerdeEncryptPublicKey {
     where
         Self: DeserializeOwned,
     {
-        let serialized = Self::decrypt_ref(encrypted_message, combined_key)?;
+        Self::decrypt_owned_with_aad(encrypted_message, combined_key, &[])
+    }
+
+    /// Decrypt and deserialize into DeserializeOwned type.
+    /// `aad` must be the same as the one given to [encrypt_with_aad()](Self::encrypt_with_aad).
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](serde_encrypt_core::error::ErrorKind::DecryptionError) when failed to decrypt message (including `aad` mismatch).
+    /// - [DeserializationError](serde_encrypt_core::error::ErrorKind::DeserializationError) when failed to deserialize decrypted message.
+    fn decrypt_owned_with_aad(
+        encrypted_message: &EncryptedMessage,
+        combined_key: &ReceiverCombinedKey,
+        aad: &[u8],
+    ) -> Result<Self, Error>
+    where
+        Self: DeserializeOwned,
+    {
+        let serialized = Self::decrypt_ref_with_aad(encrypted_message, combined_key, aad)?;
         serialized.deserialize()
     }
 
@@ -86,7 +137,28 @@ pub trait SerdeEncryptPublicKey {
     where
         Self: Deserialize<'de>,
     {
-        let plain_msg = PlainMessagePublicKey::decrypt(encrypted_message, combined_key)?;
+        Self::decrypt_ref_with_aad(encrypted_message, combined_key, &[])
+    }
+
+    /// Just decrypts cipher-text created by [encrypt_with_aad()](Self::encrypt_with_aad).
+    /// Returned data must be deserialized later.
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](serde_encrypt_core::error::ErrorKind::DecryptionError) when failed to decrypt message (including `aad` mismatch).
+    fn decrypt_ref_with_aad<'de>(
+        encrypted_message: &EncryptedMessage,
+        combined_key: &ReceiverCombinedKey,
+        aad: &[u8],
+    ) -> Result<Self::S, Error>
+    where
+        Self: Deserialize<'de>,
+    {
+        let plain_msg = PlainMessagePublicKey::<Self::B>::decrypt_with_aad(
+            encrypted_message,
+            combined_key,
+            aad,
+        )?;
         Ok(Self::S::new(plain_msg.into_vec()))
     }
 }
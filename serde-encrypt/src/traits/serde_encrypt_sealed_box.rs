@@ -0,0 +1,144 @@
+use crate::encrypt::plain_message_sealed_box::PlainMessageSealedBox;
+use crate::key::key_pair::ReceiverKeyPair;
+use crate::serialize::TypedSerialized;
+use crate::{EncryptedMessage, Error, ReceiverPublicKey};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_encrypt_core::encrypt::plain_message_sealed_box::PlainMessageSealedBoxCore;
+
+/// Anonymous public-key encryption (sealed box) for serde-serializable types.
+///
+/// Unlike [SerdeEncryptPublicKey](crate::traits::SerdeEncryptPublicKey), sender does not need its own key-pair.
+/// Sender generates an ephemeral X25519 key-pair per message and embeds the ephemeral public key in [EncryptedMessage](crate::EncryptedMessage).
+/// Receiver decrypts with only its [ReceiverKeyPair](crate::key::key_pair::ReceiverKeyPair).
+///
+/// # Features
+///
+/// - Sender anonymity: no long-term sender key is needed.
+/// - Message authentication (cipher-text is not tampered after sealed).
+/// - Associated data (AAD) binding via `*_with_aad()` functions.
+/// - Different cipher-text for the same plain-text to avoid attacks such as statistical analysis of cipher-text.
+///
+/// # Anti-features
+///
+/// - Identity authentication of sender: anyone who knows receiver's public key can seal a message.
+/// - Sender cannot decrypt its own message after sealed.
+///
+/// # Popular use cases
+///
+/// - Anonymous submissions such as telemetry, crash reports and whistleblower forms.
+///
+/// # Algorithm
+///
+/// - Public-key exchange: X25519 (ephemeral sender key and receiver key)
+/// - Encryption: XChaCha20
+/// - Message authentication: Poly1305 MAC
+/// - Ephemeral public key and receiver public key are bound to cipher-text as associated data.
+pub trait SerdeEncryptSealedBox {
+    /// Serializer implementation
+    type S: TypedSerialized<T = Self>;
+
+    /// Serialize and seal.
+    ///
+    /// # Failures
+    ///
+    /// - [SerializationError](serde_encrypt_core::error::ErrorKind::SerializationError) when failed to serialize message.
+    /// - [EncryptionError](serde_encrypt_core::error::ErrorKind::EncryptionError) when failed to encrypt serialized message.
+    fn encrypt(&self, receiver_public_key: &ReceiverPublicKey) -> Result<EncryptedMessage, Error>
+    where
+        Self: Serialize,
+    {
+        self.encrypt_with_aad(receiver_public_key, &[])
+    }
+
+    /// Serialize and seal, binding associated data (`aad`) to the cipher-text.
+    ///
+    /// `aad` is authenticated but neither encrypted nor included in [EncryptedMessage](crate::EncryptedMessage).
+    ///
+    /// # Failures
+    ///
+    /// - [SerializationError](serde_encrypt_core::error::ErrorKind::SerializationError) when failed to serialize message.
+    /// - [EncryptionError](serde_encrypt_core::error::ErrorKind::EncryptionError) when failed to encrypt serialized message.
+    fn encrypt_with_aad(
+        &self,
+        receiver_public_key: &ReceiverPublicKey,
+        aad: &[u8],
+    ) -> Result<EncryptedMessage, Error>
+    where
+        Self: Serialize,
+    {
+        let serialized = Self::S::serialize(self)?;
+        let plain_msg = PlainMessageSealedBox::new(serialized.into_vec());
+        plain_msg.encrypt_with_aad(receiver_public_key, aad)
+    }
+
+    /// Open and deserialize into DeserializeOwned type.
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](serde_encrypt_core::error::ErrorKind::DecryptionError) when failed to decrypt message.
+    /// - [DeserializationError](serde_encrypt_core::error::ErrorKind::DeserializationError) when failed to deserialize decrypted message.
+    fn decrypt_owned(
+        encrypted_message: &EncryptedMessage,
+        receiver_key_pair: &ReceiverKeyPair,
+    ) -> Result<Self, Error>
+    where
+        Self: DeserializeOwned,
+    {
+        Self::decrypt_owned_with_aad(encrypted_message, receiver_key_pair, &[])
+    }
+
+    /// Open and deserialize into DeserializeOwned type.
+    /// `aad` must be the same as the one given to [encrypt_with_aad()](Self::encrypt_with_aad).
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](serde_encrypt_core::error::ErrorKind::DecryptionError) when failed to decrypt message (including `aad` mismatch).
+    /// - [DeserializationError](serde_encrypt_core::error::ErrorKind::DeserializationError) when failed to deserialize decrypted message.
+    fn decrypt_owned_with_aad(
+        encrypted_message: &EncryptedMessage,
+        receiver_key_pair: &ReceiverKeyPair,
+        aad: &[u8],
+    ) -> Result<Self, Error>
+    where
+        Self: DeserializeOwned,
+    {
+        let serialized = Self::decrypt_ref_with_aad(encrypted_message, receiver_key_pair, aad)?;
+        serialized.deserialize()
+    }
+
+    /// Just opens sealed box. Returned data must be deserialized later.
+    /// Types implementing `serde::Deserialize<'de>` (not `serde::de::DeserializeOwned`) should use
+    /// this function to resolve lifetime.
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](serde_encrypt_core::error::ErrorKind::DecryptionError) when failed to decrypt message.
+    fn decrypt_ref<'de>(
+        encrypted_message: &EncryptedMessage,
+        receiver_key_pair: &ReceiverKeyPair,
+    ) -> Result<Self::S, Error>
+    where
+        Self: Deserialize<'de>,
+    {
+        Self::decrypt_ref_with_aad(encrypted_message, receiver_key_pair, &[])
+    }
+
+    /// Just opens sealed box created by [encrypt_with_aad()](Self::encrypt_with_aad).
+    /// Returned data must be deserialized later.
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](serde_encrypt_core::error::ErrorKind::DecryptionError) when failed to decrypt message (including `aad` mismatch).
+    fn decrypt_ref_with_aad<'de>(
+        encrypted_message: &EncryptedMessage,
+        receiver_key_pair: &ReceiverKeyPair,
+        aad: &[u8],
+    ) -> Result<Self::S, Error>
+    where
+        Self: Deserialize<'de>,
+    {
+        let plain_msg =
+            PlainMessageSealedBox::decrypt_with_aad(encrypted_message, receiver_key_pair, aad)?;
+        Ok(Self::S::new(plain_msg.into_vec()))
+    }
+}
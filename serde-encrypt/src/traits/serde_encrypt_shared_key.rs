@@ -1,15 +1,18 @@
 use crate::{
-    encrypt::plain_message_shared_key::PlainMessageSharedKey, serialize::TypedSerialized,
-    shared_key::SharedKey, EncryptedMessage, Error,
+    encrypt::plain_message_shared_key::PlainMessageSharedKey, key_ring::KeyRing,
+    serialize::TypedSerialized, shared_key::SharedKey, EncryptedMessage, Error, PasswordKdf,
 };
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use serde_encrypt_core::encrypt::plain_message_shared_key::PlainMessageSharedKeyCore;
+use serde_encrypt_core::encrypt::{
+    aead::AeadAlgorithm, plain_message_shared_key::PlainMessageSharedKeyCore,
+};
 
 /// Shared-key authenticated encryption for serde-serializable types.
 ///
 /// # Features
 ///
 /// - Message authentication.
+/// - Associated data (AAD) binding via `*_with_aad()` functions.
 /// - Different cipher-text for the same plain-text to avoid attacks such as statistical analysis of cipher-text.
 /// - Uses small (32-byte) key.
 ///
@@ -38,14 +41,31 @@ use serde_encrypt_core::encrypt::plain_message_shared_key::PlainMessageSharedKey
 ///
 /// See [this example](https://github.com/laysakura/serde-encrypt/blob/main/serde-encrypt/tests/example_serde_encrypt_shared_key_encryption_with_key_exchange.rs).
 ///
+/// ## Encrypt with passphrase
+///
+/// [encrypt_with_password()](Self::encrypt_with_password) derives a key from passphrase by Argon2id (or scrypt)
+/// and stores salt and parameters in [EncryptedMessage](crate::EncryptedMessage), so that decryption only needs the passphrase.
+/// [SharedKey::derive_from_password()](crate::AsSharedKey::derive_from_password) is also available to manage salt by yourself.
+///
+/// ## Rotate keys
+///
+/// [encrypt_with_key_ring()](Self::encrypt_with_key_ring) encrypts with the primary key of [KeyRing](crate::key_ring::KeyRing)
+/// and records its key ID in message header. See [KeyRing](crate::key_ring::KeyRing) for key rotation.
+///
 /// # Algorithm
 ///
-/// - Encryption: XChaCha20
-/// - Message authentication: Poly1305 MAC
+/// Chosen by [A](Self::A) associated type from [aead](crate::aead) module:
+///
+/// - [XChaCha20Poly1305](crate::aead::XChaCha20Poly1305): XChaCha20 encryption and Poly1305 MAC.
+/// - [Aes256Gcm](crate::aead::Aes256Gcm): AES-256 encryption in GCM mode.
+/// - [Aes256GcmSiv](crate::aead::Aes256GcmSiv): AES-256 encryption in GCM-SIV mode.
 pub trait SerdeEncryptSharedKey {
     /// Serializer implementation
     type S: TypedSerialized<T = Self>;
 
+    /// AEAD algorithm implementation
+    type A: AeadAlgorithm;
+
     /// Serialize and encrypt.
     ///
     /// # Failures
@@ -53,12 +73,33 @@ pub trait SerdeEncryptSharedKey {
     /// - [SerializationError](serde_encrypt_core::error::ErrorKind::SerializationError) when failed to serialize message.
     /// - [EncryptionError](serde_encrypt_core::error::ErrorKind::EncryptionError) when failed to encrypt serialized message.
     fn encrypt(&self, shared_key: &SharedKey) -> Result<EncryptedMessage, Error>
+    where
+        Self: Serialize,
+    {
+        self.encrypt_with_aad(shared_key, &[])
+    }
+
+    /// Serialize and encrypt, binding associated data (`aad`) to the cipher-text.
+    ///
+    /// `aad` is authenticated but neither encrypted nor included in [EncryptedMessage](crate::EncryptedMessage).
+    /// Typical `aad` is a record ID, tenant ID or table column the message belongs to.
+    /// Decryption with a different `aad` fails, so a cipher-text moved to another context cannot be decrypted.
+    ///
+    /// # Failures
+    ///
+    /// - [SerializationError](serde_encrypt_core::error::ErrorKind::SerializationError) when failed to serialize message.
+    /// - [EncryptionError](serde_encrypt_core::error::ErrorKind::EncryptionError) when failed to encrypt serialized message.
+    fn encrypt_with_aad(
+        &self,
+        shared_key: &SharedKey,
+        aad: &[u8],
+    ) -> Result<EncryptedMessage, Error>
     where
         Self: Serialize,
     {
         let serialized = Self::S::serialize(self)?;
-        let plain_msg = PlainMessageSharedKey::new(serialized.into_vec());
-        plain_msg.encrypt(shared_key)
+        let plain_msg = PlainMessageSharedKey::<Self::A>::new(serialized.into_vec());
+        plain_msg.encrypt_with_aad(shared_key, aad)
     }
 
     /// Decrypt and deserialize into DeserializeOwned type.
@@ -74,7 +115,25 @@ pub trait SerdeEncryptSharedKey {
     where
         Self: DeserializeOwned,
     {
-        let serialized = Self::decrypt_ref(encrypted_message, shared_key)?;
+        Self::decrypt_owned_with_aad(encrypted_message, shared_key, &[])
+    }
+
+    /// Decrypt and deserialize into DeserializeOwned type.
+    /// `aad` must be the same as the one given to [encrypt_with_aad()](Self::encrypt_with_aad).
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](serde_encrypt_core::error::ErrorKind::DecryptionError) when failed to decrypt message (including `aad` mismatch).
+    /// - [DeserializationError](serde_encrypt_core::error::ErrorKind::DeserializationError) when failed to deserialize decrypted message.
+    fn decrypt_owned_with_aad(
+        encrypted_message: &EncryptedMessage,
+        shared_key: &SharedKey,
+        aad: &[u8],
+    ) -> Result<Self, Error>
+    where
+        Self: DeserializeOwned,
+    {
+        let serialized = Self::decrypt_ref_with_aad(encrypted_message, shared_key, aad)?;
         serialized.deserialize()
     }
 
@@ -92,7 +151,226 @@ pub trait SerdeEncryptSharedKey {
     where
         Self: Deserialize<'de>,
     {
-        let plain_msg = PlainMessageSharedKey::decrypt(encrypted_message, shared_key)?;
+        Self::decrypt_ref_with_aad(encrypted_message, shared_key, &[])
+    }
+
+    /// Just decrypts cipher-text created by [encrypt_with_aad()](Self::encrypt_with_aad).
+    /// Returned data must be deserialized later.
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](serde_encrypt_core::error::ErrorKind::DecryptionError) when failed to decrypt message (including `aad` mismatch).
+    fn decrypt_ref_with_aad<'de>(
+        encrypted_message: &EncryptedMessage,
+        shared_key: &SharedKey,
+        aad: &[u8],
+    ) -> Result<Self::S, Error>
+    where
+        Self: Deserialize<'de>,
+    {
+        let plain_msg =
+            PlainMessageSharedKey::<Self::A>::decrypt_with_aad(encrypted_message, shared_key, aad)?;
+        Ok(Self::S::new(plain_msg.into_vec()))
+    }
+
+    /// Serialize and encrypt by a key derived from `password` with `kdf` (e.g. `PasswordKdf::default()` for Argon2id).
+    ///
+    /// Random salt and `kdf` parameters are stored in [EncryptedMessage](crate::EncryptedMessage).
+    ///
+    /// # Failures
+    ///
+    /// - [SerializationError](serde_encrypt_core::error::ErrorKind::SerializationError) when failed to serialize message.
+    /// - [KeyDerivationError](serde_encrypt_core::error::ErrorKind::KeyDerivationError) when `kdf` parameters are invalid.
+    /// - [EncryptionError](serde_encrypt_core::error::ErrorKind::EncryptionError) when failed to encrypt serialized message.
+    fn encrypt_with_password(
+        &self,
+        password: &[u8],
+        kdf: &PasswordKdf,
+    ) -> Result<EncryptedMessage, Error>
+    where
+        Self: Serialize,
+    {
+        let serialized = Self::S::serialize(self)?;
+        let plain_msg = PlainMessageSharedKey::<Self::A>::new(serialized.into_vec());
+        plain_msg.encrypt_with_password(password, kdf)
+    }
+
+    /// Decrypt message created by [encrypt_with_password()](Self::encrypt_with_password) and deserialize into DeserializeOwned type.
+    ///
+    /// KDF parameters are read from `encrypted_message`. Their costs are bounded (e.g. [MAX_KDF_MEMORY](serde_encrypt_core::key::password_key::MAX_KDF_MEMORY)),
+    /// so that a message with huge cost parameters cannot make key derivation slow and memory-hungry.
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](serde_encrypt_core::error::ErrorKind::DecryptionError) when failed to decrypt message (including wrong `password` and KDF costs exceeding upper bounds).
+    /// - [DeserializationError](serde_encrypt_core::error::ErrorKind::DeserializationError) when failed to deserialize decrypted message.
+    fn decrypt_owned_with_password(
+        encrypted_message: &EncryptedMessage,
+        password: &[u8],
+    ) -> Result<Self, Error>
+    where
+        Self: DeserializeOwned,
+    {
+        let serialized = Self::decrypt_ref_with_password(encrypted_message, password)?;
+        serialized.deserialize()
+    }
+
+    /// Just decrypts cipher-text created by [encrypt_with_password()](Self::encrypt_with_password).
+    /// Returned data must be deserialized later.
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](serde_encrypt_core::error::ErrorKind::DecryptionError) when failed to decrypt message (including wrong `password`).
+    fn decrypt_ref_with_password<'de>(
+        encrypted_message: &EncryptedMessage,
+        password: &[u8],
+    ) -> Result<Self::S, Error>
+    where
+        Self: Deserialize<'de>,
+    {
+        let plain_msg =
+            PlainMessageSharedKey::<Self::A>::decrypt_with_password(encrypted_message, password)?;
         Ok(Self::S::new(plain_msg.into_vec()))
     }
+
+    /// Serialize and encrypt with the primary key of `key_ring`, recording its key ID in message header.
+    ///
+    /// Store the returned message by [serialize_versioned()](crate::EncryptedMessage::serialize_versioned),
+    /// since [serialize()](crate::EncryptedMessage::serialize) drops the key ID.
+    ///
+    /// # Failures
+    ///
+    /// - [SerializationError](serde_encrypt_core::error::ErrorKind::SerializationError) when failed to serialize message.
+    /// - [EncryptionError](serde_encrypt_core::error::ErrorKind::EncryptionError) when failed to encrypt serialized message.
+    fn encrypt_with_key_ring(&self, key_ring: &KeyRing) -> Result<EncryptedMessage, Error>
+    where
+        Self: Serialize,
+    {
+        self.encrypt_with_key_ring_with_aad(key_ring, &[])
+    }
+
+    /// Serialize and encrypt with the primary key of `key_ring`, recording its key ID in message header,
+    /// and binding associated data (`aad`) to the cipher-text.
+    ///
+    /// `aad` is authenticated but neither encrypted nor included in [EncryptedMessage](crate::EncryptedMessage).
+    ///
+    /// # Failures
+    ///
+    /// - [SerializationError](serde_encrypt_core::error::ErrorKind::SerializationError) when failed to serialize message.
+    /// - [EncryptionError](serde_encrypt_core::error::ErrorKind::EncryptionError) when failed to encrypt serialized message.
+    fn encrypt_with_key_ring_with_aad(
+        &self,
+        key_ring: &KeyRing,
+        aad: &[u8],
+    ) -> Result<EncryptedMessage, Error>
+    where
+        Self: Serialize,
+    {
+        let serialized = Self::S::serialize(self)?;
+        let plain_msg = PlainMessageSharedKey::<Self::A>::new(serialized.into_vec());
+        plain_msg.encrypt_with_key_id(key_ring.primary_key(), key_ring.primary_key_id(), aad)
+    }
+
+    /// Decrypt message created by [encrypt_with_key_ring()](Self::encrypt_with_key_ring) with the key of its key ID,
+    /// and deserialize into DeserializeOwned type.
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](serde_encrypt_core::error::ErrorKind::DecryptionError) when:
+    ///   - message does not have key ID or its key is not in `key_ring`.
+    ///   - failed to decrypt message.
+    /// - [DeserializationError](serde_encrypt_core::error::ErrorKind::DeserializationError) when failed to deserialize decrypted message.
+    fn decrypt_owned_with_key_ring(
+        encrypted_message: &EncryptedMessage,
+        key_ring: &KeyRing,
+    ) -> Result<Self, Error>
+    where
+        Self: DeserializeOwned,
+    {
+        Self::decrypt_owned_with_key_ring_with_aad(encrypted_message, key_ring, &[])
+    }
+
+    /// Decrypt message created by [encrypt_with_key_ring_with_aad()](Self::encrypt_with_key_ring_with_aad) with the key of its key ID,
+    /// and deserialize into DeserializeOwned type.
+    /// `aad` must be the same as the one given on encryption.
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](serde_encrypt_core::error::ErrorKind::DecryptionError) when:
+    ///   - message does not have key ID or its key is not in `key_ring`.
+    ///   - failed to decrypt message (including `aad` mismatch).
+    /// - [DeserializationError](serde_encrypt_core::error::ErrorKind::DeserializationError) when failed to deserialize decrypted message.
+    fn decrypt_owned_with_key_ring_with_aad(
+        encrypted_message: &EncryptedMessage,
+        key_ring: &KeyRing,
+        aad: &[u8],
+    ) -> Result<Self, Error>
+    where
+        Self: DeserializeOwned,
+    {
+        let serialized =
+            Self::decrypt_ref_with_key_ring_with_aad(encrypted_message, key_ring, aad)?;
+        serialized.deserialize()
+    }
+
+    /// Just decrypts cipher-text created by [encrypt_with_key_ring()](Self::encrypt_with_key_ring).
+    /// Returned data must be deserialized later.
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](serde_encrypt_core::error::ErrorKind::DecryptionError) when:
+    ///   - message does not have key ID or its key is not in `key_ring`.
+    ///   - failed to decrypt message.
+    fn decrypt_ref_with_key_ring<'de>(
+        encrypted_message: &EncryptedMessage,
+        key_ring: &KeyRing,
+    ) -> Result<Self::S, Error>
+    where
+        Self: Deserialize<'de>,
+    {
+        Self::decrypt_ref_with_key_ring_with_aad(encrypted_message, key_ring, &[])
+    }
+
+    /// Just decrypts cipher-text created by [encrypt_with_key_ring_with_aad()](Self::encrypt_with_key_ring_with_aad).
+    /// Returned data must be deserialized later.
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](serde_encrypt_core::error::ErrorKind::DecryptionError) when:
+    ///   - message does not have key ID or its key is not in `key_ring`.
+    ///   - failed to decrypt message (including `aad` mismatch).
+    fn decrypt_ref_with_key_ring_with_aad<'de>(
+        encrypted_message: &EncryptedMessage,
+        key_ring: &KeyRing,
+        aad: &[u8],
+    ) -> Result<Self::S, Error>
+    where
+        Self: Deserialize<'de>,
+    {
+        let shared_key = key_ring.key_for(encrypted_message)?;
+        Self::decrypt_ref_with_aad(encrypted_message, shared_key, aad)
+    }
+
+    /// Re-encrypts message created by [encrypt_with_key_ring_with_aad()](Self::encrypt_with_key_ring_with_aad) under the current primary key of `key_ring`,
+    /// without deserializing it.
+    ///
+    /// `aad` is the one given on encryption, and is bound to the re-encrypted message as well
+    /// (pass `&[]` for a message from [encrypt_with_key_ring()](Self::encrypt_with_key_ring)).
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](serde_encrypt_core::error::ErrorKind::DecryptionError) when:
+    ///   - message does not have key ID or its key is not in `key_ring`.
+    ///   - failed to decrypt message (including `aad` mismatch).
+    /// - [EncryptionError](serde_encrypt_core::error::ErrorKind::EncryptionError) when failed to encrypt message again.
+    fn reencrypt_with_key_ring(
+        encrypted_message: &EncryptedMessage,
+        key_ring: &KeyRing,
+        aad: &[u8],
+    ) -> Result<EncryptedMessage, Error> {
+        let shared_key = key_ring.key_for(encrypted_message)?;
+        let plain_msg =
+            PlainMessageSharedKey::<Self::A>::decrypt_with_aad(encrypted_message, shared_key, aad)?;
+        plain_msg.encrypt_with_key_id(key_ring.primary_key(), key_ring.primary_key_id(), aad)
+    }
 }
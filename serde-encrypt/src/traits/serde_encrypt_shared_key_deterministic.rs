@@ -10,6 +10,7 @@ use serde_encrypt_core::encrypt::plain_message_shared_key::PlainMessageSharedKey
 /// # Features
 ///
 /// - Message authentication.
+/// - Associated data (AAD) binding via `*_with_aad()` functions.
 /// - Same cipher-text for the same plain-text for eq-match in cipher-text.
 ///   Note that this is more vulnerable than [SerdeEncryptSharedKey](crate::traits::SerdeEncryptSharedKey)
 ///   because, for example, attackers can find repeated patterns in cipher-text and then guess
@@ -34,7 +35,10 @@ use serde_encrypt_core::encrypt::plain_message_shared_key::PlainMessageSharedKey
 ///
 /// - Encryption: XChaCha20
 /// - Message authentication: Poly1305 MAC
-/// - Fixed nonce.
+/// - Synthetic nonce: `HMAC-SHA256(subkey, len(aad) || aad || plain-text)` (SIV construction).
+///   Nonce is a 192-bit truncated HMAC output, so different plain-texts share a nonce only with negligible probability
+///   (about `q^2 / 2^193` for `q` distinct plain-texts under one key), which avoids nonce reuse with XChaCha20-Poly1305.
+///   Cipher-text encrypted by older versions (fixed nonce) can still be decrypted.
 pub trait SerdeEncryptSharedKeyDeterministic {
     /// Serializer implementation
     type S: TypedSerialized<T = Self>;
@@ -46,12 +50,33 @@ pub trait SerdeEncryptSharedKeyDeterministic {
     /// - [SerializationError](serde_encrypt_core::error::ErrorKind::SerializationError) when failed to serialize message.
     /// - [EncryptionError](serde_encrypt_core::error::ErrorKind::EncryptionError) when failed to encrypt serialized message.
     fn encrypt(&self, shared_key: &SharedKey) -> Result<EncryptedMessage, Error>
+    where
+        Self: Serialize,
+    {
+        self.encrypt_with_aad(shared_key, &[])
+    }
+
+    /// Serialize and encrypt, binding associated data (`aad`) to the cipher-text.
+    ///
+    /// `aad` is authenticated but neither encrypted nor included in [EncryptedMessage](crate::EncryptedMessage).
+    /// Typical `aad` is a record ID, tenant ID or table column the message belongs to.
+    /// Decryption with a different `aad` fails, so a cipher-text moved to another context cannot be decrypted.
+    ///
+    /// # Failures
+    ///
+    /// - [SerializationError](serde_encrypt_core::error::ErrorKind::SerializationError) when failed to serialize message.
+    /// - [EncryptionError](serde_encrypt_core::error::ErrorKind::EncryptionError) when failed to encrypt serialized message.
+    fn encrypt_with_aad(
+        &self,
+        shared_key: &SharedKey,
+        aad: &[u8],
+    ) -> Result<EncryptedMessage, Error>
     where
         Self: Serialize,
     {
         let serialized = Self::S::serialize(self)?;
         let plain_msg = PlainMessageSharedKeyDeterministic::new(serialized.into_vec());
-        plain_msg.encrypt(shared_key)
+        plain_msg.encrypt_with_aad(shared_key, aad)
     }
 
     /// Decrypt and deserialize into DeserializeOwned type.
@@ -67,7 +92,25 @@ pub trait SerdeEncryptSharedKeyDeterministic {
     where
         Self: DeserializeOwned,
     {
-        let serialized = Self::decrypt_ref(encrypted_message, shared_key)?;
+        Self::decrypt_owned_with_aad(encrypted_message, shared_key, &[])
+    }
+
+    /// Decrypt and deserialize into DeserializeOwned type.
+    /// `aad` must be the same as the one given to [encrypt_with_aad()](Self::encrypt_with_aad).
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](serde_encrypt_core::error::ErrorKind::DecryptionError) when failed to decrypt message (including `aad` mismatch).
+    /// - [DeserializationError](serde_encrypt_core::error::ErrorKind::DeserializationError) when failed to deserialize decrypted message.
+    fn decrypt_owned_with_aad(
+        encrypted_message: &EncryptedMessage,
+        shared_key: &SharedKey,
+        aad: &[u8],
+    ) -> Result<Self, Error>
+    where
+        Self: DeserializeOwned,
+    {
+        let serialized = Self::decrypt_ref_with_aad(encrypted_message, shared_key, aad)?;
         serialized.deserialize()
     }
 
@@ -85,7 +128,28 @@ pub trait SerdeEncryptSharedKeyDeterministic {
     where
         Self: Deserialize<'de>,
     {
-        let plain_msg = PlainMessageSharedKeyDeterministic::decrypt(encrypted_message, shared_key)?;
+        Self::decrypt_ref_with_aad(encrypted_message, shared_key, &[])
+    }
+
+    /// Just decrypts cipher-text created by [encrypt_with_aad()](Self::encrypt_with_aad).
+    /// Returned data must be deserialized later.
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](serde_encrypt_core::error::ErrorKind::DecryptionError) when failed to decrypt message (including `aad` mismatch).
+    fn decrypt_ref_with_aad<'de>(
+        encrypted_message: &EncryptedMessage,
+        shared_key: &SharedKey,
+        aad: &[u8],
+    ) -> Result<Self::S, Error>
+    where
+        Self: Deserialize<'de>,
+    {
+        let plain_msg = PlainMessageSharedKeyDeterministic::decrypt_with_aad(
+            encrypted_message,
+            shared_key,
+            aad,
+        )?;
         Ok(Self::S::new(plain_msg.into_vec()))
     }
 }
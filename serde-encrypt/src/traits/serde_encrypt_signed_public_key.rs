@@ -0,0 +1,163 @@
+use crate::encrypt::plain_message_signed_public_key::PlainMessageSignedPublicKey;
+use crate::key::signing_key_pair::SigningKeyPair;
+use crate::serialize::TypedSerialized;
+use crate::{EncryptedMessage, Error, ReceiverCombinedKey, SenderCombinedKey, VerifyingKey};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_encrypt_core::encrypt::plain_message_signed_public_key::PlainMessageSignedPublicKeyCore;
+
+/// Public-key authenticated encryption with sender's identity signature (sign-then-encrypt) for serde-serializable types.
+///
+/// Opt-in alternative to [SerdeEncryptPublicKey](crate::traits::SerdeEncryptPublicKey) when receiver needs to know who sent a message.
+/// Sender signs serialized message (with its context: both X25519 public keys and `aad`) by Ed25519 [SigningKeyPair](crate::key::signing_key_pair::SigningKeyPair),
+/// and receiver verifies the signature after decryption against a [VerifyingKey](crate::VerifyingKey) it trusts.
+///
+/// # Features
+///
+/// - Identity authentication of sender (non-repudiable Ed25519 signature).
+/// - Signature is bound to receiver's public key, so that receiver cannot forward a signed message to others as if sent to them.
+/// - Message authentication.
+/// - Associated data (AAD) binding via `*_with_aad()` functions.
+/// - Different cipher-text for the same plain-text to avoid attacks such as statistical analysis of cipher-text.
+///
+/// # Anti-features
+///
+/// - Identity authentication of receiver.
+/// - Distribution of verifying keys: receiver must get sender's verifying key via a trusted channel.
+///
+/// # Popular use cases
+///
+/// - Commands or audit records whose sender must be identified.
+///
+/// # Algorithm
+///
+/// - Signature: Ed25519
+/// - Public-key exchange: X25519
+/// - Encryption: XChaCha20
+/// - Message authentication: Poly1305 MAC
+pub trait SerdeEncryptSignedPublicKey {
+    /// Serializer implementation
+    type S: TypedSerialized<T = Self>;
+
+    /// Serialize, sign and encrypt.
+    ///
+    /// # Failures
+    ///
+    /// - [SerializationError](serde_encrypt_core::error::ErrorKind::SerializationError) when failed to serialize message.
+    /// - [EncryptionError](serde_encrypt_core::error::ErrorKind::EncryptionError) when failed to encrypt serialized message.
+    fn encrypt(
+        &self,
+        combined_key: &SenderCombinedKey,
+        signing_key_pair: &SigningKeyPair,
+    ) -> Result<EncryptedMessage, Error>
+    where
+        Self: Serialize,
+    {
+        self.encrypt_with_aad(combined_key, signing_key_pair, &[])
+    }
+
+    /// Serialize, sign and encrypt, binding associated data (`aad`) to the signature and cipher-text.
+    ///
+    /// `aad` is authenticated but neither encrypted nor included in [EncryptedMessage](crate::EncryptedMessage).
+    ///
+    /// # Failures
+    ///
+    /// - [SerializationError](serde_encrypt_core::error::ErrorKind::SerializationError) when failed to serialize message.
+    /// - [EncryptionError](serde_encrypt_core::error::ErrorKind::EncryptionError) when failed to encrypt serialized message.
+    fn encrypt_with_aad(
+        &self,
+        combined_key: &SenderCombinedKey,
+        signing_key_pair: &SigningKeyPair,
+        aad: &[u8],
+    ) -> Result<EncryptedMessage, Error>
+    where
+        Self: Serialize,
+    {
+        let serialized = Self::S::serialize(self)?;
+        let plain_msg = PlainMessageSignedPublicKey::new(serialized.into_vec());
+        plain_msg.encrypt_with_aad(combined_key, signing_key_pair, aad)
+    }
+
+    /// Decrypt, verify and deserialize into DeserializeOwned type.
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](serde_encrypt_core::error::ErrorKind::DecryptionError) when failed to decrypt message.
+    /// - [SignatureError](serde_encrypt_core::error::ErrorKind::SignatureError) when signature does not match `verifying_key`.
+    /// - [DeserializationError](serde_encrypt_core::error::ErrorKind::DeserializationError) when failed to deserialize decrypted message.
+    fn decrypt_owned(
+        encrypted_message: &EncryptedMessage,
+        combined_key: &ReceiverCombinedKey,
+        verifying_key: &VerifyingKey,
+    ) -> Result<Self, Error>
+    where
+        Self: DeserializeOwned,
+    {
+        Self::decrypt_owned_with_aad(encrypted_message, combined_key, verifying_key, &[])
+    }
+
+    /// Decrypt, verify and deserialize into DeserializeOwned type.
+    /// `aad` must be the same as the one given to [encrypt_with_aad()](Self::encrypt_with_aad).
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](serde_encrypt_core::error::ErrorKind::DecryptionError) when failed to decrypt message (including `aad` mismatch).
+    /// - [SignatureError](serde_encrypt_core::error::ErrorKind::SignatureError) when signature does not match `verifying_key`.
+    /// - [DeserializationError](serde_encrypt_core::error::ErrorKind::DeserializationError) when failed to deserialize decrypted message.
+    fn decrypt_owned_with_aad(
+        encrypted_message: &EncryptedMessage,
+        combined_key: &ReceiverCombinedKey,
+        verifying_key: &VerifyingKey,
+        aad: &[u8],
+    ) -> Result<Self, Error>
+    where
+        Self: DeserializeOwned,
+    {
+        let serialized =
+            Self::decrypt_ref_with_aad(encrypted_message, combined_key, verifying_key, aad)?;
+        serialized.deserialize()
+    }
+
+    /// Just decrypts cipher-text and verifies signature. Returned data must be deserialized later.
+    /// Types implementing `serde::Deserialize<'de>` (not `serde::de::DeserializeOwned`) should use
+    /// this function to resolve lifetime.
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](serde_encrypt_core::error::ErrorKind::DecryptionError) when failed to decrypt message.
+    /// - [SignatureError](serde_encrypt_core::error::ErrorKind::SignatureError) when signature does not match `verifying_key`.
+    fn decrypt_ref<'de>(
+        encrypted_message: &EncryptedMessage,
+        combined_key: &ReceiverCombinedKey,
+        verifying_key: &VerifyingKey,
+    ) -> Result<Self::S, Error>
+    where
+        Self: Deserialize<'de>,
+    {
+        Self::decrypt_ref_with_aad(encrypted_message, combined_key, verifying_key, &[])
+    }
+
+    /// Just decrypts cipher-text created by [encrypt_with_aad()](Self::encrypt_with_aad) and verifies signature.
+    /// Returned data must be deserialized later.
+    ///
+    /// # Failures
+    ///
+    /// - [DecryptionError](serde_encrypt_core::error::ErrorKind::DecryptionError) when failed to decrypt message (including `aad` mismatch).
+    /// - [SignatureError](serde_encrypt_core::error::ErrorKind::SignatureError) when signature does not match `verifying_key`.
+    fn decrypt_ref_with_aad<'de>(
+        encrypted_message: &EncryptedMessage,
+        combined_key: &ReceiverCombinedKey,
+        verifying_key: &VerifyingKey,
+        aad: &[u8],
+    ) -> Result<Self::S, Error>
+    where
+        Self: Deserialize<'de>,
+    {
+        let plain_msg = PlainMessageSignedPublicKey::decrypt_with_aad(
+            encrypted_message,
+            combined_key,
+            verifying_key,
+            aad,
+        )?;
+        Ok(Self::S::new(plain_msg.into_vec()))
+    }
+}
@@ -3,6 +3,7 @@
 use serde::{Deserialize, Serialize};
 use serde_encrypt::{
     key::key_pair::{ReceiverKeyPair, SenderKeyPair},
+    public_key_backend::ChaChaBox,
     serialize::impls::BincodeSerializer,
     traits::SerdeEncryptPublicKey,
     EncryptedMessage, Error, ReceiverCombinedKey, ReceiverKeyPairCore, SenderCombinedKey,
@@ -17,6 +18,7 @@ struct Message {
 
 impl SerdeEncryptPublicKey for Message {
     type S = BincodeSerializer<Self>;
+    type B = ChaChaBox;
 }
 
 fn alice_sends_secret_message(combined_key: &SenderCombinedKey) -> Result<Vec<u8>, Error> {
@@ -16,6 +16,7 @@
 
 use serde::{Deserialize, Serialize};
 use serde_encrypt::{
+    aead::XChaCha20Poly1305,
     key::key_pair::{ReceiverKeyPair, SenderKeyPair},
     serialize::{impls::BincodeSerializer, TypedSerialized},
     shared_key::SharedKey,
@@ -26,6 +27,7 @@ use serde_encrypt::{
 
 impl<'a> SerdeEncryptSharedKey for Message<'a> {
     type S = BincodeSerializer<Self>;
+    type A = XChaCha20Poly1305;
 }
 
 fn bob_generates_shared_key() -> SharedKey {
@@ -2,8 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 use serde_encrypt::{
-    serialize::impls::BincodeSerializer, shared_key::SharedKey, traits::SerdeEncryptSharedKey,
-    AsSharedKey, EncryptedMessage, Error,
+    aead::XChaCha20Poly1305, serialize::impls::BincodeSerializer, shared_key::SharedKey,
+    traits::SerdeEncryptSharedKey, AsSharedKey, EncryptedMessage, Error,
 };
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -14,6 +14,7 @@ struct Message {
 
 impl SerdeEncryptSharedKey for Message {
     type S = BincodeSerializer<Self>;
+    type A = XChaCha20Poly1305;
 }
 
 fn alice_sends_secret_message(shared_key: &SharedKey) -> Result<Vec<u8>, Error> {
@@ -0,0 +1,117 @@
+//! Test if:
+//!
+//! - SerdeEncryptPublicKey
+//! - SerdeEncryptSharedKey
+//! - SerdeEncryptSharedKeyDeterministic
+//!
+//!  bind associated data (AAD) to cipher-text and fail to decrypt with different AAD.
+
+mod test_util;
+
+use serde::{Deserialize, Serialize};
+use serde_encrypt::{
+    aead::XChaCha20Poly1305,
+    public_key_backend::ChaChaBox,
+    serialize::impls::BincodeSerializer,
+    shared_key::SharedKey,
+    traits::{SerdeEncryptPublicKey, SerdeEncryptSharedKey, SerdeEncryptSharedKeyDeterministic},
+    AsSharedKey, Error, ErrorKind,
+};
+use test_util::serde_encrypt_public_key::*;
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct Message(String);
+
+impl SerdeEncryptPublicKey for Message {
+    type S = BincodeSerializer<Self>;
+    type B = ChaChaBox;
+}
+impl SerdeEncryptSharedKey for Message {
+    type S = BincodeSerializer<Self>;
+    type A = XChaCha20Poly1305;
+}
+impl SerdeEncryptSharedKeyDeterministic for Message {
+    type S = BincodeSerializer<Self>;
+}
+
+const AAD_ROW1: &[u8] = b"users.ssn:1";
+const AAD_ROW2: &[u8] = b"users.ssn:2";
+
+#[test]
+fn test_serde_encrypt_public_key_aad() -> Result<(), Error> {
+    combined_keys_gen!(sender_combined_key, receiver_combined_key);
+
+    let msg = Message("123-45-6789".into());
+    let enc = SerdeEncryptPublicKey::encrypt_with_aad(&msg, &sender_combined_key, AAD_ROW1)?;
+
+    let dec = <Message as SerdeEncryptPublicKey>::decrypt_owned_with_aad(
+        &enc,
+        &receiver_combined_key,
+        AAD_ROW1,
+    )?;
+    assert_eq!(dec, msg);
+
+    let e = <Message as SerdeEncryptPublicKey>::decrypt_owned_with_aad(
+        &enc,
+        &receiver_combined_key,
+        AAD_ROW2,
+    )
+    .unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+
+    let e = <Message as SerdeEncryptPublicKey>::decrypt_owned(&enc, &receiver_combined_key)
+        .unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+
+    Ok(())
+}
+
+#[test]
+fn test_serde_encrypt_shared_key_aad() -> Result<(), Error> {
+    let shared_key = SharedKey::generate();
+
+    let msg = Message("123-45-6789".into());
+    let enc = SerdeEncryptSharedKey::encrypt_with_aad(&msg, &shared_key, AAD_ROW1)?;
+
+    let dec =
+        <Message as SerdeEncryptSharedKey>::decrypt_owned_with_aad(&enc, &shared_key, AAD_ROW1)?;
+    assert_eq!(dec, msg);
+
+    let e = <Message as SerdeEncryptSharedKey>::decrypt_owned_with_aad(&enc, &shared_key, AAD_ROW2)
+        .unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+
+    let e = <Message as SerdeEncryptSharedKey>::decrypt_owned(&enc, &shared_key).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+
+    Ok(())
+}
+
+#[test]
+fn test_serde_encrypt_shared_key_deterministic_aad() -> Result<(), Error> {
+    let shared_key = SharedKey::generate();
+
+    let msg = Message("123-45-6789".into());
+    let enc = SerdeEncryptSharedKeyDeterministic::encrypt_with_aad(&msg, &shared_key, AAD_ROW1)?;
+
+    let dec = <Message as SerdeEncryptSharedKeyDeterministic>::decrypt_owned_with_aad(
+        &enc,
+        &shared_key,
+        AAD_ROW1,
+    )?;
+    assert_eq!(dec, msg);
+
+    let e = <Message as SerdeEncryptSharedKeyDeterministic>::decrypt_owned_with_aad(
+        &enc,
+        &shared_key,
+        AAD_ROW2,
+    )
+    .unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+
+    let e = <Message as SerdeEncryptSharedKeyDeterministic>::decrypt_owned(&enc, &shared_key)
+        .unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+
+    Ok(())
+}
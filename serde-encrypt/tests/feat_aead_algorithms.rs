@@ -0,0 +1,98 @@
+//! Test if SerdeEncryptSharedKey works with every AEAD algorithm chosen by `type A`.
+
+mod test_util;
+
+use serde::{Deserialize, Serialize};
+use serde_encrypt::{
+    aead::{Aes256Gcm, Aes256GcmSiv, XChaCha20Poly1305},
+    serialize::impls::BincodeSerializer,
+    shared_key::SharedKey,
+    traits::SerdeEncryptSharedKey,
+    Algorithm, AsSharedKey, EncryptedMessage, Error, ErrorKind,
+};
+use test_util::serde_encrypt_shared_key::*;
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct XChaChaMessage(String);
+impl SerdeEncryptSharedKey for XChaChaMessage {
+    type S = BincodeSerializer<Self>;
+    type A = XChaCha20Poly1305;
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct AesGcmMessage(String);
+impl SerdeEncryptSharedKey for AesGcmMessage {
+    type S = BincodeSerializer<Self>;
+    type A = Aes256Gcm;
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct AesGcmSivMessage(String);
+impl SerdeEncryptSharedKey for AesGcmSivMessage {
+    type S = BincodeSerializer<Self>;
+    type A = Aes256GcmSiv;
+}
+
+fn algorithm_of(encrypted_message: &EncryptedMessage) -> Algorithm {
+    encrypted_message
+        .header()
+        .expect("encrypted message has header")
+        .algorithm()
+}
+
+#[test]
+fn test_xchacha20poly1305() -> Result<(), Error> {
+    let shared_key = SharedKey::generate();
+    let msg = XChaChaMessage("XChaCha20-Poly1305".into());
+
+    shared_key_enc_dec_assert_eq(&msg, &shared_key)?;
+    assert_eq!(
+        algorithm_of(&msg.encrypt(&shared_key)?),
+        Algorithm::XChaCha20Poly1305
+    );
+    Ok(())
+}
+
+#[test]
+fn test_aes256gcm() -> Result<(), Error> {
+    let shared_key = SharedKey::generate();
+    let msg = AesGcmMessage("AES-256-GCM".into());
+
+    shared_key_enc_dec_assert_eq(&msg, &shared_key)?;
+    assert_eq!(
+        algorithm_of(&msg.encrypt(&shared_key)?),
+        Algorithm::Aes256Gcm
+    );
+    Ok(())
+}
+
+#[test]
+fn test_aes256gcmsiv() -> Result<(), Error> {
+    let shared_key = SharedKey::generate();
+    let msg = AesGcmSivMessage("AES-256-GCM-SIV".into());
+
+    shared_key_enc_dec_assert_eq(&msg, &shared_key)?;
+    assert_eq!(
+        algorithm_of(&msg.encrypt(&shared_key)?),
+        Algorithm::Aes256GcmSiv
+    );
+    Ok(())
+}
+
+#[test]
+fn test_decryption_error_on_algorithm_mismatch() -> Result<(), Error> {
+    let shared_key = SharedKey::generate();
+    let msg = AesGcmMessage("AES-256-GCM".into());
+
+    // with header
+    let enc = msg.encrypt(&shared_key)?;
+    let e = XChaChaMessage::decrypt_owned(&enc, &shared_key).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+
+    // without header
+    let enc = EncryptedMessage::deserialize(msg.encrypt(&shared_key)?.serialize())?;
+    let e = AesGcmSivMessage::decrypt_owned(&enc, &shared_key).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+
+    Ok(())
+}
@@ -0,0 +1,126 @@
+//! Test if tokio adapters of stream encryption:
+//!
+//! - round-trip payloads through `tokio::io::duplex`,
+//! - are compatible with `std::io` adapters,
+//! - detect truncated streams.
+
+#![cfg(feature = "tokio")]
+
+use std::io::{self, Read, Write};
+
+use serde_encrypt::{
+    async_io::{AsyncDecryptingReader, AsyncEncryptingWriter},
+    shared_key::SharedKey,
+    stream::{DecryptingReader, EncryptingWriter, CHUNK_SIZE},
+    AsSharedKey,
+};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+fn payload(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i % 251) as u8).collect()
+}
+
+async fn round_trip_via_duplex(
+    shared_key: &SharedKey,
+    plain: Vec<u8>,
+    write_size: usize,
+) -> io::Result<Vec<u8>> {
+    let (client, server) = tokio::io::duplex(1024);
+
+    let writer_key = shared_key.clone();
+    let upload = tokio::spawn(async move {
+        let mut writer = AsyncEncryptingWriter::new(client, &writer_key);
+        for chunk in plain.chunks(write_size) {
+            writer.write_all(chunk).await?;
+        }
+        writer.shutdown().await
+    });
+
+    let mut reader = AsyncDecryptingReader::new(server, shared_key);
+    let mut decrypted = Vec::new();
+    reader.read_to_end(&mut decrypted).await?;
+
+    upload.await.unwrap()?;
+    Ok(decrypted)
+}
+
+#[tokio::test]
+async fn test_round_trip_via_duplex() -> io::Result<()> {
+    let shared_key = SharedKey::generate();
+
+    for len in [0, 1, CHUNK_SIZE, CHUNK_SIZE * 2 + 5] {
+        for write_size in [100, CHUNK_SIZE + 3] {
+            let plain = payload(len);
+            let decrypted = round_trip_via_duplex(&shared_key, plain.clone(), write_size).await?;
+            assert_eq!(decrypted, plain, "len={}", len);
+        }
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_compatible_with_std_io_adapters() -> io::Result<()> {
+    let shared_key = SharedKey::generate();
+    let plain = payload(CHUNK_SIZE + 10);
+
+    // async -> sync
+    let mut writer = AsyncEncryptingWriter::new(Vec::new(), &shared_key);
+    writer.write_all(&plain).await?;
+    writer.shutdown().await?;
+    let encrypted = writer.into_inner();
+
+    let mut decrypted = Vec::new();
+    DecryptingReader::new(encrypted.as_slice(), &shared_key)?.read_to_end(&mut decrypted)?;
+    assert_eq!(decrypted, plain);
+
+    // sync -> async
+    let mut writer = EncryptingWriter::new(Vec::new(), &shared_key);
+    writer.write_all(&plain)?;
+    let encrypted = writer.finish()?;
+
+    let mut decrypted = Vec::new();
+    AsyncDecryptingReader::new(encrypted.as_slice(), &shared_key)
+        .read_to_end(&mut decrypted)
+        .await?;
+    assert_eq!(decrypted, plain);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_error_on_truncation() -> io::Result<()> {
+    let shared_key = SharedKey::generate();
+
+    // shutdown() is not called
+    let mut writer = AsyncEncryptingWriter::new(Vec::new(), &shared_key);
+    writer.write_all(&payload(CHUNK_SIZE * 2)).await?;
+    writer.flush().await?;
+    let encrypted = writer.into_inner();
+
+    let mut decrypted = Vec::new();
+    let e = AsyncDecryptingReader::new(encrypted.as_slice(), &shared_key)
+        .read_to_end(&mut decrypted)
+        .await
+        .unwrap_err();
+    assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_error_on_wrong_key() -> io::Result<()> {
+    let mut writer = AsyncEncryptingWriter::new(Vec::new(), &SharedKey::generate());
+    writer.write_all(b"secret").await?;
+    writer.shutdown().await?;
+    let encrypted = writer.into_inner();
+
+    let mut decrypted = Vec::new();
+    let e = AsyncDecryptingReader::new(encrypted.as_slice(), &SharedKey::generate())
+        .read_to_end(&mut decrypted)
+        .await
+        .unwrap_err();
+    assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+    assert!(decrypted.is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,110 @@
+//! Test if SharedKeyCodec:
+//!
+//! - frames sequence of encrypted messages over a byte stream,
+//! - decodes partial frames,
+//! - returns serde_encrypt::Error on decode errors.
+
+#![cfg(feature = "codec")]
+
+use bytes::BytesMut;
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_encrypt::{
+    aead::XChaCha20Poly1305, codec::SharedKeyCodec, serialize::impls::BincodeSerializer,
+    shared_key::SharedKey, traits::SerdeEncryptSharedKey, AsSharedKey, Error, ErrorKind,
+};
+use tokio_util::codec::{Decoder, Encoder, FramedRead, FramedWrite};
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+struct Message {
+    seq: u32,
+    content: String,
+}
+
+impl SerdeEncryptSharedKey for Message {
+    type S = BincodeSerializer<Self>;
+    type A = XChaCha20Poly1305;
+}
+
+fn messages() -> Vec<Message> {
+    (0..10)
+        .map(|seq| Message {
+            seq,
+            content: "x".repeat(seq as usize * 100),
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_framed_over_duplex() -> Result<(), Error> {
+    let shared_key = SharedKey::generate();
+    let (client, server) = tokio::io::duplex(256);
+
+    let sender_key = shared_key.clone();
+    let send = tokio::spawn(async move {
+        let mut sink = FramedWrite::new(client, SharedKeyCodec::<Message>::new(sender_key));
+        for msg in messages() {
+            sink.send(msg).await?;
+        }
+        sink.close().await
+    });
+
+    let mut stream = FramedRead::new(server, SharedKeyCodec::<Message>::new(shared_key));
+    let mut received = Vec::new();
+    while let Some(msg) = stream.next().await {
+        received.push(msg?);
+    }
+
+    send.await.unwrap()?;
+    assert_eq!(received, messages());
+    Ok(())
+}
+
+#[test]
+fn test_decode_partial_frame() -> Result<(), Error> {
+    let shared_key = SharedKey::generate();
+    let mut codec = SharedKeyCodec::<Message>::new(shared_key);
+
+    let mut encoded = BytesMut::new();
+    let msg = messages().pop().unwrap();
+    codec.encode(msg.clone(), &mut encoded)?;
+
+    let mut src = BytesMut::new();
+    let (head, tail) = encoded.split_at(encoded.len() / 2);
+    src.extend_from_slice(head);
+    assert_eq!(codec.decode(&mut src)?, None);
+    src.extend_from_slice(tail);
+    assert_eq!(codec.decode(&mut src)?, Some(msg));
+    assert!(src.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_decode_error_on_wrong_key() -> Result<(), Error> {
+    let mut encoder = SharedKeyCodec::<Message>::new(SharedKey::generate());
+    let mut decoder = SharedKeyCodec::<Message>::new(SharedKey::generate());
+
+    let mut buf = BytesMut::new();
+    encoder.encode(messages().remove(0), &mut buf)?;
+    let e = decoder.decode(&mut buf).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+    Ok(())
+}
+
+#[test]
+fn test_error_on_too_long_frame() -> Result<(), Error> {
+    let shared_key = SharedKey::generate();
+    let mut codec = SharedKeyCodec::<Message>::with_max_frame_length(shared_key.clone(), 100);
+
+    let mut buf = BytesMut::new();
+    let e = codec
+        .encode(messages().pop().unwrap(), &mut buf)
+        .unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::IoError);
+
+    let mut large_codec = SharedKeyCodec::<Message>::new(shared_key);
+    large_codec.encode(messages().pop().unwrap(), &mut buf)?;
+    let e = codec.decode(&mut buf).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::IoError);
+    Ok(())
+}
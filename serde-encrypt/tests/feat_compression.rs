@@ -0,0 +1,218 @@
+//! Test if Compressed serializer:
+//!
+//! - shrinks cipher-text of compressible messages in all traits,
+//! - records compressor ID so that decryption undoes compression even after compressor is switched,
+//! - rejects unknown compressor ID,
+//! - stops decompression at `MAX_DECOMPRESSED_SIZE`.
+
+#![cfg(all(feature = "deflate", feature = "lz4", feature = "zstd"))]
+
+use serde::{Deserialize, Serialize};
+use serde_encrypt::{
+    aead::XChaCha20Poly1305,
+    key::key_pair::{ReceiverKeyPair, SenderKeyPair},
+    public_key_backend::ChaChaBox,
+    serialize::{
+        compress::{Compressor, Deflate, Lz4, Zstd, MAX_DECOMPRESSED_SIZE},
+        impls::{BincodeSerializer, Compressed},
+        TypedSerialized,
+    },
+    shared_key::SharedKey,
+    traits::{SerdeEncryptPublicKey, SerdeEncryptSealedBox, SerdeEncryptSharedKey},
+    AsSharedKey, Error, ErrorKind, ReceiverCombinedKey, ReceiverKeyPairCore, SenderCombinedKey,
+    SenderKeyPairCore,
+};
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+struct Event {
+    kind: String,
+    user: String,
+    tags: Vec<String>,
+}
+
+fn events() -> Vec<Event> {
+    (0..100)
+        .map(|i| Event {
+            kind: "page_view".into(),
+            user: format!("user-{}", i % 3),
+            tags: vec!["web".into(), "campaign-2021".into()],
+        })
+        .collect()
+}
+
+macro_rules! events_type {
+    ($name:ident, $s:ty) => {
+        #[derive(PartialEq, Debug, Serialize, Deserialize)]
+        struct $name(Vec<Event>);
+        impl SerdeEncryptSharedKey for $name {
+            type S = $s;
+            type A = XChaCha20Poly1305;
+        }
+    };
+}
+
+events_type!(Plain, BincodeSerializer<Self>);
+events_type!(WithDeflate, Compressed<BincodeSerializer<Self>, Deflate>);
+events_type!(WithLz4, Compressed<BincodeSerializer<Self>, Lz4>);
+events_type!(WithZstd, Compressed<BincodeSerializer<Self>, Zstd>);
+
+#[test]
+fn test_shared_key_compression() -> Result<(), Error> {
+    let shared_key = SharedKey::generate();
+
+    let plain_len = Plain(events()).encrypt(&shared_key)?.len();
+
+    let enc = WithDeflate(events()).encrypt(&shared_key)?;
+    assert!(enc.len() * 5 < plain_len);
+    assert_eq!(WithDeflate::decrypt_owned(&enc, &shared_key)?.0, events());
+
+    let enc = WithLz4(events()).encrypt(&shared_key)?;
+    assert!(enc.len() * 5 < plain_len);
+    assert_eq!(WithLz4::decrypt_owned(&enc, &shared_key)?.0, events());
+
+    let enc = WithZstd(events()).encrypt(&shared_key)?;
+    assert!(enc.len() * 5 < plain_len);
+    assert_eq!(WithZstd::decrypt_owned(&enc, &shared_key)?.0, events());
+
+    Ok(())
+}
+
+#[test]
+fn test_public_key_and_sealed_box_compression() -> Result<(), Error> {
+    #[derive(PartialEq, Debug, Serialize, Deserialize)]
+    struct Report(Vec<Event>);
+    impl SerdeEncryptPublicKey for Report {
+        type S = Compressed<BincodeSerializer<Self>, Lz4>;
+        type B = ChaChaBox;
+    }
+    impl SerdeEncryptSealedBox for Report {
+        type S = Compressed<BincodeSerializer<Self>, Deflate>;
+    }
+
+    let alice_key_pair = SenderKeyPair::generate();
+    let bob_key_pair = ReceiverKeyPair::generate();
+    let alice_combined_key =
+        SenderCombinedKey::new(alice_key_pair.private_key(), bob_key_pair.public_key());
+    let bob_combined_key =
+        ReceiverCombinedKey::new(alice_key_pair.public_key(), bob_key_pair.private_key());
+
+    let msg = Report(events());
+
+    let enc = SerdeEncryptPublicKey::encrypt(&msg, &alice_combined_key)?;
+    let dec = <Report as SerdeEncryptPublicKey>::decrypt_owned(&enc, &bob_combined_key)?;
+    assert_eq!(dec, msg);
+
+    let enc = SerdeEncryptSealedBox::encrypt(&msg, bob_key_pair.public_key())?;
+    let dec = <Report as SerdeEncryptSealedBox>::decrypt_owned(&enc, &bob_key_pair)?;
+    assert_eq!(dec, msg);
+
+    Ok(())
+}
+
+#[test]
+fn test_switch_compressor() -> Result<(), Error> {
+    let shared_key = SharedKey::generate();
+
+    // Messages written by older compressor (or without compression) are still read.
+    let enc = WithDeflate(events()).encrypt(&shared_key)?;
+    assert_eq!(WithZstd::decrypt_owned(&enc, &shared_key)?.0, events());
+    let enc = WithZstd(events()).encrypt(&shared_key)?;
+    assert_eq!(WithLz4::decrypt_owned(&enc, &shared_key)?.0, events());
+
+    Ok(())
+}
+
+#[test]
+fn test_compressor_id() -> Result<(), Error> {
+    let msg = WithLz4(events());
+    let serialized = <WithLz4 as SerdeEncryptSharedKey>::S::serialize(&msg)?;
+    assert_eq!(serialized.as_slice()[0], Lz4::ID);
+
+    let mut bin = serialized.into_vec();
+    bin[0] = 0xff;
+    let e = <WithLz4 as SerdeEncryptSharedKey>::S::new(bin)
+        .deserialize()
+        .unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DeserializationError);
+
+    // uncompressed plain-text is not read by compressed serializer
+    let shared_key = SharedKey::generate();
+    let enc = Plain(events()).encrypt(&shared_key)?;
+    let e = WithLz4::decrypt_owned(&enc, &shared_key).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DeserializationError);
+
+    Ok(())
+}
+
+#[test]
+fn test_custom_compressor() -> Result<(), Error> {
+    /// Run-length encoding (`run length || byte` pairs), for testing only.
+    struct Rle;
+    impl Compressor for Rle {
+        const ID: u8 = 200;
+
+        fn compress(plain: &[u8]) -> Result<Vec<u8>, Error> {
+            let mut compressed = Vec::new();
+            for &b in plain {
+                match compressed.len() {
+                    n if n >= 2 && compressed[n - 1] == b && compressed[n - 2] < u8::MAX => {
+                        compressed[n - 2] += 1
+                    }
+                    _ => compressed.extend_from_slice(&[1, b]),
+                }
+            }
+            Ok(compressed)
+        }
+
+        fn decompress(compressed: &[u8]) -> Result<Vec<u8>, Error> {
+            Ok(compressed
+                .chunks(2)
+                .flat_map(|run| vec![run[1]; run[0] as usize])
+                .collect())
+        }
+    }
+
+    #[derive(PartialEq, Debug, Serialize, Deserialize)]
+    struct Zeros(Vec<u8>);
+    impl SerdeEncryptSharedKey for Zeros {
+        type S = Compressed<BincodeSerializer<Self>, Rle>;
+        type A = XChaCha20Poly1305;
+    }
+
+    let shared_key = SharedKey::generate();
+    let msg = Zeros(vec![0; 1000]);
+    let enc = msg.encrypt(&shared_key)?;
+    assert!(enc.len() < 100);
+    assert_eq!(Zeros::decrypt_owned(&enc, &shared_key)?, msg);
+
+    Ok(())
+}
+
+#[test]
+fn test_decompression_bomb() -> Result<(), Error> {
+    type S = <WithLz4 as SerdeEncryptSharedKey>::S;
+
+    // LZ4 size prefix claiming 4 GiB
+    let e = S::new(vec![Lz4::ID, 0xff, 0xff, 0xff, 0xff, 0x00])
+        .deserialize()
+        .unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DeserializationError);
+
+    let zeros = vec![0; MAX_DECOMPRESSED_SIZE + 1];
+    for (id, compressed) in [
+        (Deflate::ID, Deflate::compress(&zeros)?),
+        (Lz4::ID, Lz4::compress(&zeros)?),
+        (Zstd::ID, Zstd::compress(&zeros)?),
+    ] {
+        let mut bin = vec![id];
+        bin.extend_from_slice(&compressed);
+        let e = S::new(bin).deserialize().unwrap_err();
+        assert_eq!(e.kind(), &ErrorKind::DeserializationError);
+    }
+
+    // not compressed in the first place
+    let e = Compressed::<BincodeSerializer<Vec<u8>>, Lz4>::serialize(&zeros).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::SerializationError);
+
+    Ok(())
+}
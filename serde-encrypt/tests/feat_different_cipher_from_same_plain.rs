@@ -8,24 +8,33 @@
 mod test_util;
 
 use serde::{Deserialize, Serialize};
+use serde_encrypt::aead::XChaCha20Poly1305;
+use serde_encrypt::public_key_backend::ChaChaBox;
 use serde_encrypt::serialize::impls::BincodeSerializer;
 use serde_encrypt::shared_key::SharedKey;
 use serde_encrypt::traits::SerdeEncryptPublicKey;
 use serde_encrypt::traits::SerdeEncryptSharedKey;
 use serde_encrypt::AsSharedKey;
+use test_util::assert_no_duplicate;
 use test_util::serde_encrypt_public_key::*;
-use test_util::*;
 
 #[derive(PartialEq, Debug, Serialize, Deserialize)]
 struct Message(String);
 
+impl SerdeEncryptPublicKey for Message {
+    type S = BincodeSerializer<Self>;
+    type B = ChaChaBox;
+}
+
+impl SerdeEncryptSharedKey for Message {
+    type S = BincodeSerializer<Self>;
+    type A = XChaCha20Poly1305;
+}
+
 #[test]
 fn test_serde_encrypt_public_key_in_a_process() {
     combined_keys_gen!(sender_combined_key, _x);
 
-    impl SerdeEncryptPublicKey for Message {
-        type S = BincodeSerializer<Self>;
-    }
     assert_no_duplicate(
         || {
             let msg = Message("same message".into());
@@ -40,9 +49,6 @@ fn test_serde_encrypt_public_key_in_a_process() {
 fn test_serde_encrypt_shared_key_in_a_process() {
     let shared_key = SharedKey::generate();
 
-    impl SerdeEncryptSharedKey for Message {
-        type S = BincodeSerializer<Self>;
-    }
     assert_no_duplicate(
         || {
             let msg = Message("same message".into());
@@ -0,0 +1,89 @@
+//! Test if `Encrypted<T>`:
+//!
+//! - seals / opens typed value.
+//! - can be stored inside other serde structs in both human-readable and binary formats.
+
+#![cfg(feature = "serde")]
+
+use serde::{Deserialize, Serialize};
+use serde_encrypt::{
+    aead::XChaCha20Poly1305, serialize::impls::BincodeSerializer, shared_key::SharedKey,
+    traits::SerdeEncryptSharedKey, AsSharedKey, Encrypted, Error, ErrorKind,
+};
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct Payment {
+    amount: u64,
+    card: String,
+}
+
+impl SerdeEncryptSharedKey for Payment {
+    type S = BincodeSerializer<Self>;
+    type A = XChaCha20Poly1305;
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct Row {
+    id: u32,
+    payment: Encrypted<Payment>,
+}
+
+fn payment() -> Payment {
+    Payment {
+        amount: 100,
+        card: "4242-4242-4242-4242".into(),
+    }
+}
+
+#[test]
+fn test_seal_open() -> Result<(), Error> {
+    let shared_key = SharedKey::generate();
+
+    let sealed = Encrypted::seal(&payment(), &shared_key)?;
+    assert_eq!(sealed.open(&shared_key)?, payment());
+
+    let e = sealed.open(&SharedKey::generate()).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+
+    Ok(())
+}
+
+#[test]
+fn test_nested_in_human_readable_format() -> Result<(), Error> {
+    let shared_key = SharedKey::generate();
+    let row = Row {
+        id: 1,
+        payment: Encrypted::seal(&payment(), &shared_key)?,
+    };
+
+    let json = serde_json::to_string(&row).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert!(value["payment"].is_string());
+    assert!(!json.contains("4242"));
+
+    let decoded: Row = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, row);
+    assert_eq!(decoded.payment.open(&shared_key)?, payment());
+
+    Ok(())
+}
+
+#[test]
+fn test_nested_in_binary_format() -> Result<(), Error> {
+    let shared_key = SharedKey::generate();
+    let row = Row {
+        id: 1,
+        payment: Encrypted::seal(&payment(), &shared_key)?,
+    };
+
+    let bin = bincode::serialize(&row).unwrap();
+    let decoded: Row = bincode::deserialize(&bin).unwrap();
+    assert_eq!(decoded.payment.open(&shared_key)?, payment());
+
+    let mut bin = Vec::new();
+    ciborium::ser::into_writer(&row, &mut bin).unwrap();
+    let decoded: Row = ciborium::de::from_reader(bin.as_slice()).unwrap();
+    assert_eq!(decoded.payment.open(&shared_key)?, payment());
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+//! Test if EncryptedMessage implements `Serialize` / `Deserialize`:
+//!
+//! - base64 string in human-readable formats.
+//! - bytes in binary formats.
+
+#![cfg(feature = "serde")]
+
+use serde::{Deserialize, Serialize};
+use serde_encrypt::{
+    aead::XChaCha20Poly1305, serialize::impls::BincodeSerializer, shared_key::SharedKey,
+    traits::SerdeEncryptSharedKey, AsSharedKey, EncryptedMessage, Error,
+};
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct Message(String);
+
+impl SerdeEncryptSharedKey for Message {
+    type S = BincodeSerializer<Self>;
+    type A = XChaCha20Poly1305;
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct Envelope {
+    to: String,
+    body: EncryptedMessage,
+}
+
+fn envelope(shared_key: &SharedKey) -> Result<Envelope, Error> {
+    Ok(Envelope {
+        to: "Bob".into(),
+        body: Message("I ❤️ you.".into()).encrypt(shared_key)?,
+    })
+}
+
+#[test]
+fn test_json() -> Result<(), Error> {
+    let shared_key = SharedKey::generate();
+    let envelope = envelope(&shared_key)?;
+
+    let json = serde_json::to_string(&envelope).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let body = value["body"].as_str().expect("base64 string");
+    assert!(body
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '/' || c == '='));
+
+    let deserialized: Envelope = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized, envelope);
+    assert_eq!(
+        Message::decrypt_owned(&deserialized.body, &shared_key)?,
+        Message("I ❤️ you.".into())
+    );
+    Ok(())
+}
+
+#[test]
+fn test_binary_formats() -> Result<(), Error> {
+    let shared_key = SharedKey::generate();
+    let envelope = envelope(&shared_key)?;
+    let versioned = envelope.body.clone().serialize_versioned();
+
+    let bin = bincode::serialize(&envelope).unwrap();
+    assert!(bin.len() < versioned.len() * 2, "bytes, not base64");
+    let deserialized: Envelope = bincode::deserialize(&bin).unwrap();
+    assert_eq!(deserialized, envelope);
+
+    let bin = postcard::to_allocvec(&envelope).unwrap();
+    let deserialized: Envelope = postcard::from_bytes(&bin).unwrap();
+    assert_eq!(deserialized, envelope);
+
+    let mut bin = Vec::new();
+    ciborium::ser::into_writer(&envelope, &mut bin).unwrap();
+    let deserialized: Envelope = ciborium::de::from_reader(bin.as_slice()).unwrap();
+    assert_eq!(deserialized, envelope);
+    Ok(())
+}
+
+#[test]
+fn test_headerless_message() {
+    let headerless = EncryptedMessage::deserialize(vec![42u8; 40]).unwrap();
+    let json = serde_json::to_string(&headerless).unwrap();
+    let deserialized: EncryptedMessage = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized, headerless);
+    assert_eq!(deserialized.header(), None);
+}
+
+#[test]
+fn test_invalid_base64() {
+    let e = serde_json::from_str::<EncryptedMessage>("\"not base64!\"").unwrap_err();
+    assert!(e.to_string().contains("base64"));
+}
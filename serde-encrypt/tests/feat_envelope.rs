@@ -0,0 +1,172 @@
+//! Test if SerdeEncryptEnvelope:
+//!
+//! - encrypts each message by a fresh DEK wrapped by KEK (shared key, public key or external KMS),
+//! - stores wrapped DEK next to cipher-text and survives serialization,
+//! - is decrypted only by the corresponding KEK (and the same AAD).
+
+mod test_util;
+
+use serde::{Deserialize, Serialize};
+use serde_encrypt::{
+    aead::Aes256Gcm,
+    envelope::{EnvelopeEncrypted, KeyDecryptionKey, KeyEncryptionKey},
+    key::key_pair::ReceiverKeyPair,
+    serialize::impls::BincodeSerializer,
+    shared_key::SharedKey,
+    traits::SerdeEncryptEnvelope,
+    Algorithm, AsSharedKey, Error, ErrorKind, ReceiverKeyPairCore,
+};
+use test_util::mock_kms::MockKms;
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct Dataset {
+    name: String,
+    rows: Vec<u64>,
+}
+impl SerdeEncryptEnvelope for Dataset {
+    type S = BincodeSerializer<Self>;
+    type A = Aes256Gcm;
+}
+
+fn dataset() -> Dataset {
+    Dataset {
+        name: "sales".into(),
+        rows: (0..1000).collect(),
+    }
+}
+
+#[test]
+fn test_shared_key_kek() -> Result<(), Error> {
+    let kek = SharedKey::generate();
+
+    let enc = dataset().encrypt(&kek)?;
+    assert_eq!(
+        enc.encrypted_message().header().unwrap().algorithm(),
+        Algorithm::Aes256Gcm
+    );
+    assert_eq!(Dataset::decrypt_owned(&enc, &kek)?, dataset());
+
+    // DEK is not KEK
+    let dek = kek.unwrap_key(enc.wrapped_dek())?;
+    assert_ne!(dek, kek);
+    Ok(())
+}
+
+#[test]
+fn test_public_key_kek() -> Result<(), Error> {
+    let receiver_key_pair = ReceiverKeyPair::generate();
+
+    let enc = dataset().encrypt(receiver_key_pair.public_key())?;
+    assert_eq!(Dataset::decrypt_owned(&enc, &receiver_key_pair)?, dataset());
+
+    let other_key_pair = ReceiverKeyPair::generate();
+    let e = Dataset::decrypt_owned(&enc, &other_key_pair).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+    Ok(())
+}
+
+#[test]
+fn test_mock_kms_kek() -> Result<(), Error> {
+    let mut kms = MockKms::new();
+    kms.create_key("alias/datasets");
+    kms.create_key("alias/other");
+
+    let enc = dataset().encrypt(&kms.key("alias/datasets"))?;
+    let enc2 = dataset().encrypt(&kms.key("alias/other"))?;
+    assert_eq!(kms.n_wrap(), 2);
+
+    // KMS chooses master key from wrapped DEK
+    assert_eq!(Dataset::decrypt_owned(&enc, &kms)?, dataset());
+    assert_eq!(Dataset::decrypt_owned(&enc2, &kms)?, dataset());
+    assert_eq!(kms.n_unwrap(), 2);
+
+    let other_kms = MockKms::new();
+    let e = Dataset::decrypt_owned(&enc, &other_kms).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+    Ok(())
+}
+
+#[test]
+fn test_disabled_kms_key() -> Result<(), Error> {
+    let mut kms = MockKms::new();
+    kms.create_key("alias/datasets");
+    let enc = dataset().encrypt(&kms.key("alias/datasets"))?;
+
+    kms.disable_key("alias/datasets");
+    let e = Dataset::decrypt_owned(&enc, &kms).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+    let e = dataset().encrypt(&kms.key("alias/datasets")).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::EncryptionError);
+    let e = dataset().encrypt(&kms.key("alias/unknown")).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::EncryptionError);
+    Ok(())
+}
+
+#[test]
+fn test_fresh_dek_per_message() -> Result<(), Error> {
+    let kek = SharedKey::generate();
+
+    let enc1 = dataset().encrypt(&kek)?;
+    let enc2 = dataset().encrypt(&kek)?;
+    assert_ne!(enc1.wrapped_dek(), enc2.wrapped_dek());
+    assert_ne!(
+        kek.unwrap_key(enc1.wrapped_dek())?,
+        kek.unwrap_key(enc2.wrapped_dek())?
+    );
+    assert_ne!(enc1.encrypted_message(), enc2.encrypted_message());
+    Ok(())
+}
+
+#[test]
+fn test_serialize_envelope() -> Result<(), Error> {
+    let kek = SharedKey::generate();
+    let enc = dataset().encrypt(&kek)?;
+
+    let bin = enc.clone().serialize()?;
+    let deserialized = EnvelopeEncrypted::deserialize(bin.clone())?;
+    assert_eq!(deserialized, enc);
+    assert_eq!(Dataset::decrypt_owned(&deserialized, &kek)?, dataset());
+
+    let e = EnvelopeEncrypted::deserialize(bin[..3].to_vec()).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DeserializationError);
+    let e = EnvelopeEncrypted::deserialize(bin[..10].to_vec()).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DeserializationError);
+    Ok(())
+}
+
+#[test]
+fn test_wrong_kek_and_aad() -> Result<(), Error> {
+    let kek = SharedKey::generate();
+    let enc = dataset().encrypt_with_aad(&kek, b"dataset-id=42")?;
+
+    assert_eq!(
+        Dataset::decrypt_owned_with_aad(&enc, &kek, b"dataset-id=42")?,
+        dataset()
+    );
+
+    let e = Dataset::decrypt_owned_with_aad(&enc, &kek, b"dataset-id=43").unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+    let e = Dataset::decrypt_owned(&enc, &kek).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+
+    let other_kek = SharedKey::generate();
+    let e = Dataset::decrypt_owned_with_aad(&enc, &other_kek, b"dataset-id=42").unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+    Ok(())
+}
+
+#[test]
+fn test_rewrap_without_reencryption() -> Result<(), Error> {
+    let old_kek = SharedKey::generate();
+    let new_kek = SharedKey::generate();
+    let enc = dataset().encrypt(&old_kek)?;
+
+    let dek = old_kek.unwrap_key(enc.wrapped_dek())?;
+    let rewrapped =
+        EnvelopeEncrypted::new(new_kek.wrap_key(&dek)?, enc.encrypted_message().clone());
+
+    assert_eq!(Dataset::decrypt_owned(&rewrapped, &new_kek)?, dataset());
+    let e = Dataset::decrypt_owned(&rewrapped, &old_kek).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+    Ok(())
+}
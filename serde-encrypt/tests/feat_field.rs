@@ -0,0 +1,98 @@
+//! Test if `#[serde(with = "serde_encrypt::field::shared_key")]`:
+//!
+//! - encrypts only the annotated field.
+//! - reads shared key from per-scope / per-thread context.
+
+use serde::{Deserialize, Serialize};
+use serde_encrypt::{
+    aead::XChaCha20Poly1305, field, serialize::impls::BincodeSerializer, shared_key::SharedKey,
+    traits::SerdeEncryptSharedKey, AsSharedKey,
+};
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct Ssn(String);
+
+impl SerdeEncryptSharedKey for Ssn {
+    type S = BincodeSerializer<Self>;
+    type A = XChaCha20Poly1305;
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct User {
+    name: String,
+    #[serde(with = "serde_encrypt::field::shared_key")]
+    ssn: Ssn,
+}
+
+fn user() -> User {
+    User {
+        name: "Alice".into(),
+        ssn: Ssn("123-45-6789".into()),
+    }
+}
+
+#[test]
+fn test_field_encryption_with_scope_key() {
+    let shared_key = SharedKey::generate();
+    let user = user();
+
+    let json = field::with_shared_key(&shared_key, || serde_json::to_string(&user)).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["name"], "Alice");
+    assert!(value["ssn"].is_string());
+    assert!(!json.contains("123-45-6789"));
+
+    let decoded: User =
+        field::with_shared_key(&shared_key, || serde_json::from_str(&json)).unwrap();
+    assert_eq!(decoded, user);
+}
+
+#[test]
+fn test_field_encryption_with_thread_key() {
+    let shared_key = SharedKey::generate();
+    let user = user();
+
+    field::set_shared_key(shared_key);
+    let json = serde_json::to_string(&user).unwrap();
+    let decoded: User = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, user);
+
+    // another thread does not see the key
+    std::thread::spawn(move || {
+        assert!(serde_json::from_str::<User>(&json).is_err());
+    })
+    .join()
+    .unwrap();
+
+    field::clear_shared_key();
+    assert!(serde_json::to_string(&user).is_err());
+}
+
+#[test]
+fn test_field_encryption_error_without_key() {
+    assert!(serde_json::to_string(&user()).is_err());
+}
+
+#[test]
+fn test_field_decryption_error_with_wrong_key() {
+    let key1 = SharedKey::generate();
+    let key2 = SharedKey::generate();
+
+    let json = field::with_shared_key(&key1, || serde_json::to_string(&user())).unwrap();
+    assert!(field::with_shared_key(&key2, || serde_json::from_str::<User>(&json)).is_err());
+}
+
+#[test]
+fn test_nested_scope_restores_key() {
+    let outer = SharedKey::generate();
+    let inner = SharedKey::generate();
+    let user = user();
+
+    field::with_shared_key(&outer, || {
+        let inner_json = field::with_shared_key(&inner, || serde_json::to_string(&user)).unwrap();
+        assert!(serde_json::from_str::<User>(&inner_json).is_err());
+
+        let outer_json = serde_json::to_string(&user).unwrap();
+        assert_eq!(serde_json::from_str::<User>(&outer_json).unwrap(), user);
+    });
+}
@@ -0,0 +1,168 @@
+//! Test if KeyRing:
+//!
+//! - encrypts with the primary key and records its key ID in message header,
+//! - decrypts with the key chosen by key ID after rotation,
+//! - re-encrypts old messages under the new primary key,
+//! - binds associated data (AAD) to messages,
+//! - needs versioned binary to keep key ID.
+
+use serde::{Deserialize, Serialize};
+use serde_encrypt::{
+    aead::Aes256Gcm, key_ring::KeyRing, serialize::impls::BincodeSerializer, shared_key::SharedKey,
+    traits::SerdeEncryptSharedKey, AsSharedKey, EncryptedMessage, Error, ErrorKind,
+};
+use serde_encrypt_core::encrypt::encrypted_message::FORMAT_VERSION_WITH_KEY_ID;
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct Record {
+    id: u64,
+    ssn: String,
+}
+impl SerdeEncryptSharedKey for Record {
+    type S = BincodeSerializer<Self>;
+    type A = Aes256Gcm;
+}
+
+fn record() -> Record {
+    Record {
+        id: 1,
+        ssn: "123-45-6789".into(),
+    }
+}
+
+fn key_id_of(encrypted_message: &EncryptedMessage) -> Option<u32> {
+    encrypted_message.header().and_then(|h| h.key_id())
+}
+
+#[test]
+fn test_key_ring() -> Result<(), Error> {
+    let key_ring = KeyRing::new(1, SharedKey::generate());
+
+    let enc = record().encrypt_with_key_ring(&key_ring)?;
+    assert_eq!(key_id_of(&enc), Some(1));
+
+    let bin = enc.serialize_versioned();
+    let enc = EncryptedMessage::deserialize_versioned(bin)?;
+    assert_eq!(enc.version(), FORMAT_VERSION_WITH_KEY_ID);
+    assert_eq!(key_id_of(&enc), Some(1));
+
+    assert_eq!(
+        Record::decrypt_owned_with_key_ring(&enc, &key_ring)?,
+        record()
+    );
+    assert_eq!(
+        Record::decrypt_owned(&enc, key_ring.primary_key())?,
+        record()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_rotation_and_reencryption() -> Result<(), Error> {
+    let mut key_ring = KeyRing::new(1, SharedKey::generate());
+    let old_enc = record().encrypt_with_key_ring(&key_ring)?;
+
+    key_ring.rotate(2, SharedKey::generate())?;
+    assert_eq!(key_ring.primary_key_id(), 2);
+    assert_eq!(key_ring.key_ids().collect::<Vec<_>>(), vec![1, 2]);
+
+    let new_enc = record().encrypt_with_key_ring(&key_ring)?;
+    assert_eq!(key_id_of(&new_enc), Some(2));
+
+    // both old and new messages are decrypted
+    assert_eq!(
+        Record::decrypt_owned_with_key_ring(&old_enc, &key_ring)?,
+        record()
+    );
+    assert_eq!(
+        Record::decrypt_owned_with_key_ring(&new_enc, &key_ring)?,
+        record()
+    );
+
+    let reenc = Record::reencrypt_with_key_ring(&old_enc, &key_ring, &[])?;
+    assert_eq!(key_id_of(&reenc), Some(2));
+
+    // old key is no longer needed after re-encryption
+    assert!(key_ring.remove(1).is_some());
+    assert_eq!(
+        Record::decrypt_owned_with_key_ring(&reenc, &key_ring)?,
+        record()
+    );
+    let e = Record::decrypt_owned_with_key_ring(&old_enc, &key_ring).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+    Ok(())
+}
+
+#[test]
+fn test_key_ring_with_aad() -> Result<(), Error> {
+    let mut key_ring = KeyRing::new(1, SharedKey::generate());
+    let aad = b"records/1";
+
+    let enc = record().encrypt_with_key_ring_with_aad(&key_ring, aad)?;
+    assert_eq!(
+        Record::decrypt_owned_with_key_ring_with_aad(&enc, &key_ring, aad)?,
+        record()
+    );
+    let e = Record::decrypt_owned_with_key_ring(&enc, &key_ring).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+
+    key_ring.rotate(2, SharedKey::generate())?;
+    let e = Record::reencrypt_with_key_ring(&enc, &key_ring, b"records/2").unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+
+    let reenc = Record::reencrypt_with_key_ring(&enc, &key_ring, aad)?;
+    assert_eq!(key_id_of(&reenc), Some(2));
+    assert_eq!(
+        Record::decrypt_owned_with_key_ring_with_aad(&reenc, &key_ring, aad)?,
+        record()
+    );
+    let e = Record::decrypt_owned_with_key_ring(&reenc, &key_ring).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+    Ok(())
+}
+
+#[test]
+fn test_headerless_binary_loses_key_id() -> Result<(), Error> {
+    let key_ring = KeyRing::new(1, SharedKey::generate());
+
+    let bin = record().encrypt_with_key_ring(&key_ring)?.serialize();
+    let enc = EncryptedMessage::deserialize_versioned(bin)?;
+    assert_eq!(key_id_of(&enc), None);
+    let e = Record::decrypt_owned_with_key_ring(&enc, &key_ring).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+    Ok(())
+}
+
+#[test]
+fn test_primary_key_is_kept() -> Result<(), Error> {
+    let mut key_ring = KeyRing::new(1, SharedKey::generate());
+    key_ring.insert(0, SharedKey::generate())?;
+
+    assert!(key_ring.remove(1).is_none());
+    assert_eq!(key_ring.primary_key_id(), 1);
+
+    let e = key_ring.insert(1, SharedKey::generate()).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::KeyError);
+    let e = key_ring.rotate(0, SharedKey::generate()).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::KeyError);
+    assert_eq!(key_ring.primary_key_id(), 1);
+    Ok(())
+}
+
+#[test]
+fn test_decryption_error_without_key_id() -> Result<(), Error> {
+    let key_ring = KeyRing::new(1, SharedKey::generate());
+
+    let enc = record().encrypt(key_ring.primary_key())?;
+    assert_eq!(key_id_of(&enc), None);
+    let e = Record::decrypt_owned_with_key_ring(&enc, &key_ring).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+
+    let other_key_ring = KeyRing::new(7, SharedKey::generate());
+    let enc = record().encrypt_with_key_ring(&other_key_ring)?;
+    let e = Record::decrypt_owned_with_key_ring(&enc, &key_ring).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+    let e = Record::reencrypt_with_key_ring(&enc, &key_ring, &[]).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+    Ok(())
+}
@@ -8,25 +8,33 @@
 mod test_util;
 
 use serde::{Deserialize, Serialize};
+use serde_encrypt::aead::XChaCha20Poly1305;
+use serde_encrypt::public_key_backend::ChaChaBox;
 use serde_encrypt::serialize::impls::BincodeSerializer;
 use serde_encrypt::shared_key::SharedKey;
 use serde_encrypt::traits::{SerdeEncryptPublicKey, SerdeEncryptSharedKey};
 use serde_encrypt::AsSharedKey;
-use test_util::*;
+use test_util::{serde_encrypt_public_key::*, serde_encrypt_shared_key::*};
 
 const SIZE: usize = 1_000_000;
 
 #[derive(PartialEq, Debug, Serialize, Deserialize)]
 struct Message(Vec<u8>);
 
+impl SerdeEncryptPublicKey for Message {
+    type S = BincodeSerializer<Self>;
+    type B = ChaChaBox;
+}
+
+impl SerdeEncryptSharedKey for Message {
+    type S = BincodeSerializer<Self>;
+    type A = XChaCha20Poly1305;
+}
+
 #[test]
 fn test_serde_encrypt_public_key_large_message() {
     combined_keys_gen!(sender_combined_key, receiver_combined_key);
 
-    impl SerdeEncryptPublicKey for Message {
-        type S = BincodeSerializer<Self>;
-    }
-
     let msg = Message(vec![42u8; SIZE]);
     public_key_enc_dec_assert_eq(&msg, &sender_combined_key, &receiver_combined_key).unwrap();
 }
@@ -35,10 +43,6 @@ fn test_serde_encrypt_public_key_large_message() {
 fn test_serde_encrypt_shared_key_large_message() {
     let shared_key = SharedKey::generate();
 
-    impl SerdeEncryptSharedKey for Message {
-        type S = BincodeSerializer<Self>;
-    }
-
     let msg = Message(vec![42u8; SIZE]);
     shared_key_enc_dec_assert_eq(&msg, &shared_key).unwrap();
 }
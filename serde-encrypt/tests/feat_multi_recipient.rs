@@ -0,0 +1,162 @@
+//! Test if SerdeEncryptMultiRecipient:
+//!
+//! - is decrypted by each of listed receivers,
+//! - is not decrypted by unlisted receiver or with different sender public key,
+//! - detects tampered recipient table.
+
+use serde::{Deserialize, Serialize};
+use serde_encrypt::{
+    key::key_pair::{ReceiverKeyPair, SenderKeyPair},
+    serialize::impls::BincodeSerializer,
+    traits::SerdeEncryptMultiRecipient,
+    EncryptedMessage, EncryptionMode, Error, ErrorKind, ReceiverCombinedKey, ReceiverKeyPairCore,
+    SenderKeyPairCore,
+};
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct Alert {
+    service: String,
+    severity: u8,
+}
+
+impl SerdeEncryptMultiRecipient for Alert {
+    type S = BincodeSerializer<Self>;
+}
+
+fn alert() -> Alert {
+    Alert {
+        service: "payment".into(),
+        severity: 1,
+    }
+}
+
+#[test]
+fn test_multi_recipient() -> Result<(), Error> {
+    let sender_key_pair = SenderKeyPair::generate();
+    let receiver_key_pairs: Vec<ReceiverKeyPair> =
+        (0..5).map(|_| ReceiverKeyPair::generate()).collect();
+    let receiver_public_keys: Vec<_> = receiver_key_pairs.iter().map(|k| k.public_key()).collect();
+
+    let enc = alert().encrypt(&sender_key_pair, &receiver_public_keys)?;
+    assert_eq!(enc.header().unwrap().mode(), EncryptionMode::MultiRecipient);
+
+    let bin = enc.serialize_versioned();
+    let enc = EncryptedMessage::deserialize_versioned(bin)?;
+
+    for receiver_key_pair in &receiver_key_pairs {
+        let combined_key = ReceiverCombinedKey::new(
+            sender_key_pair.public_key(),
+            receiver_key_pair.private_key(),
+        );
+        let dec = Alert::decrypt_owned(&enc, &combined_key)?;
+        assert_eq!(dec, alert());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_multi_recipient_aad() -> Result<(), Error> {
+    let sender_key_pair = SenderKeyPair::generate();
+    let receiver_key_pair = ReceiverKeyPair::generate();
+    let combined_key = ReceiverCombinedKey::new(
+        sender_key_pair.public_key(),
+        receiver_key_pair.private_key(),
+    );
+
+    let enc = alert().encrypt_with_aad(
+        &sender_key_pair,
+        &[receiver_key_pair.public_key()],
+        b"incident:1",
+    )?;
+    let dec = Alert::decrypt_owned_with_aad(&enc, &combined_key, b"incident:1")?;
+    assert_eq!(dec, alert());
+
+    let e = Alert::decrypt_owned_with_aad(&enc, &combined_key, b"incident:2").unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+
+    Ok(())
+}
+
+#[test]
+fn test_decryption_error_on_unlisted_receiver_or_other_sender() -> Result<(), Error> {
+    let sender_key_pair = SenderKeyPair::generate();
+    let other_sender_key_pair = SenderKeyPair::generate();
+    let receiver_key_pair = ReceiverKeyPair::generate();
+    let unlisted_key_pair = ReceiverKeyPair::generate();
+
+    let enc = alert().encrypt(&sender_key_pair, &[receiver_key_pair.public_key()])?;
+
+    let combined_key = ReceiverCombinedKey::new(
+        sender_key_pair.public_key(),
+        unlisted_key_pair.private_key(),
+    );
+    let e = Alert::decrypt_owned(&enc, &combined_key).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+
+    let combined_key = ReceiverCombinedKey::new(
+        other_sender_key_pair.public_key(),
+        receiver_key_pair.private_key(),
+    );
+    let e = Alert::decrypt_owned(&enc, &combined_key).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+
+    Ok(())
+}
+
+#[test]
+fn test_duplicate_receivers() -> Result<(), Error> {
+    let sender_key_pair = SenderKeyPair::generate();
+    let receiver_key_pair = ReceiverKeyPair::generate();
+    let pk = receiver_key_pair.public_key();
+
+    let enc_single = alert().encrypt(&sender_key_pair, &[pk])?;
+    let enc_dup = alert().encrypt(&sender_key_pair, &[pk, pk, pk])?;
+    assert_eq!(enc_single.len(), enc_dup.len());
+
+    let e = alert().encrypt(&sender_key_pair, &[]).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::EncryptionError);
+
+    Ok(())
+}
+
+#[test]
+fn test_decryption_error_on_tampered_recipient_table() -> Result<(), Error> {
+    let sender_key_pair = SenderKeyPair::generate();
+    let receiver_key_pair = ReceiverKeyPair::generate();
+    let other_key_pair = ReceiverKeyPair::generate();
+    let combined_key = ReceiverCombinedKey::new(
+        sender_key_pair.public_key(),
+        receiver_key_pair.private_key(),
+    );
+
+    let enc = alert().encrypt(
+        &sender_key_pair,
+        &[receiver_key_pair.public_key(), other_key_pair.public_key()],
+    )?;
+    let bin = enc.serialize();
+
+    // headerless binary: nonce (24 bytes) || recipient count (4 bytes) || recipient entries (56 bytes each) || cipher-text
+
+    // drop either entry from recipient table
+    for i in 0..2 {
+        let entry_start = 24 + 4 + i * 56;
+        let mut removed = bin[..24].to_vec();
+        removed.extend_from_slice(&1u32.to_be_bytes());
+        removed.extend_from_slice(&bin[entry_start..entry_start + 56]);
+        removed.extend_from_slice(&bin[24 + 4 + 2 * 56..]);
+
+        let enc = EncryptedMessage::deserialize(removed)?;
+        let e = Alert::decrypt_owned(&enc, &combined_key).unwrap_err();
+        assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+    }
+
+    // recipient count larger than the table
+    let mut truncated = bin;
+    truncated[24..28].copy_from_slice(&u32::MAX.to_be_bytes());
+    let enc = EncryptedMessage::deserialize(truncated)?;
+    let e = Alert::decrypt_owned(&enc, &combined_key).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+
+    Ok(())
+}
@@ -9,6 +9,8 @@ mod test_util;
 use alloc::{string::String, vec, vec::Vec};
 use serde::{Deserialize, Serialize};
 use serde_encrypt::{
+    aead::XChaCha20Poly1305,
+    public_key_backend::ChaChaBox,
     serialize::impls::PostcardSerializer,
     shared_key::SharedKey,
     traits::{SerdeEncryptPublicKey, SerdeEncryptSharedKey},
@@ -16,6 +18,11 @@ use serde_encrypt::{
 };
 use test_util::{serde_encrypt_public_key::*, serde_encrypt_shared_key::*};
 
+#[cfg(feature = "ciborium")]
+use serde_encrypt::serialize::impls::CiboriumSerializer;
+#[cfg(feature = "json")]
+use serde_encrypt::serialize::impls::JsonSerializer;
+
 #[derive(PartialEq, Debug, Serialize, Deserialize)]
 struct Pagination {
     limit: u64,
@@ -37,14 +44,20 @@ struct Users {
     pagination: Pagination,
 }
 
+impl SerdeEncryptPublicKey for Users {
+    type S = PostcardSerializer<Self>;
+    type B = ChaChaBox;
+}
+
+impl SerdeEncryptSharedKey for Users {
+    type S = PostcardSerializer<Self>;
+    type A = XChaCha20Poly1305;
+}
+
 #[test]
 fn test_serde_encrypt_public_key_nostd() -> Result<(), Error> {
     combined_keys_gen!(sender_combined_key, receiver_combined_key);
 
-    impl SerdeEncryptPublicKey for Users {
-        type S = PostcardSerializer<Self>;
-    }
-
     let msg = Users {
         users: vec![
             User {
@@ -70,10 +83,6 @@ fn test_serde_encrypt_public_key_nostd() -> Result<(), Error> {
 fn test_serde_encrypt_shared_key_nostd() -> Result<(), Error> {
     let shared_key = SharedKey::generate();
 
-    impl SerdeEncryptSharedKey for Users {
-        type S = PostcardSerializer<Self>;
-    }
-
     let msg = Users {
         users: vec![
             User {
@@ -94,3 +103,67 @@ fn test_serde_encrypt_shared_key_nostd() -> Result<(), Error> {
     shared_key_enc_dec_assert_eq(&msg, &shared_key)?;
     Ok(())
 }
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_serializer_nostd() -> Result<(), Error> {
+    let shared_key = SharedKey::generate();
+
+    #[derive(PartialEq, Debug, Serialize, Deserialize)]
+    struct FlattenUsers {
+        users: Vec<User>,
+
+        #[serde(flatten)]
+        pagination: Pagination,
+    }
+    impl SerdeEncryptSharedKey for FlattenUsers {
+        type S = JsonSerializer<Self>;
+        type A = XChaCha20Poly1305;
+    }
+
+    let msg = FlattenUsers {
+        users: vec![User {
+            id: "1".into(),
+            username: "John".into(),
+        }],
+        pagination: Pagination {
+            limit: 100,
+            offset: 200,
+            total: 256,
+        },
+    };
+    shared_key_enc_dec_assert_eq(&msg, &shared_key)?;
+    Ok(())
+}
+
+#[cfg(feature = "ciborium")]
+#[test]
+fn test_ciborium_serializer_nostd() -> Result<(), Error> {
+    let shared_key = SharedKey::generate();
+
+    #[derive(PartialEq, Debug, Serialize, Deserialize)]
+    struct FlattenUsers {
+        users: Vec<User>,
+
+        #[serde(flatten)]
+        pagination: Pagination,
+    }
+    impl SerdeEncryptSharedKey for FlattenUsers {
+        type S = CiboriumSerializer<Self>;
+        type A = XChaCha20Poly1305;
+    }
+
+    let msg = FlattenUsers {
+        users: vec![User {
+            id: "1".into(),
+            username: "John".into(),
+        }],
+        pagination: Pagination {
+            limit: 100,
+            offset: 200,
+            total: 256,
+        },
+    };
+    shared_key_enc_dec_assert_eq(&msg, &shared_key)?;
+    Ok(())
+}
@@ -0,0 +1,187 @@
+//! Test if Padded serializer:
+//!
+//! - makes enum variants of different sizes encrypt to the same length in all traits,
+//! - pads to chosen scheme (block multiple, power of two, Padmé),
+//! - is removed on decryption.
+
+use serde::{Deserialize, Serialize};
+use serde_encrypt::{
+    aead::XChaCha20Poly1305,
+    key::key_pair::{ReceiverKeyPair, SenderKeyPair},
+    public_key_backend::ChaChaBox,
+    serialize::{
+        impls::{BincodeSerializer, Padded},
+        padding::{Block, Padding, Padme, PowerOfTwo},
+        TypedSerialized,
+    },
+    shared_key::SharedKey,
+    traits::{SerdeEncryptPublicKey, SerdeEncryptSharedKey, SerdeEncryptSharedKeyDeterministic},
+    AsSharedKey, Error, ErrorKind, ReceiverCombinedKey, ReceiverKeyPairCore, SenderCombinedKey,
+    SenderKeyPairCore,
+};
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+enum Answer {
+    Yes,
+    No,
+    Maybe { reason: String },
+}
+
+impl SerdeEncryptSharedKey for Answer {
+    type S = Padded<BincodeSerializer<Self>, Block<32>>;
+    type A = XChaCha20Poly1305;
+}
+impl SerdeEncryptSharedKeyDeterministic for Answer {
+    type S = Padded<BincodeSerializer<Self>, Block<32>>;
+}
+impl SerdeEncryptPublicKey for Answer {
+    type S = Padded<BincodeSerializer<Self>, Block<32>>;
+    type B = ChaChaBox;
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+enum UnpaddedAnswer {
+    Yes,
+    No,
+    Maybe { reason: String },
+}
+impl SerdeEncryptSharedKey for UnpaddedAnswer {
+    type S = BincodeSerializer<Self>;
+    type A = XChaCha20Poly1305;
+}
+
+fn answers() -> Vec<Answer> {
+    vec![
+        Answer::Yes,
+        Answer::No,
+        Answer::Maybe {
+            reason: "depends".into(),
+        },
+    ]
+}
+
+fn assert_all_same<T: PartialEq + core::fmt::Debug>(vs: &[T]) {
+    for v in vs {
+        assert_eq!(v, &vs[0]);
+    }
+}
+
+#[test]
+fn test_unpadded_lengths_differ() -> Result<(), Error> {
+    let shared_key = SharedKey::generate();
+    let yes = UnpaddedAnswer::Yes.encrypt(&shared_key)?;
+    let maybe = UnpaddedAnswer::Maybe {
+        reason: "depends".into(),
+    }
+    .encrypt(&shared_key)?;
+    assert_ne!(yes.len(), maybe.len());
+    Ok(())
+}
+
+#[test]
+fn test_shared_key_same_length() -> Result<(), Error> {
+    let shared_key = SharedKey::generate();
+
+    let mut lens = vec![];
+    for answer in answers() {
+        let enc = SerdeEncryptSharedKey::encrypt(&answer, &shared_key)?;
+        lens.push(enc.len());
+        let dec = <Answer as SerdeEncryptSharedKey>::decrypt_owned(&enc, &shared_key)?;
+        assert_eq!(dec, answer);
+    }
+    assert_all_same(&lens);
+    Ok(())
+}
+
+#[test]
+fn test_shared_key_deterministic_same_length() -> Result<(), Error> {
+    let shared_key = SharedKey::generate();
+
+    let mut lens = vec![];
+    for answer in answers() {
+        let enc = SerdeEncryptSharedKeyDeterministic::encrypt(&answer, &shared_key)?;
+        lens.push(enc.len());
+        let dec = <Answer as SerdeEncryptSharedKeyDeterministic>::decrypt_owned(&enc, &shared_key)?;
+        assert_eq!(dec, answer);
+    }
+    assert_all_same(&lens);
+    Ok(())
+}
+
+#[test]
+fn test_public_key_same_length() -> Result<(), Error> {
+    let alice_key_pair = SenderKeyPair::generate();
+    let bob_key_pair = ReceiverKeyPair::generate();
+    let alice_combined_key =
+        SenderCombinedKey::new(alice_key_pair.private_key(), bob_key_pair.public_key());
+    let bob_combined_key =
+        ReceiverCombinedKey::new(alice_key_pair.public_key(), bob_key_pair.private_key());
+
+    let mut lens = vec![];
+    for answer in answers() {
+        let enc = SerdeEncryptPublicKey::encrypt(&answer, &alice_combined_key)?;
+        lens.push(enc.len());
+
+        let dec = <Answer as SerdeEncryptPublicKey>::decrypt_ref(&enc, &bob_combined_key)?;
+        assert_eq!(dec.deserialize()?, answer);
+    }
+    assert_all_same(&lens);
+    Ok(())
+}
+
+#[test]
+fn test_padding_schemes() -> Result<(), Error> {
+    fn padded_len<P: Padding>(msg: &str) -> Result<usize, Error> {
+        let padded = Padded::<BincodeSerializer<String>, P>::serialize(&msg.to_string())?;
+        Ok(padded.as_slice().len())
+    }
+
+    // bincode: 8-byte length prefix + bytes
+    let msg = "a".repeat(100);
+    assert_eq!(padded_len::<Block<64>>(&msg)?, 128);
+    assert_eq!(padded_len::<PowerOfTwo>(&msg)?, 128);
+    assert_eq!(padded_len::<Padme>(&msg)?, 112);
+
+    let msg = "a".repeat(1000);
+    assert_eq!(padded_len::<Block<64>>(&msg)?, 1024);
+    assert_eq!(padded_len::<PowerOfTwo>(&msg)?, 1024);
+    assert_eq!(padded_len::<Padme>(&msg)?, 1024);
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_padding() -> Result<(), Error> {
+    type S = Padded<BincodeSerializer<String>, Padme>;
+
+    let padded = S::serialize(&"hello".to_string())?;
+    let mut bin = padded.into_vec();
+    *bin.last_mut().unwrap() = 0x01;
+
+    let e = S::new(bin).deserialize().unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DeserializationError);
+    Ok(())
+}
+
+#[cfg(feature = "lz4")]
+#[test]
+fn test_padded_compressed() -> Result<(), Error> {
+    use serde_encrypt::serialize::{compress::Lz4, impls::Compressed};
+
+    #[derive(PartialEq, Debug, Serialize, Deserialize)]
+    struct Log(String);
+    impl SerdeEncryptSharedKey for Log {
+        type S = Padded<Compressed<BincodeSerializer<Self>, Lz4>, PowerOfTwo>;
+        type A = XChaCha20Poly1305;
+    }
+
+    let shared_key = SharedKey::generate();
+    let short = Log("a".repeat(100)).encrypt(&shared_key)?;
+    let long = Log("a".repeat(200)).encrypt(&shared_key)?;
+    assert_eq!(short.len(), long.len());
+    assert_eq!(
+        Log::decrypt_owned(&long, &shared_key)?,
+        Log("a".repeat(200))
+    );
+    Ok(())
+}
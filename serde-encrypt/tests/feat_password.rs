@@ -0,0 +1,157 @@
+//! Test if SerdeEncryptSharedKey with password-derived key:
+//!
+//! - decrypts only with password (salt and KDF parameters are stored in message),
+//! - works with both Argon2id and scrypt,
+//! - fails with DecryptionError on wrong password, tampered KDF parameters or KDF costs exceeding upper bounds.
+
+mod test_util;
+
+use serde::{Deserialize, Serialize};
+use serde_encrypt::{
+    aead::XChaCha20Poly1305, serialize::impls::BincodeSerializer, shared_key::SharedKey,
+    traits::SerdeEncryptSharedKey, AsSharedKey, EncryptedMessage, EncryptionMode, Error, ErrorKind,
+    PasswordKdf,
+};
+use test_util::{assert_no_duplicate, serde_encrypt_shared_key::*};
+
+// Cheap parameters to keep tests fast. Use `PasswordKdf::default()` or `PasswordKdf::scrypt()` in production.
+const ARGON2ID: PasswordKdf = PasswordKdf::Argon2id {
+    m_cost: 64,
+    t_cost: 1,
+    p_cost: 1,
+};
+const SCRYPT: PasswordKdf = PasswordKdf::Scrypt {
+    log_n: 4,
+    r: 8,
+    p: 1,
+};
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct Config {
+    api_token: String,
+    retries: u8,
+}
+impl SerdeEncryptSharedKey for Config {
+    type S = BincodeSerializer<Self>;
+    type A = XChaCha20Poly1305;
+}
+
+fn config() -> Config {
+    Config {
+        api_token: "s3cr3t".into(),
+        retries: 3,
+    }
+}
+
+#[test]
+fn test_encrypt_with_password() -> Result<(), Error> {
+    for kdf in [ARGON2ID, SCRYPT] {
+        let enc = config().encrypt_with_password(b"correct horse", &kdf)?;
+        assert_eq!(
+            enc.header().unwrap().mode(),
+            EncryptionMode::SharedKeyPassword
+        );
+
+        // stored as a file, for example
+        let bin = enc.serialize_versioned();
+        let enc = EncryptedMessage::deserialize_versioned(bin)?;
+
+        let dec = Config::decrypt_owned_with_password(&enc, b"correct horse")?;
+        assert_eq!(dec, config());
+    }
+    Ok(())
+}
+
+#[test]
+fn test_different_cipher_from_same_password() {
+    assert_no_duplicate(
+        || {
+            config()
+                .encrypt_with_password(b"correct horse", &ARGON2ID)
+                .unwrap()
+                .serialize_versioned()
+        },
+        10,
+    );
+}
+
+#[test]
+fn test_decryption_error_on_wrong_password() -> Result<(), Error> {
+    for kdf in [ARGON2ID, SCRYPT] {
+        let enc = config().encrypt_with_password(b"correct horse", &kdf)?;
+        let e = Config::decrypt_owned_with_password(&enc, b"battery staple").unwrap_err();
+        assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_decryption_error_on_tampered_kdf_parameters() -> Result<(), Error> {
+    let enc = config().encrypt_with_password(b"correct horse", &ARGON2ID)?;
+    let encrypted_len = enc.encrypted().len();
+    let mut bin = enc.serialize_versioned();
+
+    // lowest byte of t_cost in password key header at the head of `encrypted` part
+    let t_cost_pos = bin.len() - encrypted_len + 1 + 4 + 3;
+    bin[t_cost_pos] ^= 0x02;
+    let tampered = EncryptedMessage::deserialize_versioned(bin)?;
+
+    let e = Config::decrypt_owned_with_password(&tampered, b"correct horse").unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+    Ok(())
+}
+
+#[test]
+fn test_decryption_error_on_oversized_kdf_parameters() -> Result<(), Error> {
+    // (KDF, offset of the parameter in password key header, oversized value)
+    for (kdf, param_pos, oversized) in [(ARGON2ID, 1, u32::MAX), (SCRYPT, 1, 40)] {
+        let enc = config().encrypt_with_password(b"correct horse", &kdf)?;
+        let encrypted_len = enc.encrypted().len();
+        let mut bin = enc.serialize_versioned();
+
+        let pos = bin.len() - encrypted_len + param_pos;
+        bin[pos..pos + 4].copy_from_slice(&oversized.to_be_bytes());
+        let tampered = EncryptedMessage::deserialize_versioned(bin)?;
+
+        // fails without allocating 4 TiB (Argon2id) or 128 TiB (scrypt)
+        let e = Config::decrypt_owned_with_password(&tampered, b"correct horse").unwrap_err();
+        assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_decryption_error_on_mode_mismatch() -> Result<(), Error> {
+    let shared_key =
+        SharedKey::derive_from_password(b"correct horse", b"0123456789abcdef", &SCRYPT)?;
+
+    let enc = config().encrypt_with_password(b"correct horse", &SCRYPT)?;
+    let e = Config::decrypt_owned(&enc, &shared_key).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+
+    let enc = config().encrypt(&shared_key)?;
+    let e = Config::decrypt_owned_with_password(&enc, b"correct horse").unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+    Ok(())
+}
+
+#[test]
+fn test_shared_key_derive_from_password() -> Result<(), Error> {
+    let salt = b"0123456789abcdef";
+    let sender_key = SharedKey::derive_from_password(b"correct horse", salt, &ARGON2ID)?;
+    let receiver_key = SharedKey::derive_from_password(b"correct horse", salt, &ARGON2ID)?;
+    assert_eq!(sender_key, receiver_key);
+
+    shared_key_enc_dec_assert_eq(&config(), &sender_key)?;
+    let enc = config().encrypt(&sender_key)?;
+    assert_eq!(Config::decrypt_owned(&enc, &receiver_key)?, config());
+
+    assert_ne!(
+        sender_key,
+        SharedKey::derive_from_password(b"battery staple", salt, &ARGON2ID)?
+    );
+
+    let e = SharedKey::derive_from_password(b"correct horse", b"short", &ARGON2ID).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::KeyDerivationError);
+    Ok(())
+}
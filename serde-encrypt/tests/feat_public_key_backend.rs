@@ -0,0 +1,139 @@
+//! Test if SerdeEncryptPublicKey works with every backend chosen by `type B`.
+
+mod test_util;
+
+use serde::{Deserialize, Serialize};
+use serde_encrypt::{
+    public_key_backend::{ChaChaBox, HpkeAuth, HpkeBase},
+    serialize::impls::BincodeSerializer,
+    traits::SerdeEncryptPublicKey,
+    Algorithm, EncryptedMessage, Error, ErrorKind,
+};
+use test_util::{assert_no_duplicate, serde_encrypt_public_key::*};
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct ChaChaBoxMessage(String);
+impl SerdeEncryptPublicKey for ChaChaBoxMessage {
+    type S = BincodeSerializer<Self>;
+    type B = ChaChaBox;
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct HpkeBaseMessage(String);
+impl SerdeEncryptPublicKey for HpkeBaseMessage {
+    type S = BincodeSerializer<Self>;
+    type B = HpkeBase;
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct HpkeAuthMessage(String);
+impl SerdeEncryptPublicKey for HpkeAuthMessage {
+    type S = BincodeSerializer<Self>;
+    type B = HpkeAuth;
+}
+
+fn algorithm_of(encrypted_message: &EncryptedMessage) -> Algorithm {
+    encrypted_message
+        .header()
+        .expect("encrypted message has header")
+        .algorithm()
+}
+
+#[test]
+fn test_chacha_box() -> Result<(), Error> {
+    combined_keys_gen!(sender_combined_key, receiver_combined_key);
+    let msg = ChaChaBoxMessage("ChaChaBox".into());
+
+    public_key_enc_dec_assert_eq(&msg, &sender_combined_key, &receiver_combined_key)?;
+    assert_eq!(
+        algorithm_of(&msg.encrypt(&sender_combined_key)?),
+        Algorithm::XChaCha20Poly1305
+    );
+    Ok(())
+}
+
+#[test]
+fn test_hpke_base() -> Result<(), Error> {
+    combined_keys_gen!(sender_combined_key, receiver_combined_key);
+    let msg = HpkeBaseMessage("HPKE base mode".into());
+
+    public_key_enc_dec_assert_eq(&msg, &sender_combined_key, &receiver_combined_key)?;
+    assert_eq!(
+        algorithm_of(&msg.encrypt(&sender_combined_key)?),
+        Algorithm::HpkeBase
+    );
+    Ok(())
+}
+
+#[test]
+fn test_hpke_auth() -> Result<(), Error> {
+    combined_keys_gen!(sender_combined_key, receiver_combined_key);
+    let msg = HpkeAuthMessage("HPKE auth mode".into());
+
+    public_key_enc_dec_assert_eq(&msg, &sender_combined_key, &receiver_combined_key)?;
+    assert_eq!(
+        algorithm_of(&msg.encrypt(&sender_combined_key)?),
+        Algorithm::HpkeAuth
+    );
+    Ok(())
+}
+
+#[test]
+fn test_hpke_different_cipher_from_same_plain() -> Result<(), Error> {
+    combined_keys_gen!(sender_combined_key, _receiver_combined_key);
+    let msg = HpkeBaseMessage("same plain".into());
+
+    assert_no_duplicate(
+        || {
+            msg.encrypt(&sender_combined_key)
+                .unwrap()
+                .serialize_versioned()
+        },
+        100,
+    );
+    Ok(())
+}
+
+#[test]
+fn test_hpke_auth_fails_with_other_sender() -> Result<(), Error> {
+    let (sender_key_pair, receiver_key_pair) = gen_key_pairs();
+    let (impostor_key_pair, _) = gen_key_pairs();
+    let (sender_combined_key, _) = mk_combined_keys(&impostor_key_pair, &receiver_key_pair);
+    let (_, receiver_combined_key) = mk_combined_keys(&sender_key_pair, &receiver_key_pair);
+
+    let enc = HpkeAuthMessage("from impostor".into()).encrypt(&sender_combined_key)?;
+    let e = HpkeAuthMessage::decrypt_owned(&enc, &receiver_combined_key).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+    Ok(())
+}
+
+#[test]
+fn test_hpke_aad() -> Result<(), Error> {
+    combined_keys_gen!(sender_combined_key, receiver_combined_key);
+    let msg = HpkeAuthMessage("bound to AAD".into());
+
+    let enc = msg.encrypt_with_aad(&sender_combined_key, b"seq:1")?;
+    let dec = HpkeAuthMessage::decrypt_owned_with_aad(&enc, &receiver_combined_key, b"seq:1")?;
+    assert_eq!(dec, msg);
+
+    let e = HpkeAuthMessage::decrypt_owned_with_aad(&enc, &receiver_combined_key, b"seq:2")
+        .unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+    Ok(())
+}
+
+#[test]
+fn test_decryption_error_on_backend_mismatch() -> Result<(), Error> {
+    combined_keys_gen!(sender_combined_key, receiver_combined_key);
+
+    let enc = HpkeBaseMessage("base".into()).encrypt(&sender_combined_key)?;
+    let e = HpkeAuthMessage::decrypt_owned(&enc, &receiver_combined_key).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+    let e = ChaChaBoxMessage::decrypt_owned(&enc, &receiver_combined_key).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+
+    let enc = ChaChaBoxMessage("chacha box".into()).encrypt(&sender_combined_key)?;
+    let e = HpkeBaseMessage::decrypt_owned(&enc, &receiver_combined_key).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+    Ok(())
+}
@@ -15,14 +15,14 @@ use serde_encrypt::AsSharedKey;
 #[derive(PartialEq, Debug, Serialize, Deserialize)]
 struct Message(String);
 
+impl SerdeEncryptSharedKeyDeterministic for Message {
+    type S = BincodeSerializer<Self>;
+}
+
 #[test]
 fn test_serde_encrypt_shared_key_deterministic() {
     let shared_key = SharedKey::generate();
 
-    impl SerdeEncryptSharedKeyDeterministic for Message {
-        type S = BincodeSerializer<Self>;
-    }
-
     let msg1 = Message("same message".into());
     let msg2 = Message("same message".into());
     let msg3 = Message("same? message".into());
@@ -33,4 +33,7 @@ fn test_serde_encrypt_shared_key_deterministic() {
 
     assert_eq!(encrypted1, encrypted2);
     assert_ne!(encrypted2, encrypted3);
+
+    // different plain-texts must not share nonce
+    assert_ne!(encrypted2.nonce(), encrypted3.nonce());
 }
@@ -0,0 +1,103 @@
+//! Test if SerdeEncryptSealedBox:
+//!
+//! - is decrypted by receiver's key-pair only (no sender key-pair),
+//! - produces different cipher-text (and ephemeral public key) for each message,
+//! - detects tampered ephemeral public key.
+
+mod test_util;
+
+use serde::{Deserialize, Serialize};
+use serde_encrypt::{
+    key::key_pair::ReceiverKeyPair, serialize::impls::BincodeSerializer,
+    traits::SerdeEncryptSealedBox, EncryptedMessage, EncryptionMode, Error, ErrorKind,
+    ReceiverKeyPairCore,
+};
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct CrashReport {
+    app: String,
+    backtrace: Vec<String>,
+}
+
+impl SerdeEncryptSealedBox for CrashReport {
+    type S = BincodeSerializer<Self>;
+}
+
+fn report() -> CrashReport {
+    CrashReport {
+        app: "my-app".into(),
+        backtrace: vec!["main".into(), "panic".into()],
+    }
+}
+
+#[test]
+fn test_sealed_box() -> Result<(), Error> {
+    let receiver_key_pair = ReceiverKeyPair::generate();
+
+    let enc = report().encrypt(receiver_key_pair.public_key())?;
+    assert_eq!(enc.header().unwrap().mode(), EncryptionMode::SealedBox);
+
+    let bin = enc.serialize_versioned();
+    let enc = EncryptedMessage::deserialize_versioned(bin)?;
+    let dec = CrashReport::decrypt_owned(&enc, &receiver_key_pair)?;
+    assert_eq!(dec, report());
+
+    Ok(())
+}
+
+#[test]
+fn test_sealed_box_aad() -> Result<(), Error> {
+    let receiver_key_pair = ReceiverKeyPair::generate();
+
+    let enc = report().encrypt_with_aad(receiver_key_pair.public_key(), b"form:1")?;
+    let dec = CrashReport::decrypt_owned_with_aad(&enc, &receiver_key_pair, b"form:1")?;
+    assert_eq!(dec, report());
+
+    let e = CrashReport::decrypt_owned_with_aad(&enc, &receiver_key_pair, b"form:2").unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+
+    Ok(())
+}
+
+#[test]
+fn test_decryption_error_on_other_receiver() -> Result<(), Error> {
+    let receiver_key_pair = ReceiverKeyPair::generate();
+    let other_key_pair = ReceiverKeyPair::generate();
+
+    let enc = report().encrypt(receiver_key_pair.public_key())?;
+    let e = CrashReport::decrypt_owned(&enc, &other_key_pair).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+
+    Ok(())
+}
+
+#[test]
+fn test_ephemeral_key_per_message() -> Result<(), Error> {
+    let receiver_key_pair = ReceiverKeyPair::generate();
+
+    let enc1 = report().encrypt(receiver_key_pair.public_key())?;
+    let enc2 = report().encrypt(receiver_key_pair.public_key())?;
+    assert_ne!(enc1.encrypted()[..32], enc2.encrypted()[..32]);
+    assert_ne!(enc1.encrypted(), enc2.encrypted());
+
+    Ok(())
+}
+
+#[test]
+fn test_decryption_error_on_tampered_ephemeral_key() -> Result<(), Error> {
+    let receiver_key_pair = ReceiverKeyPair::generate();
+
+    let enc = report().encrypt(receiver_key_pair.public_key())?;
+    let mut bin = enc.serialize();
+    // headerless binary: nonce (24 bytes) || ephemeral public key (32 bytes) || cipher-text
+    bin[24] ^= 1;
+    let tampered = EncryptedMessage::deserialize(bin)?;
+    let e = CrashReport::decrypt_owned(&tampered, &receiver_key_pair).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+
+    let truncated = EncryptedMessage::deserialize(vec![0u8; 24 + 31])?;
+    let e = CrashReport::decrypt_owned(&truncated, &receiver_key_pair).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+
+    Ok(())
+}
@@ -2,50 +2,213 @@
 
 use serde::{Deserialize, Serialize};
 use serde_encrypt::{
-    serialize::impls::CborSerializer, shared_key::SharedKey, traits::SerdeEncryptSharedKey,
-    AsSharedKey, Error,
+    aead::XChaCha20Poly1305, shared_key::SharedKey, traits::SerdeEncryptSharedKey, AsSharedKey,
+    Error,
 };
 
 const RAW_MSG: [u8; 1000] = [42; 1000];
 
-#[test]
-fn test_cbor_serializer() -> Result<(), Error> {
-    #[derive(PartialEq, Debug, Serialize, Deserialize)]
-    struct Message(Vec<u8>);
+cfg_if::cfg_if! {
+    if #[cfg(feature = "std")] {
+        use serde_encrypt::serialize::impls::{BincodeSerializer, RmpSerializer};
+
+        #[test]
+        fn test_bincode_serializer() -> Result<(), Error> {
+            #[derive(PartialEq, Debug, Serialize, Deserialize)]
+            struct Message(Vec<u8>);
+
+            impl SerdeEncryptSharedKey for Message {
+                type S = BincodeSerializer<Self>;
+                type A = XChaCha20Poly1305;
+            }
+
+            let shared_key = SharedKey::generate();
+
+            let msg = Message(RAW_MSG.to_vec());
+
+            let enc_msg = msg.encrypt(&shared_key)?;
+            let dec_msg = Message::decrypt_owned(&enc_msg, &shared_key)?;
+
+            eprintln!(
+                "[BincodeSerializer] {} bytes in plain-text ; {} bytes in cipher-text.",
+                msg.0.len(),
+                enc_msg.len()
+            );
+
+            assert_eq!(dec_msg, msg);
+
+            Ok(())
+        }
+
+        #[test]
+        fn test_rmp_serializer() -> Result<(), Error> {
+            #[derive(PartialEq, Debug, Serialize, Deserialize)]
+            struct Message(Vec<u8>);
+
+            impl SerdeEncryptSharedKey for Message {
+                type S = RmpSerializer<Self>;
+                type A = XChaCha20Poly1305;
+            }
+
+            let shared_key = SharedKey::generate();
+
+            let msg = Message(RAW_MSG.to_vec());
+
+            let enc_msg = msg.encrypt(&shared_key)?;
+            let dec_msg = Message::decrypt_owned(&enc_msg, &shared_key)?;
+
+            eprintln!(
+                "[RmpSerializer] {} bytes in plain-text ; {} bytes in cipher-text.",
+                msg.0.len(),
+                enc_msg.len()
+            );
+
+            assert_eq!(dec_msg, msg);
+
+            Ok(())
+        }
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "serde_cbor")] {
+        #[allow(deprecated)]
+        use serde_encrypt::serialize::impls::CborSerializer;
+
+        #[test]
+        #[allow(deprecated)]
+        fn test_cbor_serializer() -> Result<(), Error> {
+            #[derive(PartialEq, Debug, Serialize, Deserialize)]
+            struct Message(Vec<u8>);
+
+            impl SerdeEncryptSharedKey for Message {
+                type S = CborSerializer<Self>;
+                type A = XChaCha20Poly1305;
+            }
+
+            let shared_key = SharedKey::generate();
+
+            let msg = Message(RAW_MSG.to_vec());
+
+            let enc_msg = msg.encrypt(&shared_key)?;
+            let dec_msg = Message::decrypt_owned(&enc_msg, &shared_key)?;
 
-    impl SerdeEncryptSharedKey for Message {
-        type S = CborSerializer<Self>;
+            eprintln!(
+                "[CborSerializer] {} bytes in plain-text ; {} bytes in cipher-text.",
+                msg.0.len(),
+                enc_msg.len()
+            );
+
+            assert_eq!(dec_msg, msg);
+
+            Ok(())
+        }
     }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "ciborium")] {
+        use serde_encrypt::serialize::impls::CiboriumSerializer;
 
-    let shared_key = SharedKey::generate();
+        #[test]
+        fn test_ciborium_serializer() -> Result<(), Error> {
+            #[derive(PartialEq, Debug, Serialize, Deserialize)]
+            struct Message(Vec<u8>);
 
-    let msg = Message(RAW_MSG.to_vec());
+            impl SerdeEncryptSharedKey for Message {
+                type S = CiboriumSerializer<Self>;
+                type A = XChaCha20Poly1305;
+            }
 
-    let enc_msg = msg.encrypt(&shared_key)?;
-    let dec_msg = Message::decrypt_owned(&enc_msg, &shared_key)?;
+            let shared_key = SharedKey::generate();
 
-    eprintln!(
-        "[CborSerializer] {} bytes in plain-text ; {} bytes in cipher-text.",
-        msg.0.len(),
-        enc_msg.len()
-    );
+            let msg = Message(RAW_MSG.to_vec());
 
-    assert_eq!(dec_msg, msg);
+            let enc_msg = msg.encrypt(&shared_key)?;
+            let dec_msg = Message::decrypt_owned(&enc_msg, &shared_key)?;
 
-    Ok(())
+            eprintln!(
+                "[CiboriumSerializer] {} bytes in plain-text ; {} bytes in cipher-text.",
+                msg.0.len(),
+                enc_msg.len()
+            );
+
+            assert_eq!(dec_msg, msg);
+
+            Ok(())
+        }
+    }
 }
 
 cfg_if::cfg_if! {
-    if #[cfg(feature = "std")] {
-        use serde_encrypt::serialize::impls::BincodeSerializer;
+    if #[cfg(all(feature = "serde_cbor", feature = "ciborium"))] {
+        use serde_encrypt::serialize::TypedSerialized;
 
         #[test]
-        fn test_bincode_serializer() -> Result<(), Error> {
+        #[allow(deprecated)]
+        fn test_cbor_cipher_text_decrypted_by_ciborium_serializer() -> Result<(), Error> {
+            #[derive(PartialEq, Debug, Serialize, Deserialize)]
+            struct Params;
+
+            #[derive(PartialEq, Debug, Serialize, Deserialize)]
+            #[serde(tag = "type")]
+            enum Message {
+                Request { id: String, params: Params },
+                Response { id: String, result: Option<f64> },
+            }
+
+            impl SerdeEncryptSharedKey for Message {
+                type S = CborSerializer<Self>;
+                type A = XChaCha20Poly1305;
+            }
+
+            let shared_key = SharedKey::generate();
+
+            for msg in [
+                Message::Request {
+                    id: "1".into(),
+                    params: Params,
+                },
+                Message::Response {
+                    id: "1".into(),
+                    result: None,
+                },
+                Message::Response {
+                    id: "2".into(),
+                    result: Some(0.1),
+                },
+            ] {
+                // cipher-text made before switching to CiboriumSerializer
+                let enc_msg = msg.encrypt(&shared_key)?;
+
+                let decrypted = Message::decrypt_ref(&enc_msg, &shared_key)?;
+                let decrypted = CiboriumSerializer::<Message>::new(decrypted.into_vec());
+                assert_eq!(decrypted.deserialize()?, msg);
+
+                let serialized = CiboriumSerializer::serialize(&msg)?;
+                assert_eq!(
+                    serialized.as_slice(),
+                    CborSerializer::serialize(&msg)?.as_slice()
+                );
+            }
+
+            Ok(())
+        }
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "json")] {
+        use serde_encrypt::serialize::impls::JsonSerializer;
+
+        #[test]
+        fn test_json_serializer() -> Result<(), Error> {
             #[derive(PartialEq, Debug, Serialize, Deserialize)]
             struct Message(Vec<u8>);
 
             impl SerdeEncryptSharedKey for Message {
-                type S = BincodeSerializer<Self>;
+                type S = JsonSerializer<Self>;
+                type A = XChaCha20Poly1305;
             }
 
             let shared_key = SharedKey::generate();
@@ -56,7 +219,7 @@ cfg_if::cfg_if! {
             let dec_msg = Message::decrypt_owned(&enc_msg, &shared_key)?;
 
             eprintln!(
-                "[BincodeSerializer] {} bytes in plain-text ; {} bytes in cipher-text.",
+                "[JsonSerializer] {} bytes in plain-text ; {} bytes in cipher-text.",
                 msg.0.len(),
                 enc_msg.len()
             );
@@ -0,0 +1,163 @@
+//! Test if SerdeEncryptSignedPublicKey:
+//!
+//! - authenticates sender's identity by Ed25519 signature,
+//! - fails with SignatureError when verifying key differs,
+//! - derives verifying key from signing key,
+//! - fails with DecryptionError when cipher-text is not produced in sign-then-encrypt mode.
+
+mod test_util;
+
+use serde::{Deserialize, Serialize};
+use serde_encrypt::{
+    key::signing_key_pair::SigningKeyPair,
+    public_key_backend::ChaChaBox,
+    serialize::impls::BincodeSerializer,
+    traits::{SerdeEncryptPublicKey, SerdeEncryptSignedPublicKey},
+    EncryptedMessage, EncryptionMode, Error, ErrorKind, SigningKeyPairCore, VerifyingKey,
+};
+use test_util::serde_encrypt_public_key::*;
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct Command {
+    name: String,
+    args: Vec<String>,
+}
+
+impl SerdeEncryptSignedPublicKey for Command {
+    type S = BincodeSerializer<Self>;
+}
+impl SerdeEncryptPublicKey for Command {
+    type S = BincodeSerializer<Self>;
+    type B = ChaChaBox;
+}
+
+fn command() -> Command {
+    Command {
+        name: "restart".into(),
+        args: vec!["api-server".into()],
+    }
+}
+
+#[test]
+fn test_signed_public_key() -> Result<(), Error> {
+    combined_keys_gen!(sender_combined_key, receiver_combined_key);
+    let signing_key_pair = SigningKeyPair::generate();
+
+    let enc =
+        SerdeEncryptSignedPublicKey::encrypt(&command(), &sender_combined_key, &signing_key_pair)?;
+    assert_eq!(
+        enc.header().unwrap().mode(),
+        EncryptionMode::SignedPublicKey
+    );
+
+    let bin = enc.serialize_versioned();
+    let enc = EncryptedMessage::deserialize_versioned(bin)?;
+
+    // verifying key is distributed as bytes
+    let verifying_key = VerifyingKey::from_bytes(&signing_key_pair.verifying_key().to_bytes())?;
+    let dec = <Command as SerdeEncryptSignedPublicKey>::decrypt_owned(
+        &enc,
+        &receiver_combined_key,
+        &verifying_key,
+    )?;
+    assert_eq!(dec, command());
+
+    Ok(())
+}
+
+#[test]
+fn test_signed_public_key_aad() -> Result<(), Error> {
+    combined_keys_gen!(sender_combined_key, receiver_combined_key);
+    let signing_key_pair = SigningKeyPair::generate();
+    let verifying_key = signing_key_pair.verifying_key();
+
+    let enc = SerdeEncryptSignedPublicKey::encrypt_with_aad(
+        &command(),
+        &sender_combined_key,
+        &signing_key_pair,
+        b"seq:1",
+    )?;
+    let dec = <Command as SerdeEncryptSignedPublicKey>::decrypt_owned_with_aad(
+        &enc,
+        &receiver_combined_key,
+        verifying_key,
+        b"seq:1",
+    )?;
+    assert_eq!(dec, command());
+
+    let e = <Command as SerdeEncryptSignedPublicKey>::decrypt_owned_with_aad(
+        &enc,
+        &receiver_combined_key,
+        verifying_key,
+        b"seq:2",
+    )
+    .unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+
+    Ok(())
+}
+
+#[test]
+fn test_signature_error_on_other_verifying_key() -> Result<(), Error> {
+    combined_keys_gen!(sender_combined_key, receiver_combined_key);
+    let signing_key_pair = SigningKeyPair::generate();
+    let impostor_key_pair = SigningKeyPair::generate();
+
+    let enc =
+        SerdeEncryptSignedPublicKey::encrypt(&command(), &sender_combined_key, &impostor_key_pair)?;
+    let e = <Command as SerdeEncryptSignedPublicKey>::decrypt_owned(
+        &enc,
+        &receiver_combined_key,
+        signing_key_pair.verifying_key(),
+    )
+    .unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::SignatureError);
+
+    Ok(())
+}
+
+#[test]
+fn test_verifying_key_derived_from_signing_key() -> Result<(), Error> {
+    combined_keys_gen!(sender_combined_key, receiver_combined_key);
+    let signing_key_pair = SigningKeyPair::generate();
+
+    let restored = SigningKeyPair::new(signing_key_pair.private_key().clone());
+    assert_eq!(restored.verifying_key(), signing_key_pair.verifying_key());
+
+    let enc = SerdeEncryptSignedPublicKey::encrypt(&command(), &sender_combined_key, &restored)?;
+    let dec = <Command as SerdeEncryptSignedPublicKey>::decrypt_owned(
+        &enc,
+        &receiver_combined_key,
+        signing_key_pair.verifying_key(),
+    )?;
+    assert_eq!(dec, command());
+
+    Ok(())
+}
+
+#[test]
+fn test_decryption_error_on_unsigned_message() -> Result<(), Error> {
+    combined_keys_gen!(sender_combined_key, receiver_combined_key);
+    let signing_key_pair = SigningKeyPair::generate();
+
+    let enc = SerdeEncryptPublicKey::encrypt(&command(), &sender_combined_key)?;
+    let e = <Command as SerdeEncryptSignedPublicKey>::decrypt_owned(
+        &enc,
+        &receiver_combined_key,
+        signing_key_pair.verifying_key(),
+    )
+    .unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+
+    Ok(())
+}
+
+#[test]
+fn test_signing_key_pair_debug_is_redacted() {
+    let signing_key_pair = SigningKeyPair::generate();
+    let debug = format!("{:?}", signing_key_pair);
+    assert!(debug.contains("SigningPrivateKey(...)"));
+
+    let e = VerifyingKey::from_bytes(&[0u8; 31]).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::SignatureError);
+}
@@ -0,0 +1,90 @@
+//! Test if AsSharedKey::derive_subkey:
+//!
+//! - derives the same subkey from the same master key and context (HKDF-SHA256),
+//! - derives independent subkeys for different contexts and master keys,
+//! - makes subkeys which cannot decrypt messages for other purposes.
+
+mod test_util;
+
+use serde::{Deserialize, Serialize};
+use serde_encrypt::{
+    aead::XChaCha20Poly1305, serialize::impls::BincodeSerializer, shared_key::SharedKey,
+    traits::SerdeEncryptSharedKey, AsSharedKey, Error, ErrorKind,
+};
+use test_util::serde_encrypt_shared_key::*;
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct Email(String);
+impl SerdeEncryptSharedKey for Email {
+    type S = BincodeSerializer<Self>;
+    type A = XChaCha20Poly1305;
+}
+
+fn master_key() -> SharedKey {
+    let mut key = [0u8; 32];
+    for (i, b) in key.iter_mut().enumerate() {
+        *b = i as u8;
+    }
+    SharedKey::new(key)
+}
+
+#[test]
+fn test_derive_subkey_known_answer() {
+    // HKDF-SHA256(IKM = 0x00..0x1f, salt = none, info = "serde-encrypt subkey v1" || context)
+    let pii_column = [
+        0xec, 0xf1, 0x75, 0x4c, 0x1f, 0xa2, 0xe1, 0xe2, 0xfd, 0x0e, 0x04, 0xbc, 0x92, 0x5a, 0xf5,
+        0x79, 0xd6, 0x48, 0x11, 0xd8, 0xb8, 0x0b, 0x97, 0x58, 0xed, 0x3d, 0x84, 0x22, 0xec, 0xaa,
+        0x8c, 0x26,
+    ];
+    let session_cookie = [
+        0x38, 0x0f, 0x82, 0xdc, 0x26, 0x97, 0x67, 0x10, 0x5a, 0x59, 0x99, 0x3d, 0x8c, 0xae, 0x80,
+        0x50, 0x43, 0xcd, 0x99, 0x86, 0x84, 0x62, 0x74, 0x2d, 0xaa, 0x46, 0x97, 0xe9, 0x0c, 0xdc,
+        0x3c, 0x16,
+    ];
+
+    let master_key = master_key();
+    assert_eq!(
+        master_key.derive_subkey(b"pii-column"),
+        SharedKey::new(pii_column)
+    );
+    assert_eq!(
+        master_key.derive_subkey(b"session-cookie"),
+        SharedKey::new(session_cookie)
+    );
+}
+
+#[test]
+fn test_subkeys_are_independent() {
+    let master_key = SharedKey::generate();
+    let pii = master_key.derive_subkey(b"pii-column");
+    let cookie = master_key.derive_subkey(b"session-cookie");
+    let backup = master_key.derive_subkey(b"backup");
+
+    assert_eq!(pii, master_key.derive_subkey(b"pii-column"));
+    assert_ne!(pii, cookie);
+    assert_ne!(pii, backup);
+    assert_ne!(cookie, backup);
+    assert_ne!(pii, master_key);
+    assert_ne!(pii, pii.derive_subkey(b"pii-column"));
+
+    let other_master_key = SharedKey::generate();
+    assert_ne!(pii, other_master_key.derive_subkey(b"pii-column"));
+}
+
+#[test]
+fn test_subkey_encryption() -> Result<(), Error> {
+    let master_key = SharedKey::generate();
+    let pii = master_key.derive_subkey(b"pii-column");
+    let backup = master_key.derive_subkey(b"backup");
+
+    let msg = Email("alice@example.com".into());
+    shared_key_enc_dec_assert_eq(&msg, &pii)?;
+
+    let enc = msg.encrypt(&pii)?;
+    let e = Email::decrypt_owned(&enc, &backup).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+    let e = Email::decrypt_owned(&enc, &master_key).unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+
+    Ok(())
+}
@@ -0,0 +1,123 @@
+//! Test if EncryptedMessage:
+//!
+//! - carries header telling algorithm and encryption mode via `serialize_versioned()`,
+//! - reads headerless binary as version 0.
+
+mod test_util;
+
+use serde::{Deserialize, Serialize};
+use serde_encrypt::{
+    aead::XChaCha20Poly1305,
+    public_key_backend::ChaChaBox,
+    serialize::impls::BincodeSerializer,
+    shared_key::SharedKey,
+    traits::{SerdeEncryptPublicKey, SerdeEncryptSharedKey, SerdeEncryptSharedKeyDeterministic},
+    Algorithm, AsSharedKey, EncryptedMessage, EncryptionMode, Error, ErrorKind,
+};
+use test_util::serde_encrypt_public_key::*;
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct Message(String);
+
+impl SerdeEncryptPublicKey for Message {
+    type S = BincodeSerializer<Self>;
+    type B = ChaChaBox;
+}
+impl SerdeEncryptSharedKey for Message {
+    type S = BincodeSerializer<Self>;
+    type A = XChaCha20Poly1305;
+}
+impl SerdeEncryptSharedKeyDeterministic for Message {
+    type S = BincodeSerializer<Self>;
+}
+
+fn assert_header(encrypted_message: &EncryptedMessage, algorithm: Algorithm, mode: EncryptionMode) {
+    let header = encrypted_message
+        .header()
+        .expect("versioned message has header");
+    assert_eq!(header.algorithm(), algorithm);
+    assert_eq!(header.mode(), mode);
+}
+
+#[test]
+fn test_versioned_shared_key() -> Result<(), Error> {
+    let shared_key = SharedKey::generate();
+    let msg = Message("versioned".into());
+
+    let bin = SerdeEncryptSharedKey::encrypt(&msg, &shared_key)?.serialize_versioned();
+    let enc = EncryptedMessage::deserialize_versioned(bin)?;
+    assert_header(
+        &enc,
+        Algorithm::XChaCha20Poly1305,
+        EncryptionMode::SharedKey,
+    );
+
+    let dec = <Message as SerdeEncryptSharedKey>::decrypt_owned(&enc, &shared_key)?;
+    assert_eq!(dec, msg);
+    Ok(())
+}
+
+#[test]
+fn test_versioned_shared_key_deterministic() -> Result<(), Error> {
+    let shared_key = SharedKey::generate();
+    let msg = Message("versioned".into());
+
+    let bin = SerdeEncryptSharedKeyDeterministic::encrypt(&msg, &shared_key)?.serialize_versioned();
+    let enc = EncryptedMessage::deserialize_versioned(bin)?;
+    assert_header(
+        &enc,
+        Algorithm::XChaCha20Poly1305Siv,
+        EncryptionMode::SharedKeyDeterministic,
+    );
+
+    let dec = <Message as SerdeEncryptSharedKeyDeterministic>::decrypt_owned(&enc, &shared_key)?;
+    assert_eq!(dec, msg);
+    Ok(())
+}
+
+#[test]
+fn test_versioned_public_key() -> Result<(), Error> {
+    combined_keys_gen!(sender_combined_key, receiver_combined_key);
+    let msg = Message("versioned".into());
+
+    let bin = SerdeEncryptPublicKey::encrypt(&msg, &sender_combined_key)?.serialize_versioned();
+    let enc = EncryptedMessage::deserialize_versioned(bin)?;
+    assert_header(
+        &enc,
+        Algorithm::XChaCha20Poly1305,
+        EncryptionMode::PublicKey,
+    );
+
+    let dec = <Message as SerdeEncryptPublicKey>::decrypt_owned(&enc, &receiver_combined_key)?;
+    assert_eq!(dec, msg);
+    Ok(())
+}
+
+#[test]
+fn test_headerless_message_is_readable_as_version_0() -> Result<(), Error> {
+    let shared_key = SharedKey::generate();
+    let msg = Message("headerless".into());
+
+    let bin = SerdeEncryptSharedKey::encrypt(&msg, &shared_key)?.serialize();
+    let enc = EncryptedMessage::deserialize_versioned(bin)?;
+    assert_eq!(enc.version(), 0);
+    assert!(enc.header().is_none());
+
+    let dec = <Message as SerdeEncryptSharedKey>::decrypt_owned(&enc, &shared_key)?;
+    assert_eq!(dec, msg);
+    Ok(())
+}
+
+#[test]
+fn test_decryption_error_on_mode_mismatch() -> Result<(), Error> {
+    let shared_key = SharedKey::generate();
+    let msg = Message("mode mismatch".into());
+
+    let bin = SerdeEncryptSharedKey::encrypt(&msg, &shared_key)?.serialize_versioned();
+    let enc = EncryptedMessage::deserialize_versioned(bin)?;
+
+    let e = <Message as SerdeEncryptSharedKeyDeterministic>::decrypt_owned(&enc, &shared_key)
+        .unwrap_err();
+    assert_eq!(e.kind(), &ErrorKind::DecryptionError);
+    Ok(())
+}
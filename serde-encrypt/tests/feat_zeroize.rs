@@ -0,0 +1,50 @@
+//! Test if:
+//!
+//! - SharedKey
+//! - SenderPrivateKey / ReceiverPrivateKey
+//! - Serializers
+//!
+//! are zeroized and do not leak key material via Debug.
+
+use serde_encrypt::{
+    key::key_pair::{ReceiverKeyPair, SenderKeyPair},
+    serialize::{impls::BincodeSerializer, TypedSerialized},
+    shared_key::SharedKey,
+    AsSharedKey, ReceiverKeyPairCore, SenderKeyPairCore,
+};
+use zeroize::Zeroize;
+
+#[test]
+fn test_shared_key_zeroize() {
+    let mut shared_key = SharedKey::new([42u8; 32]);
+    shared_key.zeroize();
+    assert_eq!(shared_key.as_slice(), &[0u8; 32]);
+}
+
+#[test]
+fn test_shared_key_debug_redacted() {
+    let shared_key = SharedKey::new([42u8; 32]);
+    let debug = format!("{:?}", shared_key);
+    assert_eq!(debug, "SharedKey(...)");
+    assert!(!debug.contains("42"));
+}
+
+#[test]
+fn test_private_key_zeroize() {
+    let sender_key_pair = SenderKeyPair::generate();
+    let mut sender_private_key = sender_key_pair.private_key().clone();
+    sender_private_key.zeroize();
+    assert_eq!(sender_private_key.as_ref().to_bytes(), [0u8; 32]);
+
+    let receiver_key_pair = ReceiverKeyPair::generate();
+    let mut receiver_private_key = receiver_key_pair.private_key().clone();
+    receiver_private_key.zeroize();
+    assert_eq!(receiver_private_key.as_ref().to_bytes(), [0u8; 32]);
+}
+
+#[test]
+fn test_serializer_zeroize() {
+    let mut serialized = BincodeSerializer::<String>::new(b"secret".to_vec());
+    serialized.zeroize();
+    assert!(serialized.as_slice().iter().all(|b| *b == 0));
+}
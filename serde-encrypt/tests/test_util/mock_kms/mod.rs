@@ -0,0 +1,116 @@
+#![allow(dead_code)]
+
+//! In-memory mock of an external KMS for envelope encryption tests.
+//!
+//! Like cloud KMSs, master keys never leave the KMS and a wrapped DEK records which master key wrapped it,
+//! so that the KMS itself (not the caller) chooses the key to unwrap.
+
+extern crate alloc;
+
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::cell::Cell;
+
+use serde_encrypt::{
+    envelope::{KeyDecryptionKey, KeyEncryptionKey},
+    shared_key::SharedKey,
+    AsSharedKey, Error,
+};
+
+#[derive(Debug, Default)]
+pub struct MockKms {
+    master_keys: BTreeMap<String, SharedKey>,
+    disabled: BTreeSet<String>,
+    n_wrap: Cell<usize>,
+    n_unwrap: Cell<usize>,
+}
+
+impl MockKms {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a master key and returns a handle to wrap DEKs by it.
+    pub fn create_key(&mut self, key_id: &str) -> MockKmsKey<'_> {
+        self.master_keys
+            .insert(key_id.to_string(), SharedKey::generate());
+        self.key(key_id)
+    }
+
+    pub fn key(&self, key_id: &str) -> MockKmsKey<'_> {
+        MockKmsKey {
+            kms: self,
+            key_id: key_id.to_string(),
+        }
+    }
+
+    /// Disabled master key neither wraps nor unwraps.
+    pub fn disable_key(&mut self, key_id: &str) {
+        self.disabled.insert(key_id.to_string());
+    }
+
+    pub fn n_wrap(&self) -> usize {
+        self.n_wrap.get()
+    }
+
+    pub fn n_unwrap(&self) -> usize {
+        self.n_unwrap.get()
+    }
+
+    fn master_key(&self, key_id: &str) -> Option<&SharedKey> {
+        if self.disabled.contains(key_id) {
+            None
+        } else {
+            self.master_keys.get(key_id)
+        }
+    }
+}
+
+/// Wrapped DEK: `key ID length (u8) || key ID || DEK wrapped by master key`
+impl KeyDecryptionKey for MockKms {
+    fn unwrap_key(&self, wrapped_dek: &[u8]) -> Result<SharedKey, Error> {
+        self.n_unwrap.set(self.n_unwrap.get() + 1);
+
+        let (key_id_len, rest) = wrapped_dek
+            .split_first()
+            .ok_or_else(|| Error::decryption_error("empty wrapped DEK"))?;
+        let key_id_len = *key_id_len as usize;
+        if rest.len() < key_id_len {
+            return Err(Error::decryption_error("truncated key ID"));
+        }
+        let (key_id, wrapped_dek) = rest.split_at(key_id_len);
+        let key_id = core::str::from_utf8(key_id)
+            .map_err(|_| Error::decryption_error("key ID is not UTF-8"))?;
+
+        let master_key = self
+            .master_key(key_id)
+            .ok_or_else(|| Error::decryption_error("master key is not found or disabled"))?;
+        master_key.unwrap_key(wrapped_dek)
+    }
+}
+
+#[derive(Debug)]
+pub struct MockKmsKey<'k> {
+    kms: &'k MockKms,
+    key_id: String,
+}
+
+impl KeyEncryptionKey for MockKmsKey<'_> {
+    fn wrap_key(&self, dek: &SharedKey) -> Result<Vec<u8>, Error> {
+        self.kms.n_wrap.set(self.kms.n_wrap.get() + 1);
+
+        let master_key = self
+            .kms
+            .master_key(&self.key_id)
+            .ok_or_else(|| Error::encryption_error("master key is not found or disabled"))?;
+
+        let mut wrapped = vec![self.key_id.len() as u8];
+        wrapped.extend_from_slice(self.key_id.as_bytes());
+        wrapped.extend(master_key.wrap_key(dek)?);
+        Ok(wrapped)
+    }
+}
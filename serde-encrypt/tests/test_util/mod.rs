@@ -5,12 +5,10 @@ extern crate alloc;
 use alloc::vec::Vec;
 use core::fmt::Debug;
 
+pub mod mock_kms;
 pub mod serde_encrypt_public_key;
 pub mod serde_encrypt_shared_key;
 
-pub use serde_encrypt_public_key::*;
-pub use serde_encrypt_shared_key::*;
-
 pub fn assert_no_duplicate<T>(generator: impl Fn() -> T, n_generate: usize)
 where
     T: PartialEq + Debug,
@@ -14,16 +14,16 @@ use serde_encrypt_core::encrypt::{
     plain_message_public_key::PlainMessagePublicKeyCore,
     plain_message_shared_key::PlainMessageSharedKeyCore,
 };
-use test_util::*;
+use test_util::serde_encrypt_public_key::*;
 
 #[test]
 fn test_decrypt_with_wrong_public_key() -> Result<(), Error> {
     combined_keys_gen!(sender_combined_key1, _r);
     combined_keys_gen!(_s, receiver_combined_key2);
 
-    let plain_msg = PlainMessagePublicKey::new(b"abc".to_vec());
+    let plain_msg: PlainMessagePublicKey = PlainMessagePublicKey::new(b"abc".to_vec());
     let enc_msg = plain_msg.encrypt(&sender_combined_key1)?;
-    let e = PlainMessagePublicKey::decrypt(&enc_msg, &receiver_combined_key2).unwrap_err();
+    let e = <PlainMessagePublicKey>::decrypt(&enc_msg, &receiver_combined_key2).unwrap_err();
     assert_eq!(e.kind(), &ErrorKind::DecryptionError);
 
     Ok(())
@@ -34,9 +34,9 @@ fn test_decrypt_with_wrong_shared_key() -> Result<(), Error> {
     let shared_key1 = SharedKey::generate();
     let shared_key2 = SharedKey::generate();
 
-    let plain_msg = PlainMessageSharedKey::new(b"abc".to_vec());
+    let plain_msg: PlainMessageSharedKey = PlainMessageSharedKey::new(b"abc".to_vec());
     let enc_msg = plain_msg.encrypt(&shared_key1)?;
-    let e = PlainMessageSharedKey::decrypt(&enc_msg, &shared_key2).unwrap_err();
+    let e = <PlainMessageSharedKey>::decrypt(&enc_msg, &shared_key2).unwrap_err();
     assert_eq!(e.kind(), &ErrorKind::DecryptionError);
 
     Ok(())